@@ -0,0 +1,326 @@
+use crate::companion::cards::{resolve_card, save_card_map};
+use crate::decks::utils::{convert_cli_deck_list_to_core, load_deck_with_details};
+use crate::prelude::*;
+use clap_stdin::MaybeStdin;
+use color_eyre::owo_colors::OwoColorize;
+use mtg_core::collection::{
+    default_collection_path, parse_arena_collection, parse_collection_csv, Collection,
+    CollectionCard,
+};
+use prettytable::{Cell, Row};
+
+#[derive(Debug, clap::Parser)]
+pub struct App {
+    #[command(subcommand)]
+    pub command: SubCommands,
+}
+
+#[derive(Debug, clap::Parser)]
+pub enum SubCommands {
+    /// Import cards from a CSV export or an MTG Arena log
+    Import {
+        /// CSV file (Moxfield, Deckbox, ManaBox, Archidekt, ...) or Arena log with --arena, '-' for stdin
+        file: MaybeStdin<String>,
+
+        /// Read the file as an MTG Arena Player.log and import the Arena collection,
+        /// replacing the cards of the previous Arena import
+        #[clap(long)]
+        arena: bool,
+
+        /// Replace the collection instead of adding to it
+        #[clap(long)]
+        replace: bool,
+    },
+
+    /// Show the cards in the collection
+    Show {
+        /// Only show cards whose name contains this text
+        #[clap(long, short)]
+        name: Option<String>,
+
+        /// Only show cards from this set code
+        #[clap(long, short)]
+        set: Option<String>,
+
+        /// Only show cards of this rarity (common, uncommon, rare, mythic)
+        #[clap(long, short)]
+        rarity: Option<String>,
+
+        /// Output format (pretty, json or csv)
+        #[clap(long, default_value = "pretty")]
+        output: String,
+    },
+
+    /// Show the cards of a deck missing from the collection
+    Missing {
+        /// Deck list input (use '-' for stdin, provide deck list as string, deck ID, or omit to read from stdin)
+        #[clap(value_name = "DECK_LIST_OR_ID")]
+        input: Option<MaybeStdin<String>>,
+
+        /// Read deck list from file
+        #[clap(short, long, value_name = "FILE")]
+        file: Option<String>,
+
+        /// Output format (pretty or json)
+        #[clap(long, default_value = "pretty")]
+        output: String,
+    },
+}
+
+pub async fn run(app: App, global: crate::Global) -> Result<()> {
+    crate::companion::cards::init_resolver(&global);
+    match app.command {
+        SubCommands::Import {
+            file,
+            arena,
+            replace,
+        } => import(file, arena, replace, &global).await,
+        SubCommands::Show {
+            name,
+            set,
+            rarity,
+            output,
+        } => show(name, set, rarity, output),
+        SubCommands::Missing {
+            input,
+            file,
+            output,
+        } => missing(input, file, output, &global).await,
+    }
+}
+
+async fn import(
+    file: MaybeStdin<String>,
+    arena: bool,
+    replace: bool,
+    global: &crate::Global,
+) -> Result<()> {
+    // MaybeStdin already holds stdin's content, otherwise it is a path
+    let content = if std::path::Path::new(file.as_str()).is_file() {
+        std::fs::read_to_string(file.as_str())
+            .map_err(|e| eyre!("Failed to read '{}': {}", file.as_str(), e))?
+    } else {
+        file.to_string()
+    };
+
+    let cards = if arena {
+        import_arena(&content, global.verbose).await?
+    } else {
+        parse_collection_csv(&content)?
+    };
+
+    if cards.is_empty() {
+        return Err(eyre!("No cards found to import"));
+    }
+
+    let imported: u32 = cards.iter().map(|card| card.quantity).sum();
+    let mut collection = Collection::load_default()?;
+    if replace {
+        collection.replace(cards);
+    } else if arena {
+        collection.replace_arena(cards);
+    } else {
+        collection.merge(cards);
+    }
+    collection.save_default()?;
+
+    println!(
+        "Imported {} cards. The collection has {} cards ({} unique) in {}",
+        imported,
+        collection.total_cards(),
+        collection.unique_cards(),
+        default_collection_path().display()
+    );
+
+    Ok(())
+}
+
+/// Read the Arena collection from a log and look up each card id, in Arena's card database
+/// when it is installed and on Scryfall otherwise
+async fn import_arena(log: &str, verbose: bool) -> Result<Vec<CollectionCard>> {
+    let counts = parse_arena_collection(log).ok_or_else(|| {
+        eyre!("No collection found in the log. Open the collection in MTG Arena with detailed logs enabled and try again")
+    })?;
+
+    aeprintln!("Resolving {} Arena cards...", counts.len());
+
+    let mut cards = Vec::new();
+    let mut unresolved = 0;
+    for (index, (arena_id, quantity)) in counts.iter().enumerate() {
+        if (index + 1) % 100 == 0 {
+            aeprintln!("Resolved {} of {} cards...", index + 1, counts.len());
+        }
+        match resolve_card(*arena_id).await {
+            Ok(card) => cards.push(CollectionCard::from_arena_card(&card, *quantity)),
+            Err(e) => {
+                unresolved += 1;
+                if verbose {
+                    aeprintln!("Could not resolve Arena card {}: {}", arena_id, e);
+                }
+            }
+        }
+    }
+    save_card_map().await;
+
+    if unresolved > 0 {
+        aeprintln!("Skipped {} unknown Arena cards", unresolved);
+    }
+
+    Ok(cards)
+}
+
+fn show(
+    name: Option<String>,
+    set: Option<String>,
+    rarity: Option<String>,
+    output: String,
+) -> Result<()> {
+    let collection = Collection::load_default()?;
+    let cards: Vec<&CollectionCard> = collection
+        .cards
+        .iter()
+        .filter(|card| match &name {
+            Some(name) => card.name.to_lowercase().contains(&name.to_lowercase()),
+            None => true,
+        })
+        .filter(|card| match &set {
+            Some(set) => card
+                .set_code
+                .as_deref()
+                .is_some_and(|code| code.eq_ignore_ascii_case(set)),
+            None => true,
+        })
+        .filter(|card| match &rarity {
+            Some(rarity) => card
+                .rarity
+                .as_deref()
+                .is_some_and(|r| r.eq_ignore_ascii_case(rarity)),
+            None => true,
+        })
+        .collect();
+
+    match output.as_str() {
+        "json" => {
+            println!("{}", serde_json::to_string_pretty(&cards)?);
+            return Ok(());
+        }
+        "csv" => {
+            let mut writer = csv::Writer::from_writer(std::io::stdout());
+            writer.write_record([
+                "quantity",
+                "name",
+                "set_code",
+                "collector_number",
+                "foil",
+                "rarity",
+            ])?;
+            for card in &cards {
+                writer.write_record([
+                    card.quantity.to_string(),
+                    card.name.clone(),
+                    card.set_code.clone().unwrap_or_default(),
+                    card.collector_number.clone().unwrap_or_default(),
+                    card.foil.to_string(),
+                    card.rarity.clone().unwrap_or_default(),
+                ])?;
+            }
+            writer.flush()?;
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    if collection.cards.is_empty() {
+        println!(
+            "The collection is empty ({}). Import cards with 'mtg collection import'.",
+            default_collection_path().display()
+        );
+        return Ok(());
+    }
+
+    let mut table = new_table();
+    table.add_row(Row::new(vec![
+        Cell::new("Qty"),
+        Cell::new("Name"),
+        Cell::new("Set"),
+        Cell::new("Number"),
+        Cell::new("Rarity"),
+        Cell::new("Foil"),
+    ]));
+    for card in &cards {
+        table.add_row(Row::new(vec![
+            Cell::new(&card.quantity.to_string()),
+            Cell::new(&card.name),
+            Cell::new(&card.set_code.as_deref().unwrap_or("-").to_uppercase()),
+            Cell::new(card.collector_number.as_deref().unwrap_or("-")),
+            Cell::new(card.rarity.as_deref().unwrap_or("-")),
+            Cell::new(if card.foil { "yes" } else { "" }),
+        ]));
+    }
+    table.printstd();
+
+    println!(
+        "\n{} cards ({} unique) in the collection",
+        collection.total_cards(),
+        collection.unique_cards()
+    );
+    let rarities: Vec<String> = collection
+        .by_rarity()
+        .iter()
+        .map(|(rarity, count)| f!("{count} {rarity}"))
+        .collect();
+    println!("By rarity: {}", rarities.join(", "));
+
+    Ok(())
+}
+
+async fn missing(
+    input: Option<MaybeStdin<String>>,
+    file: Option<String>,
+    output: String,
+    global: &crate::Global,
+) -> Result<()> {
+    let deck_list = load_deck_with_details(input, file, global).await?;
+    let collection = Collection::load_default()?;
+    let report = collection.missing(&convert_cli_deck_list_to_core(&deck_list));
+
+    if output == "json" {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    if report.is_buildable() {
+        println!("{}", "You own every card of this deck".green());
+        return Ok(());
+    }
+
+    let mut table = new_table();
+    table.add_row(Row::new(vec![
+        Cell::new("Card Name"),
+        Cell::new("Needed"),
+        Cell::new("Owned"),
+        Cell::new("Missing"),
+        Cell::new("Rarity"),
+    ]));
+    for card in &report.cards {
+        table.add_row(Row::new(vec![
+            Cell::new(&card.name),
+            Cell::new(&card.needed.to_string()),
+            Cell::new(&card.owned.to_string()),
+            Cell::new(&card.missing.to_string()).style_spec("Fr"),
+            Cell::new(card.rarity.as_deref().unwrap_or("-")),
+        ]));
+    }
+    table.printstd();
+
+    println!(
+        "\n{} {} cards",
+        "Missing:".bold(),
+        report.total_missing.to_string().red()
+    );
+    for (rarity, count) in &report.by_rarity {
+        println!("  {}: {}", rarity, count);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use crate::prelude::*;
+use mtg_core::companion::cards::{default_card_map_path, find_card_database};
+use mtg_core::companion::{ArenaCard, ArenaCardDatabase, ArenaCardResolver};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tokio::sync::Mutex;
+
+/// One resolver for the whole run so every lookup shares the grpId map
+static RESOLVER: OnceLock<Mutex<ArenaCardResolver>> = OnceLock::new();
+
+/// Set up the resolver with the Scryfall options of the run, before the first lookup
+pub fn init_resolver(global: &crate::Global) {
+    RESOLVER.get_or_init(|| Mutex::new(new_resolver(global)));
+}
+
+/// Look up an Arena card by grpId: the saved map first, then Arena's card database, then
+/// Scryfall. Call [`save_card_map`] after a batch of lookups to remember the cards found.
+pub async fn resolve_card(grp_id: u32) -> Result<ArenaCard> {
+    let resolver = RESOLVER
+        .get()
+        .ok_or_else(|| eyre!("The Arena card resolver was not set up"))?;
+    resolver.lock().await.resolve(grp_id).await
+}
+
+/// Write the cards resolved since the last save to the grpId map, for later runs
+pub async fn save_card_map() {
+    let Some(resolver) = RESOLVER.get() else {
+        return;
+    };
+    if let Err(e) = resolver.lock().await.save() {
+        aeprintln!("Could not save the Arena card map: {}", e);
+    }
+}
+
+fn new_resolver(global: &crate::Global) -> ArenaCardResolver {
+    let mut resolver = ArenaCardResolver::new()
+        .with_map(&default_card_map_path())
+        .unwrap_or_else(|e| {
+            aeprintln!("Ignoring the Arena card map: {}", e);
+            ArenaCardResolver::new()
+        });
+
+    if let Some(path) = card_database_path() {
+        match ArenaCardDatabase::open(&path) {
+            Ok(database) => resolver = resolver.with_database(database),
+            Err(e) => aeprintln!("Ignoring the Arena card database: {}", e),
+        }
+    }
+
+    match global.create_scryfall_client() {
+        Ok(client) => resolver = resolver.with_client(client),
+        Err(e) => aeprintln!(
+            "Cards missing from the Arena database can't be looked up: {}",
+            e
+        ),
+    }
+    resolver
+}
+
+/// Arena's card database: `MTGA_CARD_DATABASE` (a file or the directory holding it) or the
+/// newest one of the installs found
+fn card_database_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("MTGA_CARD_DATABASE").map(PathBuf::from) {
+        return if path.is_dir() {
+            find_card_database(&path)
+        } else {
+            Some(path)
+        };
+    }
+    crate::companion::parse::get_card_database_dirs()
+        .iter()
+        .filter_map(|dir| find_card_database(dir))
+        .max_by_key(|path| {
+            std::fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .unwrap_or(std::time::SystemTime::UNIX_EPOCH)
+        })
+}
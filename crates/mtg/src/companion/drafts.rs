@@ -0,0 +1,236 @@
+use crate::companion::cards::{resolve_card, save_card_map};
+use crate::prelude::*;
+use color_eyre::owo_colors::OwoColorize;
+use mtg_core::companion::drafts::default_drafts_path;
+use mtg_core::companion::{parse_log, record_drafts, DraftHistory, DraftRecord};
+use prettytable::{Cell, Row};
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Commands {
+    /// List the recorded drafts
+    #[clap(name = "list")]
+    List {
+        /// Import drafts from old log files or directories first (the Arena logs by default)
+        #[clap(long, num_args = 0..)]
+        backfill: Option<Vec<String>>,
+
+        /// Number of recent drafts to list
+        #[clap(long, default_value = "20")]
+        limit: usize,
+
+        /// Output JSON instead of a table
+        #[clap(long)]
+        json: bool,
+    },
+    /// Show every pick of a draft with the pack it was made from
+    #[clap(name = "show")]
+    Show {
+        /// Draft id or a prefix of it (defaults to the latest draft)
+        id: Option<String>,
+
+        /// Output the draft as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Export the pool of a draft as a deck list, or the whole draft as a pick log
+    #[clap(name = "export")]
+    Export {
+        /// Draft id or a prefix of it (defaults to the latest draft)
+        id: Option<String>,
+
+        /// What to export: deck (the pool as a deck list) or json (the pick log)
+        #[clap(long = "as", default_value = "deck")]
+        kind: String,
+
+        /// Write to this file instead of stdout
+        #[clap(short, long, value_name = "FILE")]
+        output: Option<String>,
+    },
+}
+
+pub async fn run(command: Commands) -> Result<()> {
+    let path = default_drafts_path();
+    let mut history = DraftHistory::load(&path)?;
+
+    match command {
+        Commands::List {
+            backfill,
+            limit,
+            json,
+        } => {
+            if let Some(sources) = &backfill {
+                let mut found = 0;
+                let mut added = 0;
+                for file in super::history::backfill_files(sources)? {
+                    let content = std::fs::read(&file)
+                        .map_err(|e| eyre!("Failed to read log '{}': {}", file.display(), e))?;
+                    let drafts = record_drafts(&parse_log(&String::from_utf8_lossy(&content)));
+                    aeprintln!("{}: {} draft(s)", file.display(), drafts.len());
+                    for draft in drafts {
+                        found += 1;
+                        if history.upsert(draft) {
+                            added += 1;
+                        }
+                    }
+                }
+                history.save(&path)?;
+                aeprintln!(
+                    "Backfilled {} draft(s) from the logs, {} new. Drafts saved to {}",
+                    found,
+                    added,
+                    path.display()
+                );
+            }
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&history.drafts)?);
+                return Ok(());
+            }
+            if history.drafts.is_empty() {
+                aprintln!("No drafts recorded yet");
+                aprintln!(
+                    "Drafts are saved while `mtg companion watch` runs, or import old ones with --backfill"
+                );
+                return Ok(());
+            }
+            print_drafts(&history.drafts, limit);
+            Ok(())
+        }
+        Commands::Show { id, json } => {
+            let draft = find_named(&mut history, id.as_deref()).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&draft)?);
+            } else {
+                print_draft(&draft);
+            }
+            history.save(&path)
+        }
+        Commands::Export { id, kind, output } => {
+            let draft = find_named(&mut history, id.as_deref()).await?;
+            let content = match kind.as_str() {
+                "deck" => draft.deck_list(),
+                "json" => serde_json::to_string_pretty(&draft.pick_log())? + "\n",
+                other => {
+                    return Err(eyre!(
+                        "Unknown export '{}', expected 'deck' or 'json'",
+                        other
+                    ))
+                }
+            };
+            history.save(&path)?;
+            match output {
+                Some(file) => {
+                    std::fs::write(&file, content)
+                        .map_err(|e| eyre!("Failed to write '{}': {}", file, e))?;
+                    aeprintln!("Exported draft {} to {}", draft.draft_id, file);
+                }
+                None => print!("{content}"),
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Find a draft and look up the names of its cards, keeping them in the history
+async fn find_named(history: &mut DraftHistory, id: Option<&str>) -> Result<DraftRecord> {
+    let draft = history.find_mut(id).ok_or_else(|| match id {
+        Some(id) => eyre!("No recorded draft matches '{}'", id),
+        None => eyre!("No drafts recorded yet"),
+    })?;
+
+    let mut resolved = 0;
+    for grp_id in draft.grp_ids() {
+        if draft.names.contains_key(&grp_id) {
+            continue;
+        }
+        match resolve_card(grp_id).await {
+            Ok(card) => {
+                draft.names.insert(grp_id, card.name);
+                resolved += 1;
+            }
+            // Most likely offline, no point trying every card
+            Err(_) if resolved == 0 => break,
+            Err(_) => {}
+        }
+    }
+    save_card_map().await;
+    Ok(draft.clone())
+}
+
+fn format_time(draft: &DraftRecord) -> String {
+    draft
+        .started_at
+        .map(|started| started.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn print_drafts(drafts: &[DraftRecord], limit: usize) {
+    let mut table = new_table();
+    table.add_row(Row::new(vec![
+        Cell::new("Date"),
+        Cell::new("Id"),
+        Cell::new("Format"),
+        Cell::new("Set"),
+        Cell::new("Picks"),
+        Cell::new("Status"),
+    ]));
+    for draft in drafts.iter().rev().take(limit) {
+        let status = if draft.completed {
+            Cell::new("Completed").style_spec("Fg")
+        } else {
+            Cell::new("In progress").style_spec("Fy")
+        };
+        table.add_row(Row::new(vec![
+            Cell::new(&format_time(draft)),
+            Cell::new(&draft.draft_id),
+            Cell::new(draft.format.as_deref().unwrap_or("-")),
+            Cell::new(draft.set.as_deref().unwrap_or("-")),
+            Cell::new(&draft.pool().len().to_string()),
+            status,
+        ]));
+    }
+    table.printstd();
+}
+
+fn print_draft(draft: &DraftRecord) {
+    aprintln!(
+        "{} {} ({}, {})",
+        "Draft".bold(),
+        draft.draft_id,
+        draft
+            .format
+            .as_deref()
+            .or(draft.set.as_deref())
+            .unwrap_or("unknown format"),
+        format_time(draft)
+    );
+
+    let mut table = new_table();
+    table.add_row(Row::new(vec![
+        Cell::new("Pick"),
+        Cell::new("Picked"),
+        Cell::new("Pack"),
+    ]));
+    for pick in &draft.picks {
+        let picked: Vec<String> = pick
+            .picked
+            .iter()
+            .map(|grp_id| draft.card_name(*grp_id))
+            .collect();
+        let others: Vec<String> = pick
+            .pack_cards
+            .iter()
+            .filter(|grp_id| !pick.picked.contains(grp_id))
+            .map(|grp_id| draft.card_name(*grp_id))
+            .collect();
+        table.add_row(Row::new(vec![
+            Cell::new(&f!("P{}p{}", pick.pack, pick.pick)),
+            Cell::new(&picked.join(", ")).style_spec("Fg"),
+            Cell::new(&others.join(", ")),
+        ]));
+    }
+    table.printstd();
+
+    aprintln!("\n{}", "Pool".bold());
+    print!("{}", draft.deck_list());
+}
@@ -0,0 +1,311 @@
+use crate::prelude::*;
+use chrono::NaiveDate;
+use color_eyre::owo_colors::OwoColorize;
+use mtg_core::companion::history::{
+    breakdown, default_history_path, record_matches, win_rate, Breakdown, HistoryFilter,
+    MatchHistory, MatchRecord, WinRate,
+};
+use mtg_core::companion::parse_log;
+use prettytable::{Cell, Row};
+use std::path::{Path, PathBuf};
+
+pub struct Params {
+    pub deck: Option<String>,
+    pub format: Option<String>,
+    pub event: Option<String>,
+    pub opponent: Option<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub by: Vec<String>,
+    pub backfill: Option<Vec<String>>,
+    pub limit: usize,
+    pub cards: bool,
+    pub json: bool,
+}
+
+pub async fn run(params: Params) -> Result<()> {
+    let path = default_history_path();
+    let mut history = MatchHistory::load(&path)?;
+
+    if let Some(sources) = &params.backfill {
+        let (found, added) = backfill(&mut history, sources)?;
+        history.save(&path)?;
+        aeprintln!(
+            "Backfilled {} match(es) from the logs, {} new. History saved to {}",
+            found,
+            added,
+            path.display()
+        );
+    }
+
+    let filter = HistoryFilter {
+        deck: params.deck,
+        format: params.format,
+        event: params.event,
+        opponent: params.opponent,
+        since: params.since.as_deref().map(parse_date).transpose()?,
+        until: params.until.as_deref().map(parse_date).transpose()?,
+    };
+    let matches: Vec<&MatchRecord> = history
+        .matches
+        .iter()
+        .filter(|record| filter.matches(record))
+        .collect();
+
+    let breakdowns: Vec<Breakdown> = if params.by.is_empty() {
+        vec![
+            Breakdown::Deck,
+            Breakdown::Format,
+            Breakdown::PlayDraw,
+            Breakdown::Week,
+        ]
+    } else {
+        params
+            .by
+            .iter()
+            .map(|by| by.parse())
+            .collect::<Result<_>>()?
+    };
+
+    if params.json {
+        let groups: serde_json::Map<String, serde_json::Value> = breakdowns
+            .iter()
+            .map(|by| {
+                Ok((
+                    breakdown_label(*by).to_lowercase().replace('/', "_"),
+                    serde_json::to_value(breakdown(matches.iter().copied(), *by))?,
+                ))
+            })
+            .collect::<Result<_>>()?;
+        let output = serde_json::json!({
+            "overall": win_rate(matches.iter().copied()),
+            "breakdowns": groups,
+            "matches": matches,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if matches.is_empty() {
+        if history.matches.is_empty() {
+            aprintln!("No matches recorded yet");
+            aprintln!(
+                "Matches are saved while `mtg companion watch` runs, or import old ones with --backfill"
+            );
+        } else {
+            aprintln!("No recorded match matches the filters");
+        }
+        return Ok(());
+    }
+
+    print_recent(&matches, params.limit);
+    if params.cards {
+        print_opponent_cards(&matches, params.limit);
+    }
+
+    let overall = win_rate(matches.iter().copied());
+    println!(
+        "\nOverall: {} ({} matches)",
+        format_rate(&overall),
+        matches.len()
+    );
+
+    for by in breakdowns {
+        aprintln!("\nBy {}:", breakdown_label(by).bold());
+        print_breakdown(&breakdown(matches.iter().copied(), by));
+    }
+
+    Ok(())
+}
+
+/// Parse old logs and add the matches found in them
+fn backfill(history: &mut MatchHistory, sources: &[String]) -> Result<(usize, usize)> {
+    let mut found = 0;
+    let mut added = 0;
+    for file in backfill_files(sources)? {
+        let content = std::fs::read(&file)
+            .map_err(|e| eyre!("Failed to read log '{}': {}", file.display(), e))?;
+        let records = record_matches(&parse_log(&String::from_utf8_lossy(&content)));
+        aeprintln!("{}: {} match(es)", file.display(), records.len());
+        for record in records {
+            found += 1;
+            if history.upsert(record) {
+                added += 1;
+            }
+        }
+    }
+    Ok((found, added))
+}
+
+/// Log files to backfill from: the given files and directories, or every Arena log
+pub(super) fn backfill_files(sources: &[String]) -> Result<Vec<PathBuf>> {
+    if sources.is_empty() {
+        return default_log_files();
+    }
+    let mut files = Vec::new();
+    for source in sources {
+        let path = PathBuf::from(source);
+        if path.is_dir() {
+            files.extend(log_files_in(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// Every log Arena keeps: the rotated logs and Player.log with its previous copy
+fn default_log_files() -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if let Ok(dir) = crate::companion::parse::get_default_log_path() {
+        files.extend(log_files_in(&dir)?);
+    }
+    if let Ok(player_log) = crate::companion::parse::get_player_log_path() {
+        let previous = player_log.with_file_name("Player-prev.log");
+        if previous.exists() {
+            files.push(previous);
+        }
+        files.push(player_log);
+    }
+    if files.is_empty() {
+        return Err(eyre!(
+            "No MTG Arena logs found, pass the log files or directories to --backfill"
+        ));
+    }
+    Ok(files)
+}
+
+fn log_files_in(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "log"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| eyre!("Invalid date '{}', expected YYYY-MM-DD", value))
+}
+
+fn breakdown_label(by: Breakdown) -> &'static str {
+    match by {
+        Breakdown::Deck => "Deck",
+        Breakdown::Format => "Format",
+        Breakdown::PlayDraw => "Play/Draw",
+        Breakdown::Week => "Week",
+    }
+}
+
+fn format_rate(rate: &WinRate) -> String {
+    match rate.percent() {
+        Some(percent) => f!("{}-{} ({percent:.1}%)", rate.wins, rate.losses),
+        None => "no results".to_string(),
+    }
+}
+
+fn print_recent(matches: &[&MatchRecord], limit: usize) {
+    let mut table = new_table();
+    table.add_row(Row::new(vec![
+        Cell::new("Date"),
+        Cell::new("Format"),
+        Cell::new("Deck"),
+        Cell::new("Opponent"),
+        Cell::new("Opp. deck"),
+        Cell::new("Play"),
+        Cell::new("Mulls"),
+        Cell::new("Games"),
+        Cell::new("Turns"),
+        Cell::new("Time"),
+        Cell::new("Result"),
+    ]));
+
+    for record in matches.iter().rev().take(limit) {
+        let result = match record.won {
+            Some(true) => Cell::new("Win").style_spec("Fg"),
+            Some(false) => Cell::new("Loss").style_spec("Fr"),
+            None => Cell::new("?"),
+        };
+        let opponent = match (&record.opponent, &record.opponent_rank) {
+            (Some(name), Some(rank)) => f!("{name} ({rank})"),
+            (Some(name), None) => name.clone(),
+            _ => "-".to_string(),
+        };
+        let play = match record.on_play() {
+            Some(true) => "play",
+            Some(false) => "draw",
+            None => "-",
+        };
+        let time = record
+            .duration_sec
+            .map(|seconds| f!("{}:{:02}", seconds / 60, seconds % 60))
+            .unwrap_or_else(|| "-".to_string());
+        table.add_row(Row::new(vec![
+            Cell::new(
+                &record
+                    .started_at
+                    .map(|started| started.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+            ),
+            Cell::new(record.format.as_deref().unwrap_or("-")),
+            Cell::new(record.deck_name.as_deref().unwrap_or("-")),
+            Cell::new(&opponent),
+            Cell::new(record.opponent_archetype.as_deref().unwrap_or("-")),
+            Cell::new(play),
+            Cell::new(&record.mulligans().to_string()),
+            Cell::new(&record.score()),
+            Cell::new(&record.turns().to_string()),
+            Cell::new(&time),
+            result,
+        ]));
+    }
+    table.printstd();
+}
+
+fn print_opponent_cards(matches: &[&MatchRecord], limit: usize) {
+    for record in matches.iter().rev().take(limit) {
+        if record.opponent_cards.is_empty() {
+            continue;
+        }
+        let cards: Vec<String> = record
+            .opponent_cards
+            .iter()
+            .map(|card| {
+                let name = card.name.clone().unwrap_or_else(|| f!("#{}", card.grp_id));
+                if card.copies > 1 {
+                    f!("{name} x{}", card.copies)
+                } else {
+                    name
+                }
+            })
+            .collect();
+        aprintln!(
+            "\n{} {}",
+            f!(
+                "{} vs {}:",
+                record
+                    .started_at
+                    .map(|started| started.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|| record.match_id.clone()),
+                record.opponent.as_deref().unwrap_or("unknown opponent")
+            )
+            .bold(),
+            cards.join(", ")
+        );
+    }
+}
+
+fn print_breakdown(groups: &std::collections::BTreeMap<String, WinRate>) {
+    let mut rows: Vec<(&String, &WinRate)> = groups.iter().collect();
+    rows.sort_by_key(|(_, rate)| std::cmp::Reverse(rate.played()));
+
+    let mut table = new_table();
+    for (name, rate) in rows {
+        table.add_row(Row::new(vec![
+            Cell::new(name),
+            Cell::new(&format_rate(rate)),
+        ]));
+    }
+    table.printstd();
+}
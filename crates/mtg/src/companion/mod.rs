@@ -2,7 +2,12 @@
 use crate::prelude::*;
 use clap::{Parser, Subcommand};
 
+pub(crate) mod cards;
+mod drafts;
+mod history;
 mod parse;
+mod render;
+mod replay;
 mod watch;
 
 #[derive(Debug, Parser)]
@@ -20,18 +25,45 @@ pub enum CompanionCommands {
         #[clap(long, env = "MTGA_LOG_PATH")]
         log_path: Option<String>,
 
-        /// Watch for specific events only (e.g., "match", "draft", "collection")
+        /// Watch for specific events only (e.g., "match", "game", "turns", "life", "cards", "combat", "draft", "library")
         #[clap(long)]
         filter: Option<Vec<String>>,
 
-        /// Output format (json, pretty, csv)
+        /// Output format: pretty, json for the versioned event stream (one event per line), or
+        /// csv with one row per stream event
         #[clap(long, default_value = "pretty")]
         format: String,
 
+        /// Also append the event stream to this file
+        #[clap(long, value_name = "FILE")]
+        output: Option<String>,
+
+        /// Publish the event stream to clients of a Unix socket at this path
+        #[clap(long, value_name = "PATH")]
+        socket: Option<String>,
+
+        /// Publish the event stream over WebSocket on this address (e.g., 127.0.0.1:7878)
+        #[clap(long, value_name = "ADDRESS")]
+        listen: Option<String>,
+
         /// Start watching from the beginning of the file instead of the end
         #[clap(long)]
         from_beginning: bool,
 
+        /// Tournament format to guess opponent archetypes from (e.g., "standard", "pioneer"),
+        /// taken from the event when not set
+        #[clap(long)]
+        meta: Option<String>,
+
+        /// Number of draws the library odds look ahead
+        #[clap(long, default_value = "3")]
+        draws: u32,
+
+        /// Card ratings CSV (e.g. a 17lands card data export) to suggest draft picks from;
+        /// a file named after a set code, like BLB.csv, rates that set
+        #[clap(long, value_name = "FILE")]
+        ratings: Vec<String>,
+
         /// Show verbose debug output
         #[clap(long)]
         verbose: bool,
@@ -47,7 +79,7 @@ pub enum CompanionCommands {
         #[clap(long)]
         analyze: Option<String>,
 
-        /// Display output in pretty format instead of JSON
+        /// Display events as readable lines instead of one JSON object per line
         #[clap(long)]
         pretty: bool,
 
@@ -67,23 +99,112 @@ pub enum CompanionCommands {
         #[clap(long)]
         verbose: bool,
     },
+
+    /// Show recorded matches with win rates by deck, format, play/draw and week
+    History {
+        /// Only matches played with decks whose name contains this text
+        #[clap(long)]
+        deck: Option<String>,
+
+        /// Only matches of this format (e.g., "Standard", "Quick Draft")
+        #[clap(long)]
+        format: Option<String>,
+
+        /// Only matches of events whose id contains this text
+        #[clap(long)]
+        event: Option<String>,
+
+        /// Only matches against this opponent
+        #[clap(long)]
+        opponent: Option<String>,
+
+        /// Only matches played on or after this date (YYYY-MM-DD)
+        #[clap(long)]
+        since: Option<String>,
+
+        /// Only matches played on or before this date (YYYY-MM-DD)
+        #[clap(long)]
+        until: Option<String>,
+
+        /// Win-rate breakdowns to show (deck, format, play-draw, week), all by default
+        #[clap(long)]
+        by: Vec<String>,
+
+        /// Import matches from old log files or directories first (the Arena logs by default)
+        #[clap(long, num_args = 0..)]
+        backfill: Option<Vec<String>>,
+
+        /// Number of recent matches to list
+        #[clap(long, default_value = "20")]
+        limit: usize,
+
+        /// Also list the opponent cards seen in each of the recent matches
+        #[clap(long)]
+        cards: bool,
+
+        /// Output JSON instead of tables
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// List, show and export recorded drafts
+    Drafts {
+        #[command(subcommand)]
+        command: drafts::Commands,
+    },
+
+    /// Step through a match turn by turn: life totals, battlefields, your hand and graveyards
+    Replay {
+        /// Match id or a prefix of it (defaults to the last match in the log)
+        match_id: Option<String>,
+
+        /// Log file to read (defaults to the newest log file)
+        #[clap(long, default_value = "latest")]
+        file: String,
+
+        /// Only show this game of the match
+        #[clap(long)]
+        game: Option<u32>,
+
+        /// Only show the board at the end of this turn
+        #[clap(long)]
+        turn: Option<u32>,
+
+        /// Output the game states as JSON
+        #[clap(long)]
+        json: bool,
+    },
 }
 
-pub async fn run(app: App, _global: crate::Global) -> Result<()> {
+pub async fn run(app: App, global: crate::Global) -> Result<()> {
+    cards::init_resolver(&global);
     match app.command {
         CompanionCommands::Watch {
             log_path,
             filter,
             format,
+            output,
+            socket,
+            listen,
             from_beginning,
+            meta,
+            draws,
+            ratings,
             verbose,
         } => {
             watch::run(watch::Params {
                 log_path,
                 filter,
                 format,
+                output,
+                socket,
+                listen,
                 from_beginning,
+                meta,
+                draws,
+                ratings,
                 verbose,
+                global,
             })
             .await
         }
@@ -107,5 +228,50 @@ pub async fn run(app: App, _global: crate::Global) -> Result<()> {
             })
             .await
         }
+        CompanionCommands::History {
+            deck,
+            format,
+            event,
+            opponent,
+            since,
+            until,
+            by,
+            backfill,
+            limit,
+            cards,
+            json,
+        } => {
+            history::run(history::Params {
+                deck,
+                format,
+                event,
+                opponent,
+                since,
+                until,
+                by,
+                backfill,
+                limit,
+                cards,
+                json,
+            })
+            .await
+        }
+        CompanionCommands::Drafts { command } => drafts::run(command).await,
+        CompanionCommands::Replay {
+            match_id,
+            file,
+            game,
+            turn,
+            json,
+        } => {
+            replay::run(replay::Params {
+                match_id,
+                file,
+                game,
+                turn,
+                json,
+            })
+            .await
+        }
     }
 }
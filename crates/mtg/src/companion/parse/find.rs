@@ -74,7 +74,7 @@ fn parse_all_inventory_entries_from_file(file_path: &Path) -> Result<Vec<Invento
     }
 
     // Sort by timestamp, newest first
-    entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.timestamp));
 
     Ok(entries)
 }
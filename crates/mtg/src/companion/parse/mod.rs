@@ -1,5 +1,7 @@
+use super::render::{matches_filter, EventRenderer};
 use crate::prelude::*;
 use mtg_core::cache::{CacheStore, DiskCacheBuilder};
+use mtg_core::companion::{ArenaEvent, InventoryInfo};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -8,13 +10,13 @@ use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
-mod display;
-pub mod events;
 mod find;
 mod print;
 mod utils;
 
-pub(crate) use utils::{find_newest_log_file, get_default_log_path, get_player_log_path};
+pub(crate) use utils::{
+    find_newest_log_file, get_card_database_dirs, get_default_log_path, get_player_log_path,
+};
 
 pub struct Params {
     pub file: String,
@@ -55,37 +57,6 @@ struct ArenaData {
     client_periodic_rewards: Option<Value>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
-#[serde(rename_all = "PascalCase")]
-struct InventoryInfo {
-    seq_id: Option<u32>,
-    changes: Option<Vec<Value>>,
-    gems: Option<u32>,
-    gold: Option<u32>,
-    total_vault_progress: Option<u32>,
-    wc_track_position: Option<u32>,
-    wild_card_commons: Option<u32>,
-    wild_card_un_commons: Option<u32>,
-    wild_card_rares: Option<u32>,
-    wild_card_mythics: Option<u32>,
-    custom_tokens: Option<HashMap<String, u32>>,
-    boosters: Option<Vec<Value>>,
-    vouchers: Option<HashMap<String, Value>>,
-    prize_walls_unlocked: Option<Vec<Value>>,
-    cosmetics: Option<Cosmetics>,
-}
-
-#[derive(Debug, Deserialize, Serialize, Clone)]
-#[serde(rename_all = "PascalCase")]
-struct Cosmetics {
-    art_styles: Option<Vec<Value>>,
-    avatars: Option<Vec<Value>>,
-    pets: Option<Vec<Value>>,
-    sleeves: Option<Vec<Value>>,
-    emotes: Option<Vec<Value>>,
-    titles: Option<Vec<Value>>,
-}
-
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "PascalCase")]
 struct DeckSummary {
@@ -680,37 +651,33 @@ async fn run_events_analysis(params: Params) -> Result<()> {
     println!();
 
     // Parse events from the log files
-    let result = events::parse_events_from_file(
-        &file_path,
-        params.include_player_log,
-        &params.filter,
-        params.limit,
-        params.verbose,
-    )
-    .await?;
-
-    // Display the events
-    let display = display::EventDisplay::new(params.pretty);
-
-    if !result.main_events.is_empty() {
-        println!("📋 Main Log Events:");
-        println!();
-        for event in &result.main_events {
-            display.display_parsed_event(event)?;
+    let mut log = fs::read_to_string(&file_path)?;
+    if params.include_player_log {
+        if let Ok(player_log_path) = get_player_log_path() {
+            if player_log_path != file_path {
+                log.push('\n');
+                log.push_str(&fs::read_to_string(&player_log_path)?);
+            }
         }
-        println!();
     }
-
-    if !result.player_events.is_empty() {
-        println!("🎮 Player Log Events:");
-        println!();
-        for event in &result.player_events {
-            display.display_player_event(event)?;
-        }
-        println!();
+    let total_lines = log.lines().count();
+    let filters = params.filter.unwrap_or_default();
+    let all_events = mtg_core::companion::parse_log(&log);
+    if params.verbose {
+        aeprintln!(
+            "Parsed {} events from {} lines",
+            all_events.len(),
+            total_lines
+        );
     }
-
-    if result.main_events.is_empty() && result.player_events.is_empty() {
+    let matching: Vec<&ArenaEvent> = all_events
+        .iter()
+        .filter(|event| matches_filter(event, &filters))
+        .collect();
+    let first_shown = matching.len().saturating_sub(params.limit);
+    let shown = matching.len() - first_shown;
+
+    if shown == 0 {
         println!("❌ No events found matching the specified criteria");
         println!();
         println!("💡 Try:");
@@ -718,13 +685,37 @@ async fn run_events_analysis(params: Params) -> Result<()> {
         println!("   - Increasing the limit with --limit");
         println!("   - Adding --include-player-log for more detailed events");
         println!("   - Using --verbose to see parsing progress");
-    } else {
-        // Display summary
-        display.display_summary(
-            &result.main_events,
-            &result.player_events,
-            result.total_lines_processed,
-        )?;
+        return Ok(());
+    }
+
+    // Render from the start so player names are known for the shown events
+    let mut renderer = EventRenderer::new();
+    for (index, event) in matching.iter().enumerate() {
+        let rendered = renderer.render(event);
+        if index < first_shown {
+            continue;
+        }
+        if !params.pretty {
+            println!("{}", serde_json::to_string(event)?);
+        } else if let Some(rendered) = rendered {
+            println!("{}", rendered);
+        }
+    }
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for event in &matching {
+        *counts.entry(event.category()).or_insert(0) += 1;
+    }
+    let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+    counts.sort_by_key(|(category, count)| (std::cmp::Reverse(*count), *category));
+
+    println!("\n📊 Parse Summary");
+    println!("   Total lines processed: {}", total_lines);
+    println!("   Events found: {}", matching.len());
+    println!("   Events shown: {}", shown);
+    println!("\n📋 Event Breakdown:");
+    for (category, count) in counts {
+        println!("   {}: {}", category, count);
     }
 
     Ok(())
@@ -2,7 +2,8 @@ use crate::prelude::*;
 use prettytable::{Cell, Row};
 use std::collections::HashMap;
 
-use super::{get_format_abbreviation, CombinedDeckInfo, Deck, DeckSummary, InventoryInfo};
+use super::{get_format_abbreviation, CombinedDeckInfo, Deck, DeckSummary};
+use mtg_core::companion::InventoryInfo;
 
 pub fn inventory(info: &InventoryInfo) {
     println!("=== Inventory Information ===\n");
@@ -79,6 +79,185 @@ pub fn get_player_log_path() -> Result<PathBuf> {
     Ok(log_path)
 }
 
+#[cfg(target_os = "linux")]
+pub fn get_default_log_path() -> Result<PathBuf> {
+    let log_path = find_wine_mtga_dir()?.join("Logs").join("Logs");
+
+    if !log_path.exists() {
+        return Err(eyre!(
+            "MTG Arena log directory not found at: {:?}",
+            log_path
+        ));
+    }
+
+    Ok(log_path)
+}
+
+#[cfg(target_os = "linux")]
+pub fn get_player_log_path() -> Result<PathBuf> {
+    let log_path = find_wine_mtga_dir()?.join("Player.log");
+
+    if !log_path.exists() {
+        return Err(eyre!("MTG Arena Player.log not found at: {:?}", log_path));
+    }
+
+    Ok(log_path)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+pub fn get_default_log_path() -> Result<PathBuf> {
+    Err(eyre!(
+        "MTG Arena logs have no default location on this platform, pass the log file explicitly"
+    ))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+pub fn get_player_log_path() -> Result<PathBuf> {
+    Err(eyre!(
+        "MTG Arena logs have no default location on this platform, pass the log file explicitly"
+    ))
+}
+
+#[cfg(target_os = "windows")]
+pub fn get_card_database_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    for program_files in ["ProgramFiles", "ProgramFiles(x86)"] {
+        if let Some(root) = std::env::var_os(program_files) {
+            let root = PathBuf::from(root);
+            dirs.push(root.join("Wizards of the Coast").join("MTGA"));
+            dirs.push(root.join("Epic Games").join("MagicTheGathering"));
+        }
+    }
+    dirs.into_iter()
+        .map(|install| install.join("MTGA_Data").join("Downloads").join("Raw"))
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+pub fn get_card_database_dirs() -> Vec<PathBuf> {
+    dirs::home_dir()
+        .map(|home| {
+            vec![home
+                .join("Library")
+                .join("Application Support")
+                .join("com.wizards.mtga")
+                .join("Downloads")
+                .join("Raw")]
+        })
+        .unwrap_or_default()
+}
+
+/// Card database directories of Arena installs in Wine prefixes and Steam libraries
+#[cfg(target_os = "linux")]
+pub fn get_card_database_dirs() -> Vec<PathBuf> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+    let mut installs: Vec<PathBuf> = wine_prefixes(&home)
+        .into_iter()
+        .map(|prefix| prefix.join("drive_c/Program Files/Wizards of the Coast/MTGA"))
+        .collect();
+    for steam in STEAM_ROOTS {
+        installs.push(home.join(steam).join("steamapps/common/MTGA"));
+    }
+    installs
+        .into_iter()
+        .map(|install| install.join("MTGA_Data/Downloads/Raw"))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+pub fn get_card_database_dirs() -> Vec<PathBuf> {
+    Vec::new()
+}
+
+/// Find the MTGA data directory of an Arena install running under Wine, Proton, Lutris or Bottles.
+/// When several prefixes contain one, the most recently written Player.log wins.
+#[cfg(target_os = "linux")]
+fn find_wine_mtga_dir() -> Result<PathBuf> {
+    let home = dirs::home_dir().ok_or_else(|| eyre!("Could not find home directory"))?;
+    let prefixes = wine_prefixes(&home);
+
+    mtga_dirs_in_prefixes(&prefixes)
+        .into_iter()
+        .max_by_key(|dir| {
+            fs::metadata(dir.join("Player.log"))
+                .and_then(|metadata| metadata.modified())
+                .unwrap_or(std::time::SystemTime::UNIX_EPOCH)
+        })
+        .ok_or_else(|| {
+            eyre!(
+                "No MTG Arena install found in any Wine or Proton prefix (set WINEPREFIX to point at yours)"
+            )
+        })
+}
+
+/// Steam installs, native and Flatpak, relative to the home directory
+#[cfg(target_os = "linux")]
+const STEAM_ROOTS: [&str; 3] = [
+    ".steam/steam",
+    ".local/share/Steam",
+    ".var/app/com.valvesoftware.Steam/.local/share/Steam",
+];
+
+/// Candidate Wine prefixes: $WINEPREFIX, ~/.wine, Lutris games, Steam Proton and Bottles
+#[cfg(target_os = "linux")]
+fn wine_prefixes(home: &Path) -> Vec<PathBuf> {
+    let mut prefixes = Vec::new();
+    if let Some(prefix) = std::env::var_os("WINEPREFIX") {
+        prefixes.push(PathBuf::from(prefix));
+    }
+    prefixes.push(home.join(".wine"));
+
+    let subdirs = |dir: PathBuf| -> Vec<PathBuf> {
+        fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .filter(|path| path.is_dir())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    prefixes.extend(subdirs(home.join("Games")));
+    for steam in STEAM_ROOTS {
+        prefixes.extend(
+            subdirs(home.join(steam).join("steamapps/compatdata"))
+                .into_iter()
+                .map(|app| app.join("pfx")),
+        );
+    }
+    prefixes.extend(subdirs(home.join(".local/share/bottles/bottles")));
+
+    prefixes
+}
+
+/// MTGA data directories (`drive_c/users/*/AppData/LocalLow/Wizards Of The Coast/MTGA`) in the given prefixes
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub fn mtga_dirs_in_prefixes(prefixes: &[PathBuf]) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    for prefix in prefixes {
+        let Ok(users) = fs::read_dir(prefix.join("drive_c").join("users")) else {
+            continue;
+        };
+        for user in users.filter_map(|entry| entry.ok()) {
+            let dir = user
+                .path()
+                .join("AppData")
+                .join("LocalLow")
+                .join("Wizards Of The Coast")
+                .join("MTGA");
+            if dir.is_dir() && !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+    }
+    dirs.sort();
+    dirs
+}
+
 pub fn find_newest_log_file(dir: &Path) -> Result<PathBuf> {
     let mut newest_file = None;
     let mut newest_time = std::time::SystemTime::UNIX_EPOCH;
@@ -119,3 +298,21 @@ pub fn find_newest_log_file(dir: &Path) -> Result<PathBuf> {
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mtga_dirs_in_prefixes() {
+        let root = tempfile::tempdir().unwrap();
+        let prefix = root.path().join("arena");
+        let mtga =
+            prefix.join("drive_c/users/steamuser/AppData/LocalLow/Wizards Of The Coast/MTGA");
+        fs::create_dir_all(&mtga).unwrap();
+        fs::create_dir_all(prefix.join("drive_c/users/Public/AppData")).unwrap();
+
+        let prefixes = vec![prefix.clone(), prefix, root.path().join("missing")];
+        assert_eq!(mtga_dirs_in_prefixes(&prefixes), vec![mtga]);
+    }
+}
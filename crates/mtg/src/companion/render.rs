@@ -0,0 +1,344 @@
+use crate::prelude::*;
+use mtg_core::companion::{ArenaEvent, CardRef, DamageTarget, SeatId, Zone};
+use std::collections::HashMap;
+
+/// Turns [`ArenaEvent`]s into the one-line messages shown by `companion watch` and
+/// `companion parse --analyze events --pretty`
+#[derive(Debug, Default)]
+pub struct EventRenderer {
+    players: HashMap<SeatId, String>,
+    teams: HashMap<u32, String>,
+    local_seat: Option<SeatId>,
+}
+
+impl EventRenderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn player(&self, seat: SeatId) -> String {
+        let name = self
+            .players
+            .get(&seat)
+            .cloned()
+            .unwrap_or_else(|| f!("Player {seat}"));
+        if self.local_seat == Some(seat) {
+            f!("{name} (you)")
+        } else {
+            name
+        }
+    }
+
+    fn team(&self, team: Option<u32>) -> String {
+        match team {
+            Some(team) => self
+                .teams
+                .get(&team)
+                .cloned()
+                .unwrap_or_else(|| f!("Team {team}")),
+            None => "nobody".to_string(),
+        }
+    }
+
+    fn card(&self, card: &CardRef) -> String {
+        match card.grp_id {
+            Some(grp_id) => f!("card #{grp_id}"),
+            None => "a hidden card".to_string(),
+        }
+    }
+
+    fn owner(&self, card: &CardRef) -> String {
+        card.owner
+            .map(|seat| self.player(seat))
+            .unwrap_or_else(|| "Someone".to_string())
+    }
+
+    /// Render an event, or `None` for events that are not worth a line on their own
+    pub fn render(&mut self, event: &ArenaEvent) -> Option<String> {
+        let line = match event {
+            ArenaEvent::Authenticated { screen_name, .. } => {
+                f!("🔐 Logged in as {screen_name}")
+            }
+            ArenaEvent::StateChanged { from, to } => f!("🔄 Client state: {from} → {to}"),
+            ArenaEvent::SceneChanged { from, to } => f!("🧭 Scene: {from} → {to}"),
+            ArenaEvent::MatchCreated {
+                opponent_name,
+                opponent_rank,
+                ..
+            } => f!(
+                "🔎 Opponent found: {}{}",
+                opponent_name.as_deref().unwrap_or("unknown"),
+                opponent_rank
+                    .as_deref()
+                    .map(|rank| f!(" ({rank})"))
+                    .unwrap_or_default()
+            ),
+            ArenaEvent::MatchStarted {
+                match_id,
+                event_id,
+                players,
+                ..
+            } => {
+                self.players.clear();
+                self.teams.clear();
+                for player in players {
+                    self.players.insert(player.seat, player.name.clone());
+                    self.teams.insert(player.team, player.name.clone());
+                }
+                let names: Vec<&str> = players.iter().map(|p| p.name.as_str()).collect();
+                f!(
+                    "\n🎮 Match started: {} ({}) [{match_id}]",
+                    names.join(" vs "),
+                    event_id.as_deref().unwrap_or("unknown event")
+                )
+            }
+            ArenaEvent::MatchCompleted {
+                winning_team,
+                reason,
+                games,
+                ..
+            } => {
+                let reason = reason.as_deref().unwrap_or("unknown");
+                f!(
+                    "🏆 Match over: {} won after {} game(s) ({reason})\n",
+                    self.team(*winning_team),
+                    games.len().max(1)
+                )
+            }
+            ArenaEvent::MatchReport(report) => f!(
+                "📊 Match report: {} turns in {}s, {} mulligan(s)",
+                report.turns.unwrap_or(0),
+                report.seconds.unwrap_or(0),
+                report.mulligans
+            ),
+            ArenaEvent::DeckSubmitted {
+                main, sideboard, ..
+            } => f!(
+                "🃏 Deck submitted: {} main, {} sideboard",
+                main.iter().map(|entry| entry.quantity).sum::<u32>(),
+                sideboard.iter().map(|entry| entry.quantity).sum::<u32>()
+            ),
+            ArenaEvent::DeckSelected(deck) => f!(
+                "🃏 Deck selected: {}{} ({} cards)",
+                deck.name.as_deref().unwrap_or("unnamed deck"),
+                deck.format
+                    .as_deref()
+                    .map(|format| f!(" [{format}]"))
+                    .unwrap_or_default(),
+                deck.main.iter().map(|entry| entry.quantity).sum::<u32>()
+            ),
+            ArenaEvent::GameStarted {
+                game_number,
+                local_seat,
+            } => {
+                if local_seat.is_some() {
+                    self.local_seat = *local_seat;
+                }
+                f!("\n🎲 Game {game_number} started")
+            }
+            ArenaEvent::DieRoll { rolls } => {
+                let rolls: Vec<String> = rolls
+                    .iter()
+                    .map(|roll| f!("{} rolled {}", self.player(roll.seat), roll.value))
+                    .collect();
+                f!("🎲 {}", rolls.join(", "))
+            }
+            ArenaEvent::Mulligan { seat, count } => {
+                f!(
+                    "🤲 {} mulligans to {}",
+                    self.player(*seat),
+                    7u32.saturating_sub(*count)
+                )
+            }
+            ArenaEvent::TurnStarted { turn, active_seat } => {
+                f!("\n--- Turn {turn} ({}) ---", self.player(*active_seat))
+            }
+            ArenaEvent::PhaseChanged { phase, step, .. } => match step {
+                Some(step) => f!("🕐 {phase} - {step}"),
+                None => f!("🕐 {phase}"),
+            },
+            ArenaEvent::LifeChanged { seat, from, to } => {
+                let icon = if to < from { "💔" } else { "❤️" };
+                f!("{icon} {} life: {from} → {to}", self.player(*seat))
+            }
+            ArenaEvent::ZoneTransfer(transfer) => {
+                let who = self.owner(&transfer.card);
+                let card = self.card(&transfer.card);
+                match (transfer.category.as_deref(), transfer.from, transfer.to) {
+                    (Some("Draw"), _, _) => f!("📤 {who} draws {card}"),
+                    (Some("PlayLand"), _, _) => f!("🏞️ {who} plays {card}"),
+                    (Some("CastSpell"), _, _) => f!("✨ {who} casts {card}"),
+                    (Some("Resolve"), _, _) => f!("✅ {card} resolves"),
+                    (Some("Countered"), _, _) => f!("🚫 {card} is countered"),
+                    (Some("Discard"), _, _) => f!("🗑️ {who} discards {card}"),
+                    (Some("Mill"), _, _) => f!("🪦 {who} mills {card}"),
+                    (Some("Destroy"), _, _) => f!("💀 {card} is destroyed"),
+                    (Some("Sacrifice"), _, _) => f!("🩸 {who} sacrifices {card}"),
+                    (Some("SBA_Damage"), _, _) | (Some("SBA_ZeroToughness"), _, _) => {
+                        f!("💀 {card} dies")
+                    }
+                    (Some("Exile"), _, _) | (_, _, Zone::Exile) => f!("🌀 {card} is exiled"),
+                    (Some("Return"), _, Zone::Hand) => f!("↩️ {card} returns to hand"),
+                    (_, Zone::Library, Zone::Library) => return None,
+                    (category, from, to) => f!(
+                        "🎭 {card}: {} → {}{}",
+                        from.label(),
+                        to.label(),
+                        category.map(|c| f!(" ({c})")).unwrap_or_default()
+                    ),
+                }
+            }
+            ArenaEvent::Damage {
+                source,
+                target,
+                amount,
+            } => {
+                let target = match target {
+                    DamageTarget::Player(seat) => self.player(*seat),
+                    DamageTarget::Card(card) => self.card(card),
+                };
+                f!("💥 {} deals {amount} damage to {target}", self.card(source))
+            }
+            ArenaEvent::Attacking { card } => f!("⚔️ {} attacks", self.card(card)),
+            ArenaEvent::Blocking { card } => f!("🛡️ {} blocks", self.card(card)),
+            ArenaEvent::Tapped { card, tapped } => {
+                if *tapped {
+                    f!("⤵️ {} taps", self.card(card))
+                } else {
+                    f!("⤴️ {} untaps", self.card(card))
+                }
+            }
+            ArenaEvent::Counters {
+                card,
+                counter,
+                change,
+            } => {
+                let verb = if *change < 0 { "loses" } else { "gets" };
+                f!(
+                    "🔢 {} {verb} {} {counter} counter(s)",
+                    self.card(card),
+                    change.unsigned_abs()
+                )
+            }
+            ArenaEvent::Timer(update) => {
+                let timer = update.timers.iter().find(|timer| timer.running)?;
+                let remaining = timer.remaining_sec();
+                if timer.warning_sec.is_some_and(|warning| remaining > warning) {
+                    return None;
+                }
+                f!(
+                    "⏱️ {}: {remaining}s left on the {} timer",
+                    self.player(update.seat),
+                    timer.kind
+                )
+            }
+            ArenaEvent::GameOver {
+                game_number,
+                results,
+            } => {
+                let winner = results.first().and_then(|result| result.winning_team);
+                let reason = results
+                    .first()
+                    .and_then(|result| result.reason.clone())
+                    .unwrap_or_else(|| "unknown".to_string());
+                f!(
+                    "🏁 Game {game_number} over: {} wins ({reason})",
+                    self.team(winner)
+                )
+            }
+            ArenaEvent::DraftPack {
+                pack, pick, cards, ..
+            } => f!("📦 Pack {pack} pick {pick}: {} cards", cards.len()),
+            ArenaEvent::DraftPick {
+                pack, pick, cards, ..
+            } => {
+                let cards: Vec<String> = cards.iter().map(|id| f!("#{id}")).collect();
+                f!("🎯 Pack {pack} pick {pick}: picked {}", cards.join(", "))
+            }
+            ArenaEvent::DraftCompleted { .. } => "✅ Draft completed".to_string(),
+            ArenaEvent::RankUpdated {
+                constructed,
+                limited,
+            } => {
+                let describe = |rank: &Option<mtg_core::companion::Rank>| match rank {
+                    Some(rank) => match rank.level {
+                        Some(level) => f!("{} {level}", rank.class),
+                        None => rank.class.clone(),
+                    },
+                    None => "unranked".to_string(),
+                };
+                f!(
+                    "🏅 Rank: constructed {}, limited {}",
+                    describe(constructed),
+                    describe(limited)
+                )
+            }
+            ArenaEvent::Quests { quests } => {
+                let quests: Vec<String> = quests
+                    .iter()
+                    .map(|quest| f!("{} {}/{}", quest.name, quest.progress, quest.goal))
+                    .collect();
+                f!("🎯 Quests: {}", quests.join("; "))
+            }
+        };
+        Some(line)
+    }
+}
+
+/// Whether an event passes the `--filter` categories (match, game, turns, life, cards, ...)
+pub fn matches_filter(event: &ArenaEvent, filters: &[String]) -> bool {
+    if filters.is_empty() {
+        return true;
+    }
+    let category = event.category();
+    filters.iter().any(|filter| match filter.as_str() {
+        "authentication" => category == "auth",
+        // Older releases grouped card movements under actions
+        "actions" => category == "actions" || category == "cards",
+        "mulligan" => category == "mulligans",
+        "turn" => category == "turns",
+        filter => filter == category,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mtg_core::companion::parse_log;
+
+    #[test]
+    fn test_filter_matching() {
+        let event = ArenaEvent::TurnStarted {
+            turn: 1,
+            active_seat: 1,
+        };
+        assert!(matches_filter(&event, &["turns".to_string()]));
+        assert!(!matches_filter(&event, &["match".to_string()]));
+        assert!(matches_filter(&event, &[]));
+    }
+
+    #[test]
+    fn test_render_uses_player_names() {
+        let log = concat!(
+            "[UnityCrossThreadLogger]1/1/2025 12:00:00 PM: Match to A: MatchGameRoomStateChangedEvent\n",
+            r#"{"matchGameRoomStateChangedEvent":{"gameRoomInfo":{"gameRoomConfig":{"reservedPlayers":[{"playerName":"Alice","systemSeatId":1,"teamId":1},{"playerName":"Bob","systemSeatId":2,"teamId":2}],"matchId":"m"},"stateType":"MatchGameRoomStateType_Playing"}}}"#,
+            "\n"
+        );
+        let mut renderer = EventRenderer::new();
+        let lines: Vec<String> = parse_log(log)
+            .iter()
+            .filter_map(|event| renderer.render(event))
+            .collect();
+        assert!(lines[0].contains("Alice vs Bob"));
+        assert_eq!(
+            renderer
+                .render(&ArenaEvent::LifeChanged {
+                    seat: 2,
+                    from: 20,
+                    to: 17
+                })
+                .unwrap(),
+            "💔 Bob life: 20 → 17"
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use crate::prelude::*;
+use color_eyre::owo_colors::OwoColorize;
+use mtg_core::companion::{replay_matches, GameObject, GameState, MatchReplay, SeatId, Zone};
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+
+pub struct Params {
+    pub match_id: Option<String>,
+    pub file: String,
+    pub game: Option<u32>,
+    pub turn: Option<u32>,
+    pub json: bool,
+}
+
+pub async fn run(params: Params) -> Result<()> {
+    let path = log_file(&params.file)?;
+    let content = std::fs::read(&path)
+        .map_err(|e| eyre!("Failed to read log '{}': {}", path.display(), e))?;
+    let matches = replay_matches(&String::from_utf8_lossy(&content));
+    if matches.is_empty() {
+        return Err(eyre!("No games found in {}", path.display()));
+    }
+
+    let replay = match &params.match_id {
+        None => matches.last().expect("matches is not empty"),
+        Some(id) => matches
+            .iter()
+            .rev()
+            .find(|replay| replay.match_id.starts_with(id.as_str()))
+            .ok_or_else(|| {
+                let ids: Vec<&str> = matches.iter().map(|m| m.match_id.as_str()).collect();
+                eyre!(
+                    "No match '{}' in {}, found: {}",
+                    id,
+                    path.display(),
+                    ids.join(", ")
+                )
+            })?,
+    };
+
+    let games: Vec<_> = replay
+        .games
+        .iter()
+        .filter(|game| params.game.is_none_or(|number| game.game_number == number))
+        .collect();
+    if games.is_empty() {
+        return Err(eyre!(
+            "Match {} has no game {}",
+            replay.match_id,
+            params.game.unwrap_or_default()
+        ));
+    }
+
+    if params.json {
+        let output: Vec<serde_json::Value> = games
+            .iter()
+            .map(|game| {
+                serde_json::json!({
+                    "game_number": game.game_number,
+                    "turns": turns(game.turns(), params.turn),
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "match_id": replay.match_id,
+                "event_id": replay.event_id,
+                "players": replay.players,
+                "local_seat": replay.local_seat,
+                "games": output,
+            }))?
+        );
+        return Ok(());
+    }
+
+    let names = CardNames::resolve(games.iter().flat_map(|game| &game.snapshots)).await;
+    aprintln!(
+        "{} {}",
+        "Match".bold(),
+        if replay.match_id.is_empty() {
+            "(unknown id)"
+        } else {
+            replay.match_id.as_str()
+        }
+    );
+    for game in games {
+        let shown = turns(game.turns(), params.turn);
+        if shown.is_empty() {
+            aprintln!(
+                "\nGame {}: no turn {}",
+                game.game_number,
+                params.turn.unwrap_or(0)
+            );
+        }
+        for state in shown {
+            print_turn(replay, state, &names);
+        }
+    }
+
+    Ok(())
+}
+
+fn log_file(file: &str) -> Result<PathBuf> {
+    if !file.is_empty() && file != "latest" {
+        return Ok(PathBuf::from(file));
+    }
+    let newest = crate::companion::parse::get_default_log_path()
+        .and_then(|dir| crate::companion::parse::find_newest_log_file(&dir))
+        .or_else(|_| crate::companion::parse::get_player_log_path())?;
+    aeprintln!("Using log file: {}", newest.display());
+    Ok(newest)
+}
+
+fn turns(turns: Vec<&GameState>, only: Option<u32>) -> Vec<&GameState> {
+    turns
+        .into_iter()
+        .filter(|state| only.is_none_or(|turn| state.turn == turn))
+        .collect()
+}
+
+/// Card names by grpId, looked up once per card. Falls back to `#grpId` when offline.
+struct CardNames(HashMap<u32, String>);
+
+impl CardNames {
+    async fn resolve<'a>(snapshots: impl Iterator<Item = &'a GameState>) -> Self {
+        let grp_ids: BTreeSet<u32> = snapshots
+            .flat_map(|state| state.objects.values())
+            .map(|object| object.grp_id)
+            .filter(|grp_id| *grp_id != 0)
+            .collect();
+        let mut names = HashMap::new();
+        for grp_id in grp_ids {
+            match crate::companion::cards::resolve_card(grp_id).await {
+                Ok(card) => {
+                    names.insert(grp_id, card.name);
+                }
+                // Most likely offline, no point trying every card
+                Err(_) if names.is_empty() => break,
+                Err(_) => {}
+            }
+        }
+        crate::companion::cards::save_card_map().await;
+        Self(names)
+    }
+
+    fn name(&self, object: &GameObject) -> String {
+        let name = self
+            .0
+            .get(&object.grp_id)
+            .cloned()
+            .unwrap_or_else(|| f!("#{}", object.grp_id));
+        let mut notes = Vec::new();
+        if let (Some(power), Some(toughness)) = (object.power, object.toughness) {
+            notes.push(f!("{power}/{toughness}"));
+        }
+        if object.tapped {
+            notes.push("tapped".to_string());
+        }
+        if object.attacking {
+            notes.push("attacking".to_string());
+        }
+        if object.blocking {
+            notes.push("blocking".to_string());
+        }
+        if notes.is_empty() {
+            name
+        } else {
+            f!("{name} ({})", notes.join(", "))
+        }
+    }
+}
+
+fn player(replay: &MatchReplay, seat: SeatId) -> String {
+    let name = replay
+        .player_name(seat)
+        .map(str::to_string)
+        .unwrap_or_else(|| f!("Player {seat}"));
+    if replay.local_seat == Some(seat) {
+        f!("{name} (you)")
+    } else {
+        name
+    }
+}
+
+fn print_turn(replay: &MatchReplay, state: &GameState, names: &CardNames) {
+    let heading = if state.turn == 0 {
+        f!("Game {}, opening hands", state.game_number)
+    } else {
+        f!(
+            "Game {}, turn {} ({})",
+            state.game_number,
+            state.turn,
+            player(replay, state.active_seat)
+        )
+    };
+    aprintln!(
+        "\n{} {}",
+        heading.bold(),
+        f!("[state {}]", state.state_id).dimmed()
+    );
+
+    // Our seat first, then the opponents
+    let mut seats: Vec<SeatId> = state.players.keys().copied().collect();
+    seats.sort_by_key(|seat| replay.local_seat != Some(*seat));
+
+    for seat in seats {
+        let life = state.players.get(&seat).map(|p| p.life).unwrap_or_default();
+        aprintln!(
+            "  {}: {} life, {} in library, {} in hand",
+            player(replay, seat).bold(),
+            life,
+            state.zone_size(Zone::Library, seat),
+            state.zone_size(Zone::Hand, seat)
+        );
+        print_zone(state, names, Zone::Battlefield, seat, "Battlefield");
+        if replay.local_seat == Some(seat) {
+            print_zone(state, names, Zone::Hand, seat, "Hand");
+        }
+        print_zone(state, names, Zone::Graveyard, seat, "Graveyard");
+        print_zone(state, names, Zone::Exile, seat, "Exile");
+    }
+}
+
+fn print_zone(state: &GameState, names: &CardNames, zone: Zone, seat: SeatId, label: &str) {
+    let mut objects = state.objects_in(zone, seat);
+    if zone == Zone::Battlefield {
+        // Lands last, like on the table
+        objects.sort_by_key(|object| object.is_land());
+    }
+    let cards: Vec<String> = objects
+        .iter()
+        .filter(|object| object.kind != "Ability")
+        .map(|object| names.name(object))
+        .collect();
+    if cards.is_empty() && zone != Zone::Battlefield {
+        return;
+    }
+    let cards = if cards.is_empty() {
+        "-".to_string()
+    } else {
+        cards.join(", ")
+    };
+    println!("    {label}: {cards}");
+}
@@ -0,0 +1,102 @@
+use crate::companion::cards::resolve_card;
+use mtg_core::companion::LibraryContents;
+use std::collections::HashMap;
+
+/// Prints the library odds, looking up card names and types once per card
+#[derive(Debug, Default)]
+pub struct LibraryView {
+    names: HashMap<u32, String>,
+    categories: HashMap<u32, String>,
+}
+
+impl LibraryView {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn show(&mut self, library: &LibraryContents, draws: u32) {
+        for card in &library.cards {
+            if self.names.contains_key(&card.grp_id) {
+                continue;
+            }
+            match resolve_card(card.grp_id).await {
+                Ok(found) => {
+                    self.categories
+                        .insert(card.grp_id, card_category(&found.type_line).to_string());
+                    self.names.insert(card.grp_id, found.name);
+                }
+                Err(_) => {
+                    self.names
+                        .insert(card.grp_id, format!("Card #{}", card.grp_id));
+                }
+            }
+        }
+
+        println!("     📚 Library: {} cards", library.size);
+        let mut categories: Vec<(String, u32)> =
+            library.by_category(&self.categories).into_iter().collect();
+        categories.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        let categories: Vec<String> = categories
+            .iter()
+            .map(|(category, count)| {
+                format!(
+                    "{category} {:.0}% ({:.0}% in {draws})",
+                    library.next_draw(*count) * 100.0,
+                    library.within(*count, draws) * 100.0
+                )
+            })
+            .collect();
+        if !categories.is_empty() {
+            println!("        Next draw: {}", categories.join(", "));
+        }
+
+        println!(
+            "        {:<4} {:<32} {:>6} {:>8}",
+            "Left",
+            "Card",
+            "Next",
+            format!("In {draws}")
+        );
+        for card in library.cards.iter().filter(|card| card.remaining > 0) {
+            let name = self.names.get(&card.grp_id).map(String::as_str);
+            println!(
+                "        {:<4} {:<32} {:>5.1}% {:>7.1}%",
+                card.remaining,
+                name.unwrap_or_default(),
+                library.next_draw(card.remaining) * 100.0,
+                library.within(card.remaining, draws) * 100.0
+            );
+        }
+    }
+}
+
+/// Main card type of a type line, lands first since that's what matters for draws
+fn card_category(type_line: &str) -> &'static str {
+    let front = type_line.split("//").next().unwrap_or(type_line);
+    [
+        "Land",
+        "Creature",
+        "Planeswalker",
+        "Battle",
+        "Instant",
+        "Sorcery",
+        "Artifact",
+        "Enchantment",
+    ]
+    .into_iter()
+    .find(|kind| front.contains(kind))
+    .unwrap_or("Other")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_card_category() {
+        assert_eq!(card_category("Artifact Land"), "Land");
+        assert_eq!(card_category("Artifact Creature — Golem"), "Creature");
+        assert_eq!(card_category("Instant // Sorcery"), "Instant");
+        assert_eq!(card_category("Kindred Enchantment"), "Enchantment");
+    }
+}
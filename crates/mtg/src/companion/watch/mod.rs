@@ -1,32 +1,41 @@
 use crate::prelude::*;
 
 mod async_processor;
-mod display;
-mod parser;
-mod player_parser;
-mod player_tailer;
-mod resolver;
+mod library;
+mod picks;
+mod publish;
+mod scouting;
 mod state;
 mod tailer;
-mod types;
 
+use super::render::{matches_filter, EventRenderer};
 use async_processor::{AsyncProcessor, AsyncTask};
-use display::MatchDisplay;
-use parser::{EventParser, ParsedEvent};
-use player_parser::PlayerEventParser;
-use player_tailer::PlayerLogTailer;
-use resolver::CardResolver;
+use mtg_core::companion::drafts::default_drafts_path;
+use mtg_core::companion::history::{default_history_path, format_from_event};
+use mtg_core::companion::{
+    ArenaEvent, ArenaParser, DraftHistory, DraftRecord, DraftRecorder, EventStream, LibraryTracker,
+    MatchHistory, MatchRecord, MatchRecorder, OpponentTracker, StreamEvent,
+};
+use picks::PickView;
+use publish::Publisher;
+use scouting::{meta_format, Scout, SharedScouting};
 use tokio::sync::mpsc;
 
 use tailer::LogTailer;
-use types::RawLogEvent;
 
 pub struct Params {
     pub log_path: Option<String>,
     pub filter: Option<Vec<String>>,
     pub format: String,
+    pub output: Option<String>,
+    pub socket: Option<String>,
+    pub listen: Option<String>,
     pub from_beginning: bool,
+    pub meta: Option<String>,
+    pub draws: u32,
+    pub ratings: Vec<String>,
     pub verbose: bool,
+    pub global: crate::Global,
 }
 
 pub async fn run(params: Params) -> Result<()> {
@@ -46,23 +55,48 @@ pub async fn run(params: Params) -> Result<()> {
         crate::companion::parse::find_newest_log_file(&log_dir)?
     };
 
-    // Try to get Player.log path
-    let player_log_path: Option<std::path::PathBuf> =
-        crate::companion::parse::get_player_log_path().ok();
-
     aeprintln!("Watching MTG Arena log file: {}", log_path.display());
-    if let Some(ref player_path) = player_log_path {
-        aeprintln!("Also watching Player.log file: {}", player_path.display());
-    } else {
-        aeprintln!("Player.log not found - continuing with main log only");
-    }
     aeprintln!("Press Ctrl+C to stop watching...\n");
 
+    let (json, csv) = match params.format.as_str() {
+        "pretty" => (false, false),
+        "json" | "ndjson" => (true, false),
+        "csv" => (false, true),
+        other => {
+            return Err(eyre!(
+                "Unknown format '{}', expected 'pretty', 'json' or 'csv'",
+                other
+            ))
+        }
+    };
+    let mut csv_writer = if csv {
+        let mut writer = csv::Writer::from_writer(std::io::stdout());
+        writer.write_record(["seq", "category", "match_id", "type", "event"])?;
+        writer.flush()?;
+        Some(writer)
+    } else {
+        None
+    };
+    // Both machine formats print the stream only, without the rendered events
+    let machine = json || csv;
+    let mut publisher = Publisher::start(
+        json,
+        params.output.as_deref(),
+        params.socket.as_deref(),
+        params.listen.as_deref(),
+    )
+    .await?;
+    let mut stream = EventStream::new();
+
+    let picks = PickView::load(&params.ratings)?;
+    let suggest_picks = picks.is_some();
+
     // Create channel for async tasks
     let (tx, rx) = mpsc::channel::<AsyncTask>(100);
 
     // Spawn async processor
-    let processor = AsyncProcessor::new(rx);
+    let scouting = SharedScouting::default();
+    let processor = AsyncProcessor::new(rx, Scout::new(scouting.clone(), params.global), picks);
     let processor_handle = tokio::spawn(async move {
         if let Err(e) = processor.run().await {
             eprintln!("Async processor error: {}", e);
@@ -71,62 +105,96 @@ pub async fn run(params: Params) -> Result<()> {
 
     // Initialize components
     let mut tailer = LogTailer::new(&log_path, params.from_beginning).await?;
-    let mut parser = EventParser::new();
-    let mut resolver = CardResolver::new();
-    let display = MatchDisplay::new()
-        .with_colors(params.format == "pretty")
-        .with_detailed_actions(true);
+    let mut parser = ArenaParser::new();
+    let mut renderer = EventRenderer::new();
+    let mut recorder = MatchRecorder::new();
+    let mut opponent = OpponentTracker::new();
+    let mut library = LibraryTracker::new();
+    let mut drafts = DraftRecorder::new();
+    let history_path = default_history_path();
+    let drafts_path = default_drafts_path();
+    let filters = params.filter.unwrap_or_default();
 
-    // Clone parameters for player log processing
-    let tx_clone = tx.clone();
-    let filter_clone = params.filter.clone();
-    let verbose_clone = params.verbose;
+    // Start main log tailing and processing events
+    let result = tailer
+        .tail_with_callback(|line| {
+            for event in parser.push_line(line) {
+                if let Some(mut record) = recorder.push(&event) {
+                    if let Ok(scouting) = scouting.lock() {
+                        scouting.annotate(&mut record);
+                    }
+                    if let Err(e) = save_match(&history_path, record) {
+                        aeprintln!("Could not save the match to the history: {}", e);
+                    }
+                }
 
-    // Start Player.log processing in a separate task if available
-    let player_handle = if let Some(player_path) = player_log_path {
-        let player_path_clone = player_path.clone();
-        let from_beginning = params.from_beginning;
+                if let Some(draft) = drafts.push(&event) {
+                    if let Err(e) = save_draft(&drafts_path, draft) {
+                        aeprintln!("Could not save the draft: {}", e);
+                    }
+                }
+                if let (ArenaEvent::DraftPack { cards, .. }, true, false) =
+                    (&event, suggest_picks, machine)
+                {
+                    if filters.is_empty() || filters.iter().any(|filter| filter == "draft") {
+                        let draft = drafts.current();
+                        let _ = tx.try_send(AsyncTask::DraftPack {
+                            pack: cards.clone(),
+                            picked: draft.map(|draft| draft.pool()).unwrap_or_default(),
+                            set: draft.and_then(|draft| draft.set.clone()),
+                        });
+                    }
+                }
+
+                let seen = opponent.push(&event);
+                let contents = library.push(&event, parser.game_state());
+                if let (Some(library), false) = (contents, machine) {
+                    if filters.is_empty() || filters.iter().any(|filter| filter == "library") {
+                        let _ = tx.try_send(AsyncTask::Library {
+                            library,
+                            draws: params.draws,
+                        });
+                    }
+                }
+                if let (ArenaEvent::MatchStarted { event_id, .. }, false) = (&event, machine) {
+                    let format = params.meta.clone().or_else(|| {
+                        let format = format_from_event(event_id.as_deref()?);
+                        meta_format(&format).map(str::to_string)
+                    });
+                    let _ = tx.try_send(AsyncTask::OpponentMatch { format });
+                }
 
-        Some(tokio::spawn(async move {
-            match PlayerLogTailer::new(&player_path_clone, from_beginning).await {
-                Ok(mut player_tailer) => {
-                    let mut player_parser = PlayerEventParser::new();
+                if !matches_filter(&event, &filters) {
+                    if params.verbose {
+                        aeprintln!("🚫 Event filtered out: {}", event.category());
+                    }
+                    continue;
+                }
 
-                    if let Err(e) = player_tailer
-                        .tail_with_callback(|raw_event| {
-                            process_player_event(
-                                raw_event,
-                                &mut player_parser,
-                                &filter_clone,
-                                verbose_clone,
-                            )
-                        })
-                        .await
-                    {
-                        aeprintln!("Player.log processing error: {}", e);
+                if publisher.is_active() || csv_writer.is_some() {
+                    let stream_event = stream.next(&event, parser.match_id());
+                    if publisher.is_active() {
+                        publisher.publish(&stream_event.to_line()?)?;
+                    }
+                    if let Some(writer) = &mut csv_writer {
+                        write_csv_event(writer, &stream_event)?;
                     }
                 }
-                Err(e) => {
-                    aeprintln!("Failed to initialize Player.log tailer: {}", e);
+                if machine {
+                    continue;
                 }
-            }
-        }))
-    } else {
-        None
-    };
 
-    // Start main log tailing and processing events
-    let result = tailer
-        .tail_with_callback(|raw_event| {
-            process_raw_event(
-                raw_event,
-                &mut parser,
-                &mut resolver,
-                &display,
-                &params.filter,
-                params.verbose,
-                &tx,
-            )
+                if let Some(rendered) = renderer.render(&event) {
+                    println!("{}", rendered);
+                }
+                if let Some(task) = card_details_task(&event) {
+                    let _ = tx.try_send(task);
+                }
+                if let Some(card) = seen {
+                    let _ = tx.try_send(AsyncTask::OpponentCard { card });
+                }
+            }
+            Ok(())
         })
         .await;
 
@@ -134,304 +202,81 @@ pub async fn run(params: Params) -> Result<()> {
     drop(tx); // Close the channel
     let _ = processor_handle.await;
 
-    // Clean up player log task if it exists
-    if let Some(handle) = player_handle {
-        handle.abort();
-    }
-
     result
 }
 
-fn process_raw_event(
-    raw_event: RawLogEvent,
-    parser: &mut EventParser,
-    resolver: &mut CardResolver,
-    display: &MatchDisplay,
-    filter: &Option<Vec<String>>,
-    verbose: bool,
-    async_tx: &mpsc::Sender<AsyncTask>,
+/// One stream event as a CSV row, with the event itself as JSON in the last column
+fn write_csv_event(
+    writer: &mut csv::Writer<std::io::Stdout>,
+    stream_event: &StreamEvent,
 ) -> Result<()> {
-    if verbose {
-        aeprintln!("🔍 Processing event: {}", raw_event.event_name);
-    }
-
-    // Parse the raw event
-    if let Some(event) = parser.parse_event(raw_event.clone(), async_tx.clone())? {
-        if verbose {
-            aeprintln!("🎯 Parsed event: {:?}", std::mem::discriminant(&event));
-        }
-
-        // Apply filters if specified
-        if let Some(filters) = filter {
-            if !should_show_event(&event, filters) {
-                if verbose {
-                    aeprintln!("🚫 Event filtered out");
-                }
-                return Ok(());
-            }
-        }
-
-        // Handle the parsed event
-        handle_event(event, parser, resolver, display)?;
-    } else if verbose {
-        aeprintln!(
-            "❌ No event parsed from raw event: {}",
-            raw_event.event_name
-        );
-    }
-
+    let event = serde_json::to_value(&stream_event.event)?;
+    writer.write_record([
+        stream_event.seq.to_string(),
+        stream_event.category.clone(),
+        stream_event.match_id.clone().unwrap_or_default(),
+        event["type"].as_str().unwrap_or_default().to_string(),
+        event.to_string(),
+    ])?;
+    // Flush every row so the output follows the game live
+    writer.flush()?;
     Ok(())
 }
 
-fn process_player_event(
-    raw_event: RawLogEvent,
-    player_parser: &mut PlayerEventParser,
-    filter: &Option<Vec<String>>,
-    verbose: bool,
-) -> Result<()> {
-    if verbose {
-        aeprintln!("🔍 Processing Player.log event: {}", raw_event.event_name);
-    }
-
-    // Apply basic filtering for player events
-    if let Some(filters) = filter {
-        let should_show = match raw_event.event_name.as_str() {
-            "PlayerMatchStarted" => filters.contains(&"match".to_string()),
-            "PlayerLifeChange" => filters.contains(&"life".to_string()),
-            "PlayerCardDrawn" | "PlayerCardPlayed" | "PlayerCardRevealed" => {
-                filters.contains(&"cards".to_string())
-            }
-            "PlayerActionTaken"
-            | "PlayerAbilityActivated"
-            | "PlayerSpellCast"
-            | "PlayerTargetSelection"
-            | "PlayerManaPaid"
-            | "PlayerPermanentTapped" => filters.contains(&"actions".to_string()),
-            "PlayerPhaseChange" => filters.contains(&"turns".to_string()),
-            "PlayerMulligan" => filters.contains(&"mulligans".to_string()),
-            "PlayerAttackers" | "PlayerBlockers" => filters.contains(&"combat".to_string()),
-            "PlayerDeckInfo" => filters.contains(&"deck".to_string()),
-            "PlayerGameState"
-            | "PlayerSpellResolution"
-            | "PlayerCounterChange"
-            | "PlayerPriorityPass" => filters.contains(&"game".to_string()),
-            _ => true, // Show other events by default
-        };
-
-        if !should_show && !filters.is_empty() {
-            if verbose {
-                aeprintln!("🚫 Player event filtered out");
-            }
-            return Ok(());
-        }
-    }
-
-    // Parse the player event
-    if let Err(e) = player_parser.parse_player_event(raw_event) {
-        if verbose {
-            aeprintln!("❌ Error parsing player event: {}", e);
-        }
-    }
-
-    Ok(())
+/// Add a completed match to the history file
+fn save_match(path: &std::path::Path, record: MatchRecord) -> Result<()> {
+    let mut history = MatchHistory::load(path)?;
+    history.upsert(record);
+    history.save(path)
 }
 
-fn should_show_event(event: &ParsedEvent, filters: &[String]) -> bool {
-    // If no filters specified, show everything
-    if filters.is_empty() {
-        return true;
-    }
-
-    // Check if event matches any of the filters
-    for filter in filters {
-        let matches = match filter.as_str() {
-            "auth" | "authentication" => {
-                matches!(event, ParsedEvent::UserAuthenticated { .. })
-            }
-            "match" => {
-                matches!(
-                    event,
-                    ParsedEvent::MatchStarted(_) | ParsedEvent::MatchEnded(_)
-                )
-            }
-            "turns" => {
-                matches!(event, ParsedEvent::TurnChange { .. })
-            }
-            "life" => {
-                matches!(event, ParsedEvent::LifeChange { .. })
-            }
-            "actions" => {
-                matches!(
-                    event,
-                    ParsedEvent::GameAction(_) | ParsedEvent::CardPlayed { .. }
-                )
-            }
-            "cards" => {
-                matches!(event, ParsedEvent::CardPlayed { .. })
-            }
-            "mulligans" => {
-                matches!(event, ParsedEvent::Mulligan { .. })
-            }
-            "draft" => {
-                matches!(
-                    event,
-                    ParsedEvent::DraftPack { .. }
-                        | ParsedEvent::DraftPick { .. }
-                        | ParsedEvent::DraftCompleted
-                )
-            }
-            "deck" => {
-                matches!(event, ParsedEvent::DeckSubmitted { .. })
-            }
-            "combat" => {
-                // This filter is mainly for Player.log events
-                false
-            }
-            "game" => {
-                // This filter is mainly for Player.log events
-                false
-            }
-            _ => true, // Unknown filter, show everything
-        };
-
-        if matches {
-            return true;
-        }
-    }
-
-    false
+/// Add or update a draft in the drafts file
+fn save_draft(path: &std::path::Path, record: DraftRecord) -> Result<()> {
+    let mut history = DraftHistory::load(path)?;
+    history.upsert(record);
+    history.save(path)
 }
 
-fn handle_event(
-    event: ParsedEvent,
-    parser: &mut EventParser,
-    _resolver: &mut CardResolver,
-    display: &MatchDisplay,
-) -> Result<()> {
-    // Most events are now handled directly in the parser with println!
-    // This function is kept for compatibility but most cases won't be reached
+/// Card lookups worth doing in the background for the pretty output
+fn card_details_task(event: &ArenaEvent) -> Option<AsyncTask> {
     match event {
-        ParsedEvent::UserAuthenticated {
-            user_id,
-            display_name,
-        } => {
-            println!("🔐 User authenticated: {} ({})", display_name, user_id);
-        }
-
-        ParsedEvent::MatchStarted(match_state) => {
-            display.display_match_start(&match_state)?;
-        }
-
-        ParsedEvent::MatchEnded(match_state) => {
-            display.display_match_end(&match_state)?;
-        }
-
-        ParsedEvent::TurnChange {
-            turn,
-            active_player,
-        } => {
-            if let Some(current_match) = parser.current_match() {
-                display.display_turn_change(turn, active_player, &current_match.players)?;
-            }
-        }
-
-        ParsedEvent::LifeChange {
-            player,
-            old_life,
-            new_life,
-        } => {
-            if let Some(current_match) = parser.current_match() {
-                display.display_life_change(player, old_life, new_life, &current_match.players)?;
-            }
-        }
-
-        ParsedEvent::GameAction(action) => {
-            if let Some(current_match) = parser.current_match() {
-                display.display_game_action(&action, &current_match.players)?;
-            }
-        }
-
-        ParsedEvent::CardPlayed {
-            player,
-            card_name,
-            zone_from,
-            zone_to,
-        } => {
-            if let Some(current_match) = parser.current_match() {
-                display.display_card_played(
-                    player,
-                    &card_name,
-                    &zone_from,
-                    &zone_to,
-                    &current_match.players,
-                )?;
-            }
-        }
-
-        ParsedEvent::Mulligan {
-            player,
-            from_size,
-            to_size,
-        } => {
-            if let Some(current_match) = parser.current_match() {
-                display.display_mulligan(player, from_size, to_size, &current_match.players)?;
-            }
-        }
-
-        ParsedEvent::DraftPack {
-            pack_number,
-            pick_number,
-            cards,
-        } => {
-            println!(
-                "📦 Draft Pack {}.{}: {} cards available",
-                pack_number + 1,
-                pick_number + 1,
-                cards.len()
-            );
-        }
-
-        ParsedEvent::DraftPick { card_id } => {
-            println!("🎯 Draft Pick: Card ID {}", card_id);
-        }
-
-        ParsedEvent::DraftCompleted => {
-            println!("✅ Draft completed!");
-        }
-
-        ParsedEvent::DeckSubmitted { deck_id, deck_name } => {
-            // This case shouldn't be reached anymore as we handle it in the parser
-            match (deck_id, deck_name) {
-                (Some(id), Some(name)) => println!("🃏 Deck submitted: {} ({})", name, id),
-                (Some(id), None) => println!("🃏 Deck submitted: {}", id),
-                (None, Some(name)) => println!("🃏 Deck submitted: {}", name),
-                (None, None) => println!("🃏 Deck submitted"),
-            }
+        ArenaEvent::DeckSelected(deck) => Some(AsyncTask::FetchDeckCards { deck: deck.clone() }),
+        ArenaEvent::ZoneTransfer(transfer) => {
+            let context = match transfer.category.as_deref()? {
+                "CastSpell" => "Spell cast",
+                "PlayLand" => "Land played",
+                _ => return None,
+            };
+            Some(AsyncTask::FetchCardByGrpId {
+                grp_id: transfer.card.grp_id?,
+                context: context.to_string(),
+            })
         }
+        _ => None,
     }
-
-    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use types::MatchState;
+    use mtg_core::companion::{CardRef, Zone, ZoneTransfer};
 
     #[test]
-    fn test_filter_matching() {
-        let event = ParsedEvent::MatchStarted(MatchState::default());
-        let filters = vec!["match".to_string()];
-        assert!(should_show_event(&event, &filters));
-
-        let filters = vec!["turns".to_string()];
-        assert!(!should_show_event(&event, &filters));
-    }
-
-    #[test]
-    fn test_empty_filters() {
-        let event = ParsedEvent::MatchStarted(MatchState::default());
-        let filters = vec![];
-        assert!(should_show_event(&event, &filters)); // Empty filters should show everything
+    fn test_card_details_only_for_known_cards() {
+        let transfer = |grp_id, category: &str| {
+            ArenaEvent::ZoneTransfer(ZoneTransfer {
+                card: CardRef {
+                    instance_id: 1,
+                    grp_id,
+                    owner: Some(1),
+                },
+                from: Zone::Hand,
+                to: Zone::Stack,
+                category: Some(category.to_string()),
+            })
+        };
+        assert!(card_details_task(&transfer(Some(87521), "CastSpell")).is_some());
+        assert!(card_details_task(&transfer(None, "CastSpell")).is_none());
+        assert!(card_details_task(&transfer(Some(87521), "Draw")).is_none());
     }
 }
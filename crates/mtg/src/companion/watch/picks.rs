@@ -0,0 +1,95 @@
+use crate::companion::cards::resolve_card;
+use crate::prelude::*;
+use mtg_core::companion::{suggest_picks, CardRatings};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Number of ranked cards printed per pack
+const SHOWN_PICKS: usize = 5;
+
+/// Prints pick suggestions for each draft pack from the loaded ratings
+#[derive(Debug)]
+pub struct PickView {
+    ratings: CardRatings,
+    names: HashMap<u32, String>,
+}
+
+impl PickView {
+    /// Load rating files; a file named after a set code, e.g. `BLB.csv`, rates that set
+    pub fn load(files: &[String]) -> Result<Option<Self>> {
+        if files.is_empty() {
+            return Ok(None);
+        }
+        let mut ratings = CardRatings::default();
+        for file in files {
+            let content = std::fs::read_to_string(file)
+                .map_err(|e| eyre!("Failed to read ratings '{}': {}", file, e))?;
+            ratings
+                .extend_csv(&content, set_from_file_name(Path::new(file)))
+                .map_err(|e| eyre!("{} ({})", e, file))?;
+        }
+        aeprintln!("Loaded {} card ratings for draft picks", ratings.len());
+        Ok(Some(Self {
+            ratings,
+            names: HashMap::new(),
+        }))
+    }
+
+    pub async fn show(&mut self, pack: &[u32], picked: &[u32], set: Option<&str>) {
+        for grp_id in pack.iter().chain(picked) {
+            if self.names.contains_key(grp_id) {
+                continue;
+            }
+            // Without a name the card can still be rated when the ratings carry grpIds
+            if let Ok(card) = resolve_card(*grp_id).await {
+                self.names.insert(*grp_id, card.name);
+            }
+        }
+
+        let advice = suggest_picks(pack, picked, &self.names, &self.ratings, set);
+        let lane: String = advice.commitment.lane().into_iter().collect();
+        if lane.is_empty() {
+            println!("     🎯 Pick suggestions (colors still open):");
+        } else {
+            println!(
+                "     🎯 Pick suggestions (leaning {lane}, {:.0}% committed):",
+                advice.commitment.strength() * 100.0
+            );
+        }
+        for suggestion in advice.suggestions.iter().take(SHOWN_PICKS) {
+            let name = suggestion
+                .name
+                .clone()
+                .unwrap_or_else(|| f!("Card #{}", suggestion.grp_id));
+            let score = suggestion
+                .score
+                .map(|score| f!("{score:5.1}"))
+                .unwrap_or_else(|| "    -".to_string());
+            println!(
+                "        {score}  {name:<32} {}",
+                suggestion.reasons.join(", ")
+            );
+        }
+    }
+}
+
+/// Set code a ratings file is named after, e.g. `BLB` for `blb.csv`
+fn set_from_file_name(path: &Path) -> Option<&str> {
+    let stem = path.file_stem()?.to_str()?;
+    ((2..=5).contains(&stem.len()) && stem.chars().all(|c| c.is_ascii_alphanumeric()))
+        .then_some(stem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_from_file_name() {
+        assert_eq!(
+            set_from_file_name(Path::new("ratings/BLB.csv")),
+            Some("BLB")
+        );
+        assert_eq!(set_from_file_name(Path::new("card-ratings.csv")), None);
+    }
+}
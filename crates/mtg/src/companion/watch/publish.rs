@@ -0,0 +1,153 @@
+use crate::prelude::*;
+use futures::SinkExt;
+use std::io::Write;
+use tokio::sync::broadcast;
+
+/// Lines a slow client may fall behind by before it starts missing events
+const CLIENT_BUFFER: usize = 1024;
+
+/// Where the NDJSON event stream goes: stdout, a file, and clients of a Unix socket or
+/// WebSocket
+pub struct Publisher {
+    stdout: bool,
+    file: Option<std::fs::File>,
+    clients: Option<broadcast::Sender<String>>,
+}
+
+impl Publisher {
+    /// Open the file and start the servers that were asked for
+    pub async fn start(
+        stdout: bool,
+        output: Option<&str>,
+        socket: Option<&str>,
+        listen: Option<&str>,
+    ) -> Result<Self> {
+        let file = output
+            .map(|path| {
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .map_err(|e| eyre!("Failed to open '{}' for events: {}", path, e))
+            })
+            .transpose()?;
+
+        let clients = if socket.is_some() || listen.is_some() {
+            let (sender, _) = broadcast::channel(CLIENT_BUFFER);
+            if let Some(path) = socket {
+                serve_socket(path, &sender)?;
+            }
+            if let Some(address) = listen {
+                serve_websocket(address, &sender).await?;
+            }
+            Some(sender)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            stdout,
+            file,
+            clients,
+        })
+    }
+
+    /// Whether any output wants the stream, so events are only serialized when needed
+    pub fn is_active(&self) -> bool {
+        self.stdout || self.file.is_some() || self.clients.is_some()
+    }
+
+    pub fn publish(&mut self, line: &str) -> Result<()> {
+        if self.stdout {
+            println!("{line}");
+        }
+        if let Some(file) = &mut self.file {
+            writeln!(file, "{line}").map_err(|e| eyre!("Failed to write event: {}", e))?;
+        }
+        if let Some(clients) = &self.clients {
+            // Fails only when nobody is connected
+            let _ = clients.send(line.to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Wait for the next line for a client, skipping over lines it was too slow for
+async fn next_line(receiver: &mut broadcast::Receiver<String>) -> Option<String> {
+    loop {
+        match receiver.recv().await {
+            Ok(line) => return Some(line),
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return None,
+        }
+    }
+}
+
+#[cfg(unix)]
+fn serve_socket(path: &str, sender: &broadcast::Sender<String>) -> Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+    use tokio::io::AsyncWriteExt;
+
+    // A socket left behind by an earlier run would make the bind fail
+    if std::fs::metadata(path).is_ok_and(|metadata| metadata.file_type().is_socket()) {
+        std::fs::remove_file(path)?;
+    }
+    let listener = tokio::net::UnixListener::bind(path)
+        .map_err(|e| eyre!("Failed to listen on socket '{}': {}", path, e))?;
+    aeprintln!("Streaming events to clients of {}", path);
+
+    let sender = sender.clone();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut receiver = sender.subscribe();
+            tokio::spawn(async move {
+                while let Some(line) = next_line(&mut receiver).await {
+                    if stream.write_all(f!("{line}\n").as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn serve_socket(_path: &str, _sender: &broadcast::Sender<String>) -> Result<()> {
+    Err(eyre!(
+        "Unix sockets are not available on this platform, use --listen for a WebSocket instead"
+    ))
+}
+
+async fn serve_websocket(address: &str, sender: &broadcast::Sender<String>) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(address)
+        .await
+        .map_err(|e| eyre!("Failed to listen on '{}': {}", address, e))?;
+    let local = listener.local_addr()?;
+    if !local.ip().is_loopback() {
+        aeprintln!(
+            "Warning: {} is reachable from other machines, use 127.0.0.1 to keep the stream local",
+            local
+        );
+    }
+    aeprintln!("Streaming events to WebSocket clients on ws://{}", local);
+
+    let sender = sender.clone();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let mut receiver = sender.subscribe();
+            tokio::spawn(async move {
+                let Ok(mut socket) = tokio_tungstenite::accept_async(stream).await else {
+                    return;
+                };
+                while let Some(line) = next_line(&mut receiver).await {
+                    let message = tokio_tungstenite::tungstenite::Message::Text(line.into());
+                    if socket.send(message).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    Ok(())
+}
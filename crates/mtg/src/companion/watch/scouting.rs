@@ -0,0 +1,190 @@
+use crate::companion::cards::resolve_card;
+use crate::prelude::*;
+use mtg_core::companion::{MatchRecord, SeenCard};
+use mtg_core::decks::{guess_archetype, ArchetypeGuess, ArchetypeReport};
+use mtg_core::ParsedDeck;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Number of recent tournament articles the archetype guess is based on
+const META_ARTICLES: u32 = 5;
+
+/// What we know about the current opponent, shared between the log loop and the background
+/// lookups so completed matches are saved with card names and the archetype guess
+#[derive(Debug, Default)]
+pub struct Scouting {
+    names: HashMap<u32, String>,
+    archetype: Option<String>,
+}
+
+pub type SharedScouting = Arc<Mutex<Scouting>>;
+
+impl Scouting {
+    /// Fill in the card names and archetype found so far
+    pub fn annotate(&self, record: &mut MatchRecord) {
+        for card in &mut record.opponent_cards {
+            if card.name.is_none() {
+                card.name = self.names.get(&card.grp_id).cloned();
+            }
+        }
+        if record.opponent_archetype.is_none() {
+            record.opponent_archetype = self.archetype.clone();
+        }
+    }
+}
+
+/// Tournament format to compare an Arena format against, if there is one
+pub fn meta_format(format: &str) -> Option<&'static str> {
+    let format = format.to_lowercase();
+    if format.contains("standard") {
+        Some("standard")
+    } else if format.contains("explorer") || format.contains("pioneer") {
+        Some("pioneer")
+    } else {
+        None
+    }
+}
+
+/// Background side of the scouting: names the opponent's cards and guesses their archetype
+pub struct Scout {
+    shared: SharedScouting,
+    global: crate::Global,
+    seen: Vec<String>,
+    format: Option<String>,
+    best: Option<String>,
+    /// Labeled tournament decks per format, `None` when they could not be fetched
+    meta: HashMap<String, Option<(Vec<ParsedDeck>, ArchetypeReport)>>,
+}
+
+impl Scout {
+    pub fn new(shared: SharedScouting, global: crate::Global) -> Self {
+        Self {
+            shared,
+            global,
+            seen: Vec::new(),
+            format: None,
+            best: None,
+            meta: HashMap::new(),
+        }
+    }
+
+    pub fn new_match(&mut self, format: Option<String>) {
+        self.seen.clear();
+        self.best = None;
+        self.format = format;
+        if let Ok(mut shared) = self.shared.lock() {
+            shared.archetype = None;
+        }
+    }
+
+    pub async fn card_seen(&mut self, card: &SeenCard) {
+        let how = card
+            .seen
+            .last()
+            .map(|sighting| sighting.label())
+            .unwrap_or("seen");
+        let name = match resolve_card(card.grp_id).await {
+            Ok(found) => found.name,
+            Err(_) => {
+                println!("     👁️ Opponent card #{} ({how})", card.grp_id);
+                return;
+            }
+        };
+        println!("     👁️ Opponent card: {name} ({how})");
+        if let Ok(mut shared) = self.shared.lock() {
+            shared.names.insert(card.grp_id, name.clone());
+        }
+        if self.seen.contains(&name) {
+            return;
+        }
+        self.seen.push(name);
+
+        let Some(guess) = self.guess().await else {
+            return;
+        };
+        // Only speak up when the best guess changes
+        if self.best.as_deref() == Some(guess.archetype.as_str()) {
+            return;
+        }
+        self.best = Some(guess.archetype.clone());
+        if let Ok(mut shared) = self.shared.lock() {
+            shared.archetype = Some(guess.archetype.clone());
+        }
+
+        let unseen: Vec<String> = guess
+            .likely_unseen
+            .iter()
+            .take(5)
+            .map(|card| f!("{} ({:.0}%)", card.name, card.share * 100.0))
+            .collect();
+        println!(
+            "     🔮 Looks like {} ({:.0}% of seen cards in {} deck(s))",
+            guess.archetype,
+            guess.score * 100.0,
+            guess.decks
+        );
+        if !unseen.is_empty() {
+            println!("        Watch for: {}", unseen.join(", "));
+        }
+    }
+
+    async fn guess(&mut self) -> Option<ArchetypeGuess> {
+        let format = self.format.clone()?;
+        if !self.meta.contains_key(&format) {
+            let meta = self.load_meta(&format).await;
+            self.meta.insert(format.clone(), meta);
+        }
+        let (decks, report) = self.meta.get(&format)?.as_ref()?;
+        guess_archetype(&self.seen, decks, report)
+            .into_iter()
+            .next()
+    }
+
+    async fn load_meta(&self, format: &str) -> Option<(Vec<ParsedDeck>, ArchetypeReport)> {
+        // Deck pages are parsed with an HTML parser that can't cross threads, so the fetch
+        // runs on a thread of its own instead of the background task
+        let global = self.global.clone();
+        let meta_format = format.to_string();
+        let fetched = tokio::task::spawn_blocking(move || -> Result<Vec<ParsedDeck>> {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?;
+            let (decks, _) = runtime.block_on(crate::decks::fetch_recent_decks(
+                Some(meta_format),
+                META_ARTICLES,
+                &global,
+            ))?;
+            Ok(decks)
+        })
+        .await
+        .map_err(|e| eyre!("{}", e))
+        .and_then(|decks| decks);
+
+        let decks = match fetched {
+            Ok(decks) => decks,
+            Err(e) => {
+                aeprintln!("Could not load {format} tournament decks, no archetype guesses: {e}");
+                return None;
+            }
+        };
+        match crate::decks::label_decks(&decks, None) {
+            Ok(report) => Some((decks, report)),
+            Err(e) => {
+                aeprintln!("Could not label {format} tournament decks: {e}");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meta_format() {
+        assert_eq!(meta_format("Traditional Standard"), Some("standard"));
+        assert_eq!(meta_format("Explorer"), Some("pioneer"));
+        assert_eq!(meta_format("Quick Draft"), None);
+    }
+}
@@ -1,8 +1,15 @@
-use super::types::LogFileState;
 use crate::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use tokio::fs;
 
+/// How far `companion watch` got in a log file, so it can resume after a restart
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct LogFileState {
+    pub file_path: String,
+    pub bytes_read: u64,
+}
+
 pub struct StateManager {
     state_file_path: PathBuf,
     current_state: LogFileState,
@@ -60,7 +67,6 @@ impl StateManager {
         let new_state = LogFileState {
             file_path: log_file_path.to_string_lossy().to_string(),
             bytes_read: 0,
-            ..Default::default()
         };
 
         self.current_state = new_state.clone();
@@ -0,0 +1,164 @@
+use clap::Args;
+use prettytable::{Cell, Row};
+use std::path::{Path, PathBuf};
+
+use super::meta::fetch_recent_decks;
+use crate::prelude::*;
+use mtg_core::decks::archetypes::default_archetypes_path;
+use mtg_core::decks::{
+    cluster_archetypes, ArchetypeDefinitions, ArchetypeReport, ClusterConfig, Similarity,
+};
+use mtg_core::ParsedDeck;
+
+#[derive(Args, Debug)]
+pub struct ArchetypesArgs {
+    /// Filter articles by format (e.g., standard, pioneer, modern)
+    #[clap(short, long)]
+    format: Option<String>,
+
+    /// Number of recent articles to cluster
+    #[clap(short, long, default_value = "5")]
+    articles: u32,
+
+    /// Archetype definitions file (defaults to ~/.config/mtg/archetypes.json)
+    #[clap(long, env = "MTG_ARCHETYPES_FILE", value_name = "FILE")]
+    definitions: Option<String>,
+
+    /// Similarity measure between decks (cosine or jaccard)
+    #[clap(long, default_value = "cosine")]
+    similarity: String,
+
+    /// Minimum similarity for decks to share an archetype (0.0 - 1.0)
+    #[clap(long, default_value = "0.5")]
+    threshold: f64,
+
+    /// Write an example definitions file and exit
+    #[clap(long)]
+    init: bool,
+
+    /// Output format (pretty table or JSON)
+    #[clap(long, default_value = "pretty")]
+    output: String,
+}
+
+impl ArchetypesArgs {
+    pub async fn run(self, global: &crate::Global) -> Result<()> {
+        let path = definitions_path(self.definitions.clone());
+
+        if self.init {
+            return init_definitions(&path);
+        }
+
+        if !(0.0..=1.0).contains(&self.threshold) {
+            return Err(eyre!("Threshold must be between 0.0 and 1.0"));
+        }
+
+        let config = ClusterConfig {
+            similarity: self.similarity.parse::<Similarity>()?,
+            threshold: self.threshold,
+        };
+        let definitions = ArchetypeDefinitions::load_or_default(&path)?;
+
+        let (decks, _) = fetch_recent_decks(self.format.clone(), self.articles, global).await?;
+        let report = cluster_archetypes(&decks, &definitions, &config);
+
+        match self.output.as_str() {
+            "json" => println!("{}", serde_json::to_string_pretty(&report)?),
+            _ => output_pretty(&report, &decks),
+        }
+
+        Ok(())
+    }
+}
+
+/// Resolve the definitions file from an option or the default location
+pub(crate) fn definitions_path(definitions: Option<String>) -> PathBuf {
+    definitions
+        .map(PathBuf::from)
+        .unwrap_or_else(default_archetypes_path)
+}
+
+/// Label decks with their archetypes using the definitions file
+pub(crate) fn label_decks(
+    decks: &[ParsedDeck],
+    definitions: Option<String>,
+) -> Result<ArchetypeReport> {
+    let definitions = ArchetypeDefinitions::load_or_default(&definitions_path(definitions))?;
+    Ok(cluster_archetypes(
+        decks,
+        &definitions,
+        &ClusterConfig::default(),
+    ))
+}
+
+fn init_definitions(path: &Path) -> Result<()> {
+    if path.exists() {
+        return Err(eyre!(
+            "Definitions file '{}' already exists",
+            path.display()
+        ));
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(
+        path,
+        serde_json::to_string_pretty(&ArchetypeDefinitions::example())?,
+    )?;
+
+    println!("Wrote example archetype definitions to {}", path.display());
+    Ok(())
+}
+
+fn output_pretty(report: &ArchetypeReport, decks: &[ParsedDeck]) {
+    println!("=== ARCHETYPES ===\n");
+    println!("Decks: {}\n", decks.len());
+
+    if report.clusters.is_empty() {
+        println!("No decks found.");
+        return;
+    }
+
+    let mut clusters_table = new_table();
+    clusters_table.add_row(Row::new(vec![
+        Cell::new("Archetype"),
+        Cell::new("Colors"),
+        Cell::new("Decks"),
+        Cell::new("Share"),
+        Cell::new("Core Cards"),
+    ]));
+    for cluster in &report.clusters {
+        clusters_table.add_row(Row::new(vec![
+            Cell::new(&cluster.name),
+            Cell::new(&cluster.colors),
+            Cell::new(&cluster.decks.len().to_string()),
+            Cell::new(&format!("{:.1}%", cluster.share * 100.0)),
+            Cell::new(&cluster.core_cards.join("\n")),
+        ]));
+    }
+    clusters_table.printstd();
+    println!();
+
+    println!("Decks:");
+    let mut decks_table = new_table();
+    decks_table.add_row(Row::new(vec![
+        Cell::new("Id"),
+        Cell::new("Title"),
+        Cell::new("Archetype"),
+        Cell::new("Confidence"),
+    ]));
+    for deck in decks {
+        let Some(archetype) = report.deck(&deck.id) else {
+            continue;
+        };
+        decks_table.add_row(Row::new(vec![
+            Cell::new(&deck.id),
+            Cell::new(deck.title.as_deref().unwrap_or("")),
+            Cell::new(&archetype.archetype),
+            Cell::new(&format!("{:.0}%", archetype.confidence * 100.0)),
+        ]));
+    }
+    decks_table.printstd();
+    println!();
+}
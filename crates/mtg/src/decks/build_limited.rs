@@ -0,0 +1,239 @@
+use clap::Args;
+use clap_stdin::MaybeStdin;
+use color_eyre::owo_colors::OwoColorize;
+use prettytable::{Cell, Row};
+
+use super::utils::{convert_cli_deck_list_to_core, load_deck_with_details};
+use crate::companion::cards::{init_resolver, resolve_card, save_card_map};
+use crate::prelude::*;
+use mtg_core::decks::limited::parse_arena_draft_picks;
+use mtg_core::decks::roles::default_roles_path;
+use mtg_core::decks::utils::format_deck_list;
+use mtg_core::decks::{build_limited, LimitedBuild, LimitedConfig, RoleOverrides};
+use mtg_core::sealed::{fetch_set_cards, generate_pool, BoosterKind, BoosterSheets, SealedConfig};
+use mtg_core::{DeckCard, DeckList};
+
+#[derive(Args, Debug)]
+pub struct BuildLimitedArgs {
+    /// Card pool input (use '-' for stdin, provide deck list as string, deck ID, or library deck name)
+    #[clap(value_name = "POOL")]
+    input: Option<MaybeStdin<String>>,
+
+    /// Read the card pool from file
+    #[clap(short, long, value_name = "FILE")]
+    file: Option<String>,
+
+    /// Generate a sealed pool from this set instead of reading one
+    #[clap(long, value_name = "SET", conflicts_with_all = ["input", "file", "arena_log"])]
+    sealed: Option<String>,
+
+    /// Number of boosters in the generated sealed pool
+    #[clap(long, default_value = "6", requires = "sealed")]
+    packs: u32,
+
+    /// Seed for the generated sealed pool
+    #[clap(long, requires = "sealed")]
+    seed: Option<u64>,
+
+    /// Use the picks of the last draft in an MTG Arena Player.log as the pool
+    #[clap(long, value_name = "FILE", conflicts_with_all = ["input", "file"])]
+    arena_log: Option<String>,
+
+    /// Only suggest straight two-color builds
+    #[clap(long)]
+    no_splash: bool,
+
+    /// Number of builds to suggest
+    #[clap(long, default_value = "3")]
+    builds: usize,
+
+    /// Output format (pretty, json, or text for the deck lists)
+    #[clap(long, default_value = "pretty")]
+    output: String,
+}
+
+impl BuildLimitedArgs {
+    pub async fn run(self, global: &crate::Global) -> Result<()> {
+        let pool = if let Some(set) = &self.sealed {
+            self.sealed_pool(set, global).await?
+        } else if let Some(path) = &self.arena_log {
+            arena_draft_pool(path, global).await?
+        } else {
+            let deck_list = load_deck_with_details(self.input, self.file, global).await?;
+            convert_cli_deck_list_to_core(&deck_list)
+        };
+
+        let overrides = RoleOverrides::load_or_default(&default_roles_path())?;
+        let config = LimitedConfig {
+            splash: !self.no_splash,
+            builds: self.builds,
+            ..Default::default()
+        };
+        let builds = build_limited(&pool, &overrides, &config)?;
+
+        match self.output.as_str() {
+            "json" => println!("{}", serde_json::to_string_pretty(&builds)?),
+            "text" => {
+                for build in &builds {
+                    println!("// {} ({:.1})", build.label(), build.score);
+                    println!("{}", format_deck_list(&build.deck));
+                }
+            }
+            _ => {
+                for (index, build) in builds.iter().enumerate() {
+                    print_build(index + 1, build);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn sealed_pool(&self, set: &str, global: &crate::Global) -> Result<DeckList> {
+        let client = global.create_scryfall_client()?;
+        aeprintln!("Loading the cards of {}...", set.to_uppercase());
+        let cards = fetch_set_cards(&client, set).await?;
+        let sheets = BoosterSheets::from_cards(set, cards.clone())?;
+        let pool = generate_pool(
+            &sheets,
+            &SealedConfig {
+                kind: BoosterKind::Play,
+                packs: self.packs,
+                seed: self.seed,
+                mythic_rate: None,
+            },
+        );
+        aeprintln!(
+            "Opened {} boosters with seed {}",
+            pool.packs.len(),
+            pool.seed
+        );
+
+        // The set's cards already have every detail the builder needs
+        let mut deck_list = pool.to_deck_list();
+        for entry in &mut deck_list.main_deck {
+            entry.card_details = cards.iter().find(|card| card.name == entry.name).cloned();
+        }
+        Ok(deck_list)
+    }
+}
+
+/// Resolve the picks of the last Arena draft, then fetch their details from Scryfall
+async fn arena_draft_pool(path: &str, global: &crate::Global) -> Result<DeckList> {
+    let log = std::fs::read_to_string(path)
+        .map_err(|e| eyre!("Failed to read log file '{}': {}", path, e))?;
+    let picks = parse_arena_draft_picks(&log);
+    if picks.is_empty() {
+        return Err(eyre!("No draft picks found in '{}'", path));
+    }
+
+    aeprintln!("Resolving {} draft picks...", picks.len());
+    init_resolver(global);
+    let mut main_deck: Vec<DeckCard> = Vec::new();
+    for arena_id in picks {
+        let card = match resolve_card(arena_id).await {
+            Ok(card) => card,
+            Err(e) => {
+                aeprintln!("Could not resolve Arena card {}: {}", arena_id, e);
+                continue;
+            }
+        };
+        match main_deck.iter_mut().find(|entry| entry.name == card.name) {
+            Some(entry) => entry.quantity += 1,
+            // Looked up by name only: Arena's set codes don't always exist on Scryfall
+            None => main_deck.push(DeckCard {
+                quantity: 1,
+                name: card.name.clone(),
+                set_code: None,
+                collector_number: None,
+                card_details: None,
+            }),
+        }
+    }
+    save_card_map().await;
+
+    let client = global.create_scryfall_client()?;
+    mtg_core::decks::utils::fetch_card_details(
+        DeckList {
+            main_deck,
+            sideboard: Vec::new(),
+        },
+        &client,
+    )
+    .await
+}
+
+fn print_build(rank: usize, build: &LimitedBuild) {
+    println!(
+        "\n{}",
+        f!(
+            "#{} {} - score {:.1}, {} playables",
+            rank,
+            build.label(),
+            build.score,
+            build.playables
+        )
+        .bold()
+        .underline()
+    );
+
+    let mut table = new_table();
+    table.add_row(Row::new(vec![
+        Cell::new("Qty"),
+        Cell::new("Name"),
+        Cell::new("Cost"),
+        Cell::new("Type"),
+        Cell::new("Rarity"),
+    ]));
+    for card in &build.deck.main_deck {
+        let details = card.card_details.as_ref();
+        table.add_row(Row::new(vec![
+            Cell::new(&card.quantity.to_string()),
+            Cell::new(&card.name),
+            Cell::new(
+                details
+                    .and_then(|details| details.mana_cost.as_deref())
+                    .unwrap_or(""),
+            ),
+            Cell::new(
+                details
+                    .map(|details| details.type_line.as_str())
+                    .unwrap_or("Basic Land"),
+            ),
+            Cell::new(details.map(|details| details.rarity.as_str()).unwrap_or("")),
+        ]));
+    }
+    table.printstd();
+
+    let mut curve: Vec<_> = build.stats.mana_curve.iter().collect();
+    curve.sort_by_key(|(mv, _)| **mv);
+    let curve: Vec<String> = curve.iter().map(|(mv, count)| f!("{mv}:{count}")).collect();
+    println!("Curve: {}", curve.join(" "));
+
+    let roles: Vec<String> = build
+        .stats
+        .roles
+        .roles
+        .iter()
+        .filter(|role| role.role != mtg_core::decks::Role::Land)
+        .map(|role| f!("{} {}", role.count, role.role.label()))
+        .collect();
+    if !roles.is_empty() {
+        println!("Roles: {}", roles.join(", "));
+    }
+
+    let basics: Vec<String> = build
+        .basics
+        .iter()
+        .map(|(name, count)| f!("{count} {name}"))
+        .collect();
+    println!("Basics: {}", basics.join(", "));
+
+    let under_supported = &build.stats.mana_base.under_supported_colors;
+    if !under_supported.is_empty() {
+        println!(
+            "{}",
+            f!("Under-supported colors: {}", under_supported.join(", ")).yellow()
+        );
+    }
+}
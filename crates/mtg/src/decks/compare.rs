@@ -1,48 +1,306 @@
 use crate::prelude::*;
 use clap::Args;
 use color_eyre::owo_colors::OwoColorize;
-use mtg_core::cache::{CachedHttpClient, DiskCacheBuilder};
-use mtg_core::decks::{compare_decks, load_deck_from_id_or_url};
-use mtg_core::RankedDecksClient;
-use prettytable::row;
+use mtg_core::cache::{CachedHttpClient, DiskCache, DiskCacheBuilder};
+use mtg_core::decks::library::DeckSection;
+use mtg_core::decks::{
+    compare_decks, compare_many_decks, load_deck_from_id_or_url, CardRole, DeckLibrary,
+    MultiDeckComparison,
+};
+use mtg_core::{parse_deck_list, ParsedDeck, RankedDecksClient};
+use prettytable::{row, Cell, Row};
+use std::io::Read;
+use std::path::Path;
 
 #[derive(Args, Debug)]
 pub struct CompareArgs {
-    #[arg(help = "First deck ID or article ID")]
-    deck1: String,
+    /// Decks to compare: deck IDs, article IDs, URLs, library deck names, files, or '-' for stdin
+    #[arg(value_name = "DECK")]
+    decks: Vec<String>,
 
-    #[arg(help = "Second deck ID or article ID")]
-    deck2: String,
+    /// Compare every deck of an article (ID or URL)
+    #[clap(long, value_name = "ID_OR_URL")]
+    article: Option<String>,
+
+    /// Only compare the article decks of this archetype
+    #[clap(long, requires = "article")]
+    archetype: Option<String>,
+
+    /// Archetype definitions file used with --archetype
+    #[clap(long, env = "MTG_ARCHETYPES_FILE", value_name = "FILE")]
+    definitions: Option<String>,
+
+    /// Output format (pretty table, JSON or CSV)
+    #[clap(long, default_value = "pretty")]
+    output: String,
 }
 
 impl CompareArgs {
     pub async fn run(&self, global: &crate::Global) -> Result<()> {
-        // Create cache and HTTP client - use same prefixes as other commands
-        let cache = DiskCacheBuilder::new().prefix("ranked_list").build()?;
+        let (ranked_client, cache) = ranked_client(global)?;
+
+        let mut decks = Vec::new();
+        if let Some(article) = &self.article {
+            let mut article_decks = ranked_client.fetch_decks_from_article(article).await?;
 
-        let http_client = CachedHttpClient::builder()
-            .timeout(std::time::Duration::from_secs(global.timeout))
-            .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36")
-            .cache_prefix("ranked_list_http")
-            .build()?;
+            if let Some(archetype) = &self.archetype {
+                let report = crate::decks::archetypes::label_decks(
+                    &article_decks,
+                    self.definitions.clone(),
+                )?;
+                article_decks.retain(|deck| {
+                    report
+                        .deck(&deck.id)
+                        .is_some_and(|label| label.archetype.eq_ignore_ascii_case(archetype))
+                });
 
-        // Create ranked decks client
-        let ranked_client = RankedDecksClient::new(http_client, cache.clone());
+                if article_decks.is_empty() {
+                    return Err(eyre!(
+                        "No decks of archetype '{}' in the article",
+                        archetype
+                    ));
+                }
+            }
 
-        // Load both decks using mtg_core business logic
-        let deck1 = load_deck_from_id_or_url(&self.deck1, &ranked_client, &cache).await?;
-        let deck2 = load_deck_from_id_or_url(&self.deck2, &ranked_client, &cache).await?;
+            decks.extend(article_decks);
+        }
+
+        let mut stdin_used = false;
+        for identifier in &self.decks {
+            decks.push(load_deck(identifier, &ranked_client, &cache, &mut stdin_used).await?);
+        }
 
-        // Compare decks using mtg_core business logic
-        let comparison = compare_decks(&deck1, &deck2);
+        if decks.len() < 2 {
+            return Err(eyre!(
+                "At least two decks are needed for a comparison, got {}",
+                decks.len()
+            ));
+        }
 
-        // Display results (presentation layer)
-        display_comparison(&comparison);
+        match self.output.as_str() {
+            "json" => {
+                let comparison = compare_many_decks(&decks);
+                println!("{}", serde_json::to_string_pretty(&comparison)?);
+            }
+            "csv" => output_csv(&compare_many_decks(&decks))?,
+            _ if decks.len() == 2 => display_comparison(&compare_decks(&decks[0], &decks[1])),
+            _ => display_many(&compare_many_decks(&decks)),
+        }
 
         Ok(())
     }
 }
 
+/// Ranked decks client and cache, using the same prefixes as other commands
+pub(super) fn ranked_client(global: &crate::Global) -> Result<(RankedDecksClient, DiskCache)> {
+    let cache = DiskCacheBuilder::new().prefix("ranked_list").build()?;
+
+    let http_client = CachedHttpClient::builder()
+        .timeout(std::time::Duration::from_secs(global.timeout))
+        .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36")
+        .cache_prefix("ranked_list_http")
+        .build()?;
+
+    Ok((RankedDecksClient::new(http_client, cache.clone()), cache))
+}
+
+/// Load a deck from stdin ('-'), a file, the library, or a cached ID or URL
+pub(super) async fn load_deck(
+    identifier: &str,
+    ranked_client: &RankedDecksClient,
+    cache: &DiskCache,
+    stdin_used: &mut bool,
+) -> Result<ParsedDeck> {
+    if identifier == "-" {
+        if *stdin_used {
+            return Err(eyre!("Standard input can only be used for one deck"));
+        }
+        *stdin_used = true;
+
+        let mut content = String::new();
+        std::io::stdin()
+            .read_to_string(&mut content)
+            .map_err(|e| eyre!("Failed to read from stdin: {}", e))?;
+        return Ok(deck_list_to_parsed_deck(
+            "stdin",
+            parse_deck_list(&content)?,
+        ));
+    }
+
+    let path = Path::new(identifier);
+    if path.is_file() {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| eyre!("Failed to read file '{}': {}", identifier, e))?;
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(identifier);
+        return Ok(deck_list_to_parsed_deck(name, parse_deck_list(&content)?));
+    }
+
+    let library = DeckLibrary::open_default();
+    if library.contains(identifier) {
+        let deck = library.get(identifier)?;
+        return Ok(deck_list_to_parsed_deck(
+            &deck.name,
+            deck.latest().deck_list.clone(),
+        ));
+    }
+
+    load_deck_from_id_or_url(identifier, ranked_client, cache).await
+}
+
+fn deck_list_to_parsed_deck(name: &str, deck_list: mtg_core::DeckList) -> ParsedDeck {
+    ParsedDeck {
+        id: name.to_string(),
+        title: Some(name.to_string()),
+        subtitle: None,
+        event_date: None,
+        event_name: None,
+        format: None,
+        main_deck: deck_list.main_deck,
+        sideboard: deck_list.sideboard,
+    }
+}
+
+fn display_many(comparison: &MultiDeckComparison) {
+    println!("\n{}", "Deck Comparison".bold().underline());
+    for (index, name) in comparison.decks.iter().enumerate() {
+        println!("{}: {}", format!("D{}", index + 1).cyan(), name);
+    }
+
+    println!("\n{}", "Summary".bold());
+    println!(
+        "Core cards: {}",
+        comparison
+            .cards_with_role(CardRole::Core)
+            .count()
+            .to_string()
+            .green()
+    );
+    println!(
+        "Flex cards: {}",
+        comparison
+            .cards_with_role(CardRole::Flex)
+            .count()
+            .to_string()
+            .yellow()
+    );
+    println!(
+        "Sideboard only: {}",
+        comparison
+            .cards_with_role(CardRole::Sideboard)
+            .count()
+            .to_string()
+            .yellow()
+    );
+
+    // Card by deck matrix
+    println!("\n{} (main/side)", "Cards".bold());
+    let mut table = new_table();
+    let mut header = vec![Cell::new("Card Name")];
+    header.extend((1..=comparison.decks.len()).map(|i| Cell::new(&format!("D{i}"))));
+    header.push(Cell::new("Role"));
+    header.push(Cell::new("Consensus"));
+    table.add_row(Row::new(header));
+
+    for card in &comparison.cards {
+        let mut cells = vec![Cell::new(&card.name)];
+        cells.extend(card.counts.iter().map(|count| {
+            if count.total() == 0 {
+                Cell::new("-")
+            } else {
+                Cell::new(&format!("{}/{}", count.main_count, count.side_count))
+            }
+        }));
+        cells.push(Cell::new(role_name(card.role)));
+        cells.push(Cell::new(&format!(
+            "{}/{}",
+            card.consensus.main_count, card.consensus.side_count
+        )));
+        table.add_row(Row::new(cells));
+    }
+    table.printstd();
+
+    // Consensus list
+    let consensus = comparison.consensus_list();
+    let main: Vec<_> = consensus.iter().filter(|(_, main, _)| *main > 0).collect();
+    let side: Vec<_> = consensus.iter().filter(|(_, _, side)| *side > 0).collect();
+    println!(
+        "\n{} ({} main, {} sideboard)",
+        "Consensus List".bold().green(),
+        main.iter().map(|(_, main, _)| main).sum::<u32>(),
+        side.iter().map(|(_, _, side)| side).sum::<u32>()
+    );
+    for (name, main, _) in &main {
+        println!("{} {}", main, name);
+    }
+    if !side.is_empty() {
+        println!("\nSideboard");
+        for (name, _, side) in &side {
+            println!("{} {}", side, name);
+        }
+    }
+
+    // Deviations from the consensus
+    println!("\n{}", "Deviations from Consensus".bold().yellow());
+    let mut table = new_table();
+    table.add_row(row!["Deck", "Main Swing", "Side Swing", "Changes"]);
+    for (index, deviation) in comparison.deviations.iter().enumerate() {
+        let changes: Vec<String> = deviation
+            .changes
+            .iter()
+            .map(|change| {
+                let section = match change.section {
+                    DeckSection::Main => "",
+                    DeckSection::Sideboard => " (SB)",
+                };
+                format!("{:+} {}{}", change.delta(), change.name, section)
+            })
+            .collect();
+        table.add_row(row![
+            format!("D{} {}", index + 1, deviation.deck),
+            deviation.main_swing,
+            deviation.sideboard_swing,
+            changes.join("\n")
+        ]);
+    }
+    table.printstd();
+}
+
+fn role_name(role: CardRole) -> &'static str {
+    match role {
+        CardRole::Core => "core",
+        CardRole::Flex => "flex",
+        CardRole::Sideboard => "sideboard",
+    }
+}
+
+fn output_csv(comparison: &MultiDeckComparison) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    let mut header = vec!["card".to_string()];
+    for name in &comparison.decks {
+        header.push(format!("{name} main"));
+        header.push(format!("{name} side"));
+    }
+    header.extend(["role", "consensus_main", "consensus_side"].map(String::from));
+    writer.write_record(&header)?;
+
+    for card in &comparison.cards {
+        let mut fields = vec![card.name.clone()];
+        for count in &card.counts {
+            fields.push(count.main_count.to_string());
+            fields.push(count.side_count.to_string());
+        }
+        fields.push(role_name(card.role).to_string());
+        fields.push(card.consensus.main_count.to_string());
+        fields.push(card.consensus.side_count.to_string());
+        writer.write_record(&fields)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
 fn display_comparison(comparison: &mtg_core::decks::DeckComparison) {
     println!("\n{}", "Deck Comparison".bold().underline());
     println!("{}: {}", "Deck 1".cyan(), comparison.deck1_name);
@@ -0,0 +1,169 @@
+use crate::prelude::*;
+use clap::Args;
+use color_eyre::owo_colors::OwoColorize;
+use mtg_core::decks::library::DeckSection;
+use mtg_core::decks::utils::format_deck_list;
+use mtg_core::decks::{
+    apply_deck_diff, diff_decks, format_deck_diff, parse_deck_diff, DeckDiff, DiffLine,
+};
+use mtg_core::{DeckList, ParsedDeck};
+
+use super::compare::{load_deck, ranked_client};
+
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    /// Old deck: deck ID, URL, library deck name, file, or '-' for stdin
+    #[arg(value_name = "OLD")]
+    old: String,
+
+    /// New deck: deck ID, URL, library deck name, file, or '-' for stdin
+    #[arg(value_name = "NEW", required_unless_present = "apply")]
+    new: Option<String>,
+
+    /// Apply a diff file (as printed with --output text) to OLD and print the new deck list
+    #[clap(long, value_name = "DIFF_FILE", conflicts_with = "new")]
+    apply: Option<String>,
+
+    /// Output format (pretty, text for a patch file, or json)
+    #[clap(long, default_value = "pretty")]
+    output: String,
+}
+
+impl DiffArgs {
+    pub async fn run(&self, global: &crate::Global) -> Result<()> {
+        let (ranked_client, cache) = ranked_client(global)?;
+        let mut stdin_used = false;
+
+        let old = load_deck(&self.old, &ranked_client, &cache, &mut stdin_used).await?;
+
+        if let Some(path) = &self.apply {
+            let text = if path == "-" {
+                if stdin_used {
+                    return Err(eyre!("Standard input can only be used once"));
+                }
+                std::io::read_to_string(std::io::stdin())
+                    .map_err(|e| eyre!("Failed to read from stdin: {}", e))?
+            } else {
+                std::fs::read_to_string(path)
+                    .map_err(|e| eyre!("Failed to read diff file '{}': {}", path, e))?
+            };
+            let diff = parse_deck_diff(&text)?;
+            let patched = apply_deck_diff(&deck_list(&old), &diff)?;
+
+            if self.output == "json" {
+                println!("{}", serde_json::to_string_pretty(&patched)?);
+            } else {
+                print!("{}", format_deck_list(&patched));
+            }
+            return Ok(());
+        }
+
+        let new_id = self.new.as_deref().unwrap_or_default();
+        let new = load_deck(new_id, &ranked_client, &cache, &mut stdin_used).await?;
+        let diff = diff_decks(&deck_list(&old), &deck_list(&new));
+
+        match self.output.as_str() {
+            "json" => println!("{}", serde_json::to_string_pretty(&diff)?),
+            "text" => {
+                println!("--- {}", deck_name(&old));
+                println!("+++ {}", deck_name(&new));
+                print!("{}", format_deck_diff(&diff));
+            }
+            _ => print_diff(&old, &new, &diff),
+        }
+
+        Ok(())
+    }
+}
+
+fn deck_list(deck: &ParsedDeck) -> DeckList {
+    DeckList {
+        main_deck: deck.main_deck.clone(),
+        sideboard: deck.sideboard.clone(),
+    }
+}
+
+fn deck_name(deck: &ParsedDeck) -> &str {
+    deck.title.as_deref().unwrap_or(&deck.id)
+}
+
+fn print_diff(old: &ParsedDeck, new: &ParsedDeck, diff: &DeckDiff) {
+    println!(
+        "{} {} {}",
+        deck_name(old).bold(),
+        "->".dimmed(),
+        deck_name(new).bold()
+    );
+
+    if diff.is_empty() {
+        println!("\nNo changes");
+        return;
+    }
+
+    let mut header = None;
+    for line in &diff.lines {
+        let group = match line {
+            DiffLine::Change {
+                section: DeckSection::Main,
+                ..
+            } => "Main Deck",
+            DiffLine::Change {
+                section: DeckSection::Sideboard,
+                ..
+            } => "Sideboard",
+            DiffLine::Move { .. } => "Moved",
+        };
+        if header != Some(group) {
+            println!("\n{}", group.bold());
+            header = Some(group);
+        }
+
+        match line {
+            DiffLine::Change { name, delta, .. } if *delta > 0 => {
+                println!("{}", f!("+{} {}", delta, name).green())
+            }
+            DiffLine::Change { name, delta, .. } => {
+                println!("{}", f!("-{} {}", delta.abs(), name).red())
+            }
+            DiffLine::Move {
+                name,
+                quantity,
+                from,
+                to,
+            } => println!(
+                "{}",
+                f!(
+                    "~{} {} ({} -> {})",
+                    quantity,
+                    name,
+                    section_label(*from),
+                    section_label(*to)
+                )
+                .yellow()
+            ),
+        }
+    }
+
+    let (added, removed) = diff.totals();
+    let moved: u32 = diff
+        .lines
+        .iter()
+        .map(|line| match line {
+            DiffLine::Move { quantity, .. } => *quantity,
+            _ => 0,
+        })
+        .sum();
+    println!(
+        "\n{} added, {} removed, {} moved",
+        added.to_string().green(),
+        removed.to_string().red(),
+        moved.to_string().yellow()
+    );
+}
+
+fn section_label(section: DeckSection) -> &'static str {
+    match section {
+        DeckSection::Main => "main",
+        DeckSection::Sideboard => "sideboard",
+    }
+}
@@ -0,0 +1,299 @@
+use clap::Args;
+use clap_stdin::MaybeStdin;
+use prettytable::{Cell, Row};
+
+use super::utils::{convert_cli_deck_list_to_core, read_deck_input, resolve_deck_list};
+use crate::prelude::*;
+use mtg_core::decks::library::{CardChange, DeckSection};
+use mtg_core::decks::utils::format_deck_list;
+use mtg_core::decks::{DeckLibrary, LibraryDeck, SaveOutcome};
+
+#[derive(Args, Debug)]
+pub struct SaveArgs {
+    /// Name of the deck in the library
+    name: String,
+
+    /// Deck list input (use '-' for stdin, provide deck list as string, deck ID, or omit to read from stdin)
+    #[clap(value_name = "DECK_LIST_OR_ID")]
+    input: Option<MaybeStdin<String>>,
+
+    /// Read deck list from file
+    #[clap(short, long, value_name = "FILE")]
+    file: Option<String>,
+
+    /// Describe the changes in this revision
+    #[clap(short, long)]
+    message: Option<String>,
+
+    /// Tags to add to the deck
+    #[clap(short, long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+
+    /// Replace the deck's notes
+    #[clap(long)]
+    notes: Option<String>,
+}
+
+impl SaveArgs {
+    pub async fn run(self, global: &crate::Global) -> Result<()> {
+        let source = match (&self.file, &self.input) {
+            (Some(file), _) => Some(file.clone()),
+            (None, Some(input)) if !input.contains('\n') => Some(input.trim().to_string()),
+            _ => None,
+        };
+
+        let deck_content = read_deck_input(self.input, self.file)?;
+        let (deck_list, _) = resolve_deck_list(&deck_content, global).await?;
+        let core_deck_list = convert_cli_deck_list_to_core(&deck_list);
+
+        let library = DeckLibrary::open_default();
+        let outcome = library.save(&self.name, &core_deck_list, self.message, source)?;
+
+        let slug = outcome.deck().slug.clone();
+        if !self.tags.is_empty() {
+            library.tag(&slug, &self.tags, &[])?;
+        }
+        if let Some(notes) = self.notes {
+            library.set_notes(&slug, Some(notes))?;
+        }
+
+        match &outcome {
+            SaveOutcome::Created(deck) => {
+                println!("Saved '{}' as a new deck ({})", deck.name, deck.slug)
+            }
+            SaveOutcome::Updated(deck) => {
+                let revision = deck.latest();
+                println!("Saved revision {} of '{}'", revision.number, deck.name);
+                print_changes(&revision.changes);
+            }
+            SaveOutcome::Unchanged(deck) => println!(
+                "No changes to '{}' (revision {})",
+                deck.name,
+                deck.latest().number
+            ),
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct ListArgs {
+    /// Only show decks with this tag
+    #[clap(short, long)]
+    tag: Option<String>,
+
+    /// Output format (pretty table or JSON)
+    #[clap(long, default_value = "pretty")]
+    output: String,
+}
+
+impl ListArgs {
+    pub fn run(self) -> Result<()> {
+        let library = DeckLibrary::open_default();
+        let decks: Vec<LibraryDeck> = library
+            .list()?
+            .into_iter()
+            .filter(|deck| match &self.tag {
+                Some(tag) => deck.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)),
+                None => true,
+            })
+            .collect();
+
+        if self.output == "json" {
+            let summaries: Vec<serde_json::Value> = decks
+                .iter()
+                .map(|deck| {
+                    serde_json::json!({
+                        "name": deck.name,
+                        "slug": deck.slug,
+                        "tags": deck.tags,
+                        "revisions": deck.revisions.len(),
+                        "main_deck_cards": card_count(deck, DeckSection::Main),
+                        "sideboard_cards": card_count(deck, DeckSection::Sideboard),
+                        "created_at": deck.created_at,
+                        "updated_at": deck.updated_at,
+                    })
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&summaries)?);
+            return Ok(());
+        }
+
+        if decks.is_empty() {
+            println!("No decks in the library ({}).", library.root().display());
+            return Ok(());
+        }
+
+        let mut table = new_table();
+        table.add_row(Row::new(vec![
+            Cell::new("Name"),
+            Cell::new("Id"),
+            Cell::new("Cards"),
+            Cell::new("Revisions"),
+            Cell::new("Tags"),
+            Cell::new("Updated"),
+        ]));
+        for deck in &decks {
+            table.add_row(Row::new(vec![
+                Cell::new(&deck.name),
+                Cell::new(&deck.slug),
+                Cell::new(&format!(
+                    "{} + {}",
+                    card_count(deck, DeckSection::Main),
+                    card_count(deck, DeckSection::Sideboard)
+                )),
+                Cell::new(&deck.revisions.len().to_string()),
+                Cell::new(&deck.tags.join(", ")),
+                Cell::new(&deck.updated_at.format("%Y-%m-%d %H:%M").to_string()),
+            ]));
+        }
+        table.printstd();
+
+        Ok(())
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct ShowArgs {
+    /// Name of the deck in the library
+    name: String,
+
+    /// Show this revision instead of the latest one
+    #[clap(short, long)]
+    revision: Option<u32>,
+
+    /// Show the revision history with the changes of every revision
+    #[clap(long)]
+    history: bool,
+
+    /// Output format (pretty, JSON or the plain deck list)
+    #[clap(long, default_value = "pretty")]
+    output: String,
+}
+
+impl ShowArgs {
+    pub fn run(self) -> Result<()> {
+        let deck = DeckLibrary::open_default().get(&self.name)?;
+        let revision = match self.revision {
+            Some(number) => deck
+                .revision(number)
+                .ok_or_else(|| eyre!("Deck '{}' has no revision {}", deck.name, number))?,
+            None => deck.latest(),
+        };
+
+        match self.output.as_str() {
+            "json" => {
+                println!("{}", serde_json::to_string_pretty(&deck)?);
+                return Ok(());
+            }
+            "text" => {
+                print!("{}", format_deck_list(&revision.deck_list));
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        println!("=== {} ===\n", deck.name);
+        println!("Id: {}", deck.slug);
+        println!(
+            "Revision: {} of {} ({})",
+            revision.number,
+            deck.revisions.len(),
+            revision.created_at.format("%Y-%m-%d %H:%M")
+        );
+        if !deck.tags.is_empty() {
+            println!("Tags: {}", deck.tags.join(", "));
+        }
+        if let Some(source) = &deck.source {
+            println!("Source: {}", source);
+        }
+        if let Some(notes) = &deck.notes {
+            println!("Notes: {}", notes);
+        }
+        println!();
+        print!("{}", format_deck_list(&revision.deck_list));
+
+        if self.history {
+            println!("\nHistory:");
+            for revision in deck.revisions.iter().rev() {
+                println!(
+                    "\nRevision {} - {}{}",
+                    revision.number,
+                    revision.created_at.format("%Y-%m-%d %H:%M"),
+                    revision
+                        .message
+                        .as_deref()
+                        .map(|message| format!(" - {message}"))
+                        .unwrap_or_default()
+                );
+                print_changes(&revision.changes);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct RmArgs {
+    /// Name of the deck in the library
+    name: String,
+}
+
+impl RmArgs {
+    pub fn run(self) -> Result<()> {
+        let deck = DeckLibrary::open_default().remove(&self.name)?;
+        println!(
+            "Removed '{}' and its {} revision(s)",
+            deck.name,
+            deck.revisions.len()
+        );
+        Ok(())
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct TagArgs {
+    /// Name of the deck in the library
+    name: String,
+
+    /// Tags to add
+    #[clap(value_name = "TAG")]
+    tags: Vec<String>,
+
+    /// Tags to remove
+    #[clap(short, long = "remove", value_name = "TAG")]
+    remove: Vec<String>,
+}
+
+impl TagArgs {
+    pub fn run(self) -> Result<()> {
+        let deck = DeckLibrary::open_default().tag(&self.name, &self.tags, &self.remove)?;
+        if deck.tags.is_empty() {
+            println!("'{}' has no tags", deck.name);
+        } else {
+            println!("'{}' tags: {}", deck.name, deck.tags.join(", "));
+        }
+        Ok(())
+    }
+}
+
+fn card_count(deck: &LibraryDeck, section: DeckSection) -> u32 {
+    let deck_list = &deck.latest().deck_list;
+    let cards = match section {
+        DeckSection::Main => &deck_list.main_deck,
+        DeckSection::Sideboard => &deck_list.sideboard,
+    };
+    cards.iter().map(|card| card.quantity).sum()
+}
+
+fn print_changes(changes: &[CardChange]) {
+    for change in changes {
+        let section = match change.section {
+            DeckSection::Main => "",
+            DeckSection::Sideboard => " (sideboard)",
+        };
+        println!("  {:+} {}{}", change.delta(), change.name, section);
+    }
+}
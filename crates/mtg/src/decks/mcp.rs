@@ -1,10 +1,15 @@
 use crate::prelude::*;
 
 use super::utils::{calculate_deck_stats_cli, fetch_card_details_with_global};
+use mtg_core::decks::{find_budget_alternatives, Currency};
 use mtg_core::parse_deck_list;
 
 // Function for MCP integration
-pub async fn analyze_deck_list_mcp(deck_content: &str, global: crate::Global) -> Result<String> {
+pub async fn analyze_deck_list_mcp(
+    deck_content: &str,
+    budget: Option<Currency>,
+    global: crate::Global,
+) -> Result<String> {
     // Check if content is empty
     if deck_content.trim().is_empty() {
         return Err(eyre!(
@@ -59,6 +64,120 @@ pub async fn analyze_deck_list_mcp(deck_content: &str, global: crate::Global) ->
         output.push('\n');
     }
 
+    // Roles
+    if !stats.roles.roles.is_empty() {
+        output.push_str("Roles:\n");
+        for role in &stats.roles.roles {
+            let curve: Vec<String> = role
+                .curve
+                .iter()
+                .map(|(mv, count)| format!("{mv}:{count}"))
+                .collect();
+            output.push_str(&format!(
+                "  {}: {} cards (curve {}): {}\n",
+                role.role.label(),
+                role.count,
+                curve.join(" "),
+                role.cards.join(", ")
+            ));
+        }
+        output.push('\n');
+    }
+
+    // Mana base
+    if !stats.mana_base.sources.is_empty() {
+        output.push_str("Mana Sources:\n");
+        for sources in &stats.mana_base.sources {
+            output.push_str(&format!(
+                "  {}: {} sources ({} lands, {} fetches, {} rocks/dorks)\n",
+                sources.color, sources.total, sources.lands, sources.fetches, sources.rocks
+            ));
+        }
+        output.push('\n');
+    }
+
+    if !stats.mana_base.requirements.is_empty() {
+        output.push_str("Colored Requirements:\n");
+        for requirement in &stats.mana_base.requirements {
+            output.push_str(&format!(
+                "  {} x{} by turn {} ({}): {}/{} sources{}\n",
+                requirement.color,
+                requirement.pips,
+                requirement.turn,
+                requirement.card,
+                requirement.available_sources,
+                requirement.recommended_sources,
+                if requirement.supported {
+                    ""
+                } else {
+                    " - UNDER-SUPPORTED"
+                }
+            ));
+        }
+        output.push('\n');
+    }
+
+    // Pricing
+    if !stats.pricing.totals.is_empty() {
+        output.push_str("Deck Price:\n");
+    }
+    for total in &stats.pricing.totals {
+        output.push_str(&format!(
+            "  {}: {} (main {}, sideboard {})\n",
+            total.currency,
+            total.currency.format(total.total),
+            total.currency.format(total.main_deck),
+            total.currency.format(total.sideboard)
+        ));
+    }
+    if let Some(currency) = stats.pricing.totals.first().map(|total| total.currency) {
+        output.push_str("Most Expensive Cards:\n");
+        for card in &stats.pricing.most_expensive {
+            output.push_str(&format!(
+                "  {}x {}: {} ({} each)\n",
+                card.quantity,
+                card.name,
+                currency.format(card.total_price),
+                currency.format(card.unit_price)
+            ));
+        }
+        output.push('\n');
+    }
+
+    if let Some(currency) = budget {
+        let scryfall_client = global.create_scryfall_client()?;
+        let core_deck_list = super::utils::convert_cli_deck_list_to_core(&deck_with_details);
+        let report = find_budget_alternatives(&core_deck_list, currency, &scryfall_client).await?;
+
+        output.push_str(&format!(
+            "Budget ({}): current {}, cheapest printings {}, savings {}\n",
+            currency,
+            currency.format(report.current_total),
+            currency.format(report.budget_total),
+            currency.format(report.savings)
+        ));
+        if !report.unpriced.is_empty() {
+            output.push_str(&format!(
+                "  Not in the totals (no {} price): {}\n",
+                currency,
+                report.unpriced.join(", ")
+            ));
+        }
+        for suggestion in &report.suggestions {
+            output.push_str(&format!(
+                "  {}x {}: {} ({}) {} at {} each, saves {}\n",
+                suggestion.quantity,
+                suggestion.name,
+                suggestion.cheapest_set_name,
+                suggestion.cheapest_set.to_uppercase(),
+                suggestion.cheapest_collector_number,
+                currency.format(suggestion.cheapest_price),
+                currency.format(suggestion.savings)
+            ));
+        }
+        output.push('\n');
+    }
+
     // Format legality
     if !stats.format_legality.is_empty() {
         output.push_str("Format Legality:\n");
@@ -0,0 +1,268 @@
+use clap::Args;
+use prettytable::{Cell, Row};
+use std::collections::HashMap;
+
+use crate::prelude::*;
+use mtg_core::cache::{CachedHttpClient, DiskCacheBuilder};
+use mtg_core::decks::mana::basic_land_color;
+use mtg_core::decks::{aggregate_meta, MetaReport};
+use mtg_core::{ParsedDeck, RankedDecksClient, RankedListParams};
+
+#[derive(Args, Debug)]
+pub struct MetaArgs {
+    /// Filter articles by format (e.g., standard, pioneer, modern)
+    #[clap(short, long)]
+    format: Option<String>,
+
+    /// Number of recent articles to aggregate
+    #[clap(short, long, default_value = "5")]
+    articles: u32,
+
+    /// Number of cards to show in the pretty output
+    #[clap(short, long, default_value = "40")]
+    top: usize,
+
+    /// Skip the Scryfall lookups used to work out deck colors (uses basic lands only)
+    #[clap(long)]
+    skip_colors: bool,
+
+    /// Output format (pretty table, JSON or CSV)
+    #[clap(long, default_value = "pretty")]
+    output: String,
+}
+
+impl MetaArgs {
+    pub async fn run(self, global: &crate::Global) -> Result<()> {
+        if self.articles == 0 {
+            return Err(eyre!("Number of articles must be 1 or greater"));
+        }
+
+        let (decks, article_count) =
+            fetch_recent_decks(self.format.clone(), self.articles, global).await?;
+
+        let card_colors = if self.skip_colors {
+            HashMap::new()
+        } else {
+            fetch_card_colors(&decks, global).await?
+        };
+
+        let mut report = aggregate_meta(&decks, &card_colors);
+        report.format = self.format.clone();
+        report.articles = article_count;
+
+        match self.output.as_str() {
+            "json" => println!("{}", serde_json::to_string_pretty(&report)?),
+            "csv" => output_csv(&report)?,
+            _ => output_pretty(&report, self.top),
+        }
+
+        Ok(())
+    }
+}
+
+/// Fetch and parse the decks of the most recent articles, returning the decks
+/// and the number of articles they came from
+pub(crate) async fn fetch_recent_decks(
+    format: Option<String>,
+    articles: u32,
+    global: &crate::Global,
+) -> Result<(Vec<ParsedDeck>, u32)> {
+    // Create cache and HTTP client
+    let cache = DiskCacheBuilder::new().prefix("ranked_list").build()?;
+
+    let http_client = CachedHttpClient::builder()
+        .timeout(std::time::Duration::from_secs(global.timeout))
+        .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36")
+        .cache_prefix("ranked_list_http")
+        .build()?;
+
+    let client = RankedDecksClient::new(http_client, cache);
+
+    let params = RankedListParams {
+        format_filter: format,
+        limit: articles,
+        skip: 0,
+    };
+    let response = client.fetch_ranked_list(params).await?;
+
+    if response.items_with_ids.is_empty() {
+        return Err(eyre!("No deck list articles found"));
+    }
+
+    let mut decks: Vec<ParsedDeck> = Vec::new();
+    let mut article_count = 0;
+    for article in &response.items_with_ids {
+        match client.fetch_article_decks_cached(article).await {
+            Ok(article_decks) => {
+                aeprintln!(
+                    "{}: {} decks",
+                    article.item.fields.article_title,
+                    article_decks.len()
+                );
+                decks.extend(article_decks);
+                article_count += 1;
+            }
+            Err(e) => aeprintln!(
+                "Failed to fetch decks from '{}': {}",
+                article.item.fields.article_title,
+                e
+            ),
+        }
+    }
+
+    Ok((decks, article_count))
+}
+
+/// Look up the colors of every nonbasic main deck card on Scryfall
+async fn fetch_card_colors(
+    decks: &[ParsedDeck],
+    global: &crate::Global,
+) -> Result<HashMap<String, Vec<String>>> {
+    let scryfall_client = global.create_scryfall_client()?;
+
+    let mut names: Vec<&str> = decks
+        .iter()
+        .flat_map(|deck| deck.main_deck.iter())
+        .map(|card| card.name.as_str())
+        .filter(|name| basic_land_color(name).is_none())
+        .collect();
+    names.sort();
+    names.dedup();
+
+    let mut card_colors = HashMap::new();
+    for name in names {
+        match scryfall_client.get_card_named(name, None).await {
+            Ok(card) => {
+                // Lands have no colors, use the colors they produce instead
+                let colors = if card.type_line.contains("Land") {
+                    Vec::new()
+                } else {
+                    card.colors.unwrap_or(card.color_identity)
+                };
+                card_colors.insert(name.to_string(), colors);
+            }
+            Err(e) => {
+                if global.verbose {
+                    aeprintln!("Failed to fetch colors for '{}': {}", name, e);
+                }
+            }
+        }
+    }
+
+    Ok(card_colors)
+}
+
+fn percent(rate: f64) -> String {
+    format!("{:.1}%", rate * 100.0)
+}
+
+fn output_pretty(report: &MetaReport, top: usize) {
+    println!("=== METAGAME ===\n");
+
+    if let Some(format) = &report.format {
+        println!("Format: {}", format.to_uppercase());
+    }
+    println!("Articles: {}", report.articles);
+    println!("Decks: {}\n", report.decks);
+
+    if report.decks == 0 {
+        println!("No decks found.");
+        return;
+    }
+
+    println!("Most Played Cards:");
+    let mut cards_table = new_table();
+    cards_table.add_row(Row::new(vec![
+        Cell::new("Card"),
+        Cell::new("Decks"),
+        Cell::new("Inclusion"),
+        Cell::new("Main"),
+        Cell::new("Side"),
+        Cell::new("Avg Main"),
+        Cell::new("Avg Side"),
+    ]));
+    for card in report.cards.iter().take(top) {
+        cards_table.add_row(Row::new(vec![
+            Cell::new(&card.name),
+            Cell::new(&card.decks.to_string()),
+            Cell::new(&percent(card.inclusion_rate)),
+            Cell::new(&percent(card.main_deck_rate)),
+            Cell::new(&percent(card.sideboard_rate)),
+            Cell::new(&format!("{:.2}", card.average_main_copies)),
+            Cell::new(&format!("{:.2}", card.average_sideboard_copies)),
+        ]));
+    }
+    cards_table.printstd();
+    println!();
+
+    println!("Colors:");
+    let mut colors_table = new_table();
+    colors_table.add_row(Row::new(vec![
+        Cell::new("Colors"),
+        Cell::new("Decks"),
+        Cell::new("Share"),
+    ]));
+    for pair in &report.color_pairs {
+        colors_table.add_row(Row::new(vec![
+            Cell::new(&pair.colors),
+            Cell::new(&pair.decks.to_string()),
+            Cell::new(&percent(pair.share)),
+        ]));
+    }
+    colors_table.printstd();
+    println!();
+
+    if report.cards.len() > top {
+        aeprintln!(
+            "Showing {} of {} cards. Use --top to show more.",
+            top,
+            report.cards.len()
+        );
+    }
+}
+
+/// Cards and color combinations in one CSV, told apart by the `kind` column.
+///
+/// Color rows carry the colors in `name` and their share of decks in `inclusion_rate`.
+fn output_csv(report: &MetaReport) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    writer.write_record([
+        "kind",
+        "name",
+        "decks",
+        "inclusion_rate",
+        "main_deck_rate",
+        "sideboard_rate",
+        "average_main_copies",
+        "average_sideboard_copies",
+        "average_copies",
+    ])?;
+    for card in &report.cards {
+        writer.write_record([
+            "card".to_string(),
+            card.name.clone(),
+            card.decks.to_string(),
+            format!("{:.4}", card.inclusion_rate),
+            format!("{:.4}", card.main_deck_rate),
+            format!("{:.4}", card.sideboard_rate),
+            format!("{:.2}", card.average_main_copies),
+            format!("{:.2}", card.average_sideboard_copies),
+            format!("{:.2}", card.average_copies),
+        ])?;
+    }
+    for pair in &report.color_pairs {
+        writer.write_record([
+            "colors".to_string(),
+            pair.colors.clone(),
+            pair.decks.to_string(),
+            format!("{:.4}", pair.share),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
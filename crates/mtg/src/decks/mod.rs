@@ -1,12 +1,23 @@
 use clap_stdin::MaybeStdin;
 
+mod archetypes;
+mod build_limited;
 mod compare;
+mod diff;
+mod library;
 mod mcp;
+mod meta;
+mod proxies;
 mod ranked;
+mod sideboard;
+mod simulate;
 mod stats;
-mod utils;
+pub(crate) mod utils;
+mod wildcards;
 
+pub(crate) use archetypes::label_decks;
 pub use mcp::analyze_deck_list_mcp;
+pub(crate) use meta::fetch_recent_decks;
 
 // Re-export some types from mtg_core
 
@@ -54,6 +65,14 @@ pub enum Commands {
         /// Output format (pretty table or JSON)
         #[clap(long, default_value = "pretty")]
         format: String,
+
+        /// Suggest the cheapest printing of every card (queries Scryfall for each card)
+        #[clap(long)]
+        budget: bool,
+
+        /// Currency used for the budget report (usd, eur or tix)
+        #[clap(long, default_value = "usd")]
+        currency: String,
     },
     /// Access ranked deck lists from tournaments
     #[clap(name = "ranked")]
@@ -64,6 +83,45 @@ pub enum Commands {
     /// Compare two deck lists
     #[clap(name = "compare")]
     Compare(compare::CompareArgs),
+    /// Show what changed between two versions of a deck, or apply a diff
+    #[clap(name = "diff")]
+    Diff(diff::DiffArgs),
+    /// Group recent tournament decks into archetypes
+    #[clap(name = "archetypes")]
+    Archetypes(archetypes::ArchetypesArgs),
+    /// Aggregate the metagame across recent tournament articles
+    #[clap(name = "meta")]
+    Meta(meta::MetaArgs),
+    /// Save a deck to the local library, adding a revision when it changed
+    #[clap(name = "save")]
+    Save(library::SaveArgs),
+    /// List the decks in the local library
+    #[clap(name = "list")]
+    List(library::ListArgs),
+    /// Show a library deck with its tags, notes and history
+    #[clap(name = "show")]
+    Show(library::ShowArgs),
+    /// Remove a deck from the local library
+    #[clap(name = "rm")]
+    Rm(library::RmArgs),
+    /// Add or remove tags on a library deck
+    #[clap(name = "tag")]
+    Tag(library::TagArgs),
+    /// Validate sideboard plans and show the post-board deck per matchup
+    #[clap(name = "sideboard")]
+    Sideboard(sideboard::SideboardArgs),
+    /// Simulate opening hands and early turns (goldfish and mulligans)
+    #[clap(name = "simulate")]
+    Simulate(simulate::SimulateArgs),
+    /// Print a deck as a PDF sheet of proxies, nine cards per page
+    #[clap(name = "proxies")]
+    Proxies(proxies::ProxiesArgs),
+    /// Count the MTG Arena wildcards needed to build a deck
+    #[clap(name = "wildcards")]
+    Wildcards(wildcards::WildcardsArgs),
+    /// Suggest two-color (plus splash) 40-card builds from a sealed or draft pool
+    #[clap(name = "build-limited")]
+    BuildLimited(build_limited::BuildLimitedArgs),
 }
 
 // Types are now re-exported from mtg_core above
@@ -75,9 +133,24 @@ impl App {
                 input,
                 file,
                 format,
-            } => stats::run(input, file, format, global).await,
+                budget,
+                currency,
+            } => stats::run(input, file, format, budget, currency, global).await,
             Commands::Ranked { command } => ranked::run(command, global).await,
             Commands::Compare(args) => args.run(&global).await,
+            Commands::Diff(args) => args.run(&global).await,
+            Commands::Archetypes(args) => args.run(&global).await,
+            Commands::Meta(args) => args.run(&global).await,
+            Commands::Save(args) => args.run(&global).await,
+            Commands::List(args) => args.run(),
+            Commands::Show(args) => args.run(),
+            Commands::Rm(args) => args.run(),
+            Commands::Tag(args) => args.run(),
+            Commands::Sideboard(args) => args.run(&global).await,
+            Commands::Simulate(args) => args.run(&global).await,
+            Commands::Proxies(args) => args.run(&global).await,
+            Commands::Wildcards(args) => args.run(&global).await,
+            Commands::BuildLimited(args) => args.run(&global).await,
         }
     }
 }
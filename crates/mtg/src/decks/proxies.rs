@@ -0,0 +1,500 @@
+use clap::Args;
+use clap_stdin::MaybeStdin;
+use printpdf::{
+    BuiltinFont, Color, ColorBits, ColorSpace, Image, ImageFilter, ImageTransform, ImageXObject,
+    IndirectFontRef, Line, Mm, PdfDocument, PdfLayerReference, Point, Px, Rgb,
+};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+use super::stats::wrap_text_to_width;
+use super::utils::{convert_cli_deck_list_to_core, load_deck_with_details};
+use crate::prelude::*;
+use mtg_core::decks::mana::basic_land_color;
+use mtg_core::ScryfallCard;
+
+const CARD_WIDTH: f32 = 63.0;
+const CARD_HEIGHT: f32 = 88.0;
+const COLUMNS: usize = 3;
+const ROWS: usize = 3;
+const CUT_MARK_LENGTH: f32 = 4.0;
+/// Millimetres per point
+const PT: f32 = 0.3528;
+
+#[derive(Args, Debug)]
+pub struct ProxiesArgs {
+    /// Deck list input (use '-' for stdin, provide deck list as string, deck ID, or library deck name)
+    #[clap(value_name = "DECK_LIST_OR_ID")]
+    input: Option<MaybeStdin<String>>,
+
+    /// Read deck list from file
+    #[clap(short, long, value_name = "FILE")]
+    file: Option<String>,
+
+    /// PDF file to write
+    #[clap(long, short, value_name = "FILE", default_value = "proxies.pdf")]
+    out: PathBuf,
+
+    /// Paper size (letter or a4)
+    #[clap(long, default_value = "letter")]
+    paper: String,
+
+    /// Bleed around each card in millimetres
+    #[clap(long, default_value = "0")]
+    bleed: f32,
+
+    /// Draw cut marks at the card corners
+    #[clap(long)]
+    cut_marks: bool,
+
+    /// Directory with card images named `<set>-<collector number>.jpg` or `<card name>.jpg`
+    #[clap(long, env = "MTG_IMAGE_DIR", value_name = "DIR")]
+    images: Option<PathBuf>,
+
+    /// Only print text proxies, even when card images are cached
+    #[clap(long)]
+    text_only: bool,
+
+    /// Include the sideboard
+    #[clap(long)]
+    sideboard: bool,
+
+    /// Leave out basic lands
+    #[clap(long)]
+    skip_basics: bool,
+}
+
+impl ProxiesArgs {
+    pub async fn run(self, global: &crate::Global) -> Result<()> {
+        let deck_list = load_deck_with_details(self.input, self.file, global).await?;
+        let deck_list = convert_cli_deck_list_to_core(&deck_list);
+
+        let (page_width, page_height) = match self.paper.to_lowercase().as_str() {
+            "letter" => (215.9, 279.4),
+            "a4" => (210.0, 297.0),
+            other => return Err(eyre!("Unknown paper size '{}' (use letter or a4)", other)),
+        };
+        let layout = SheetLayout::new(page_width, page_height, self.bleed)?;
+
+        let mut proxies = Vec::new();
+        let sections = if self.sideboard {
+            vec![&deck_list.main_deck, &deck_list.sideboard]
+        } else {
+            vec![&deck_list.main_deck]
+        };
+        for card in sections.into_iter().flatten() {
+            if self.skip_basics && basic_land_color(&card.name).is_some() {
+                continue;
+            }
+            for _ in 0..card.quantity {
+                proxies.push(card);
+            }
+        }
+        if proxies.is_empty() {
+            return Err(eyre!("No cards to print"));
+        }
+
+        let image_dir = if self.text_only {
+            None
+        } else {
+            self.images.or_else(default_image_dir)
+        };
+
+        let (document, first_page, first_layer) =
+            PdfDocument::new("Proxies", Mm(page_width), Mm(page_height), "Cards");
+        let fonts = Fonts {
+            regular: document
+                .add_builtin_font(BuiltinFont::Helvetica)
+                .map_err(|e| eyre!("Failed to load font: {}", e))?,
+            bold: document
+                .add_builtin_font(BuiltinFont::HelveticaBold)
+                .map_err(|e| eyre!("Failed to load font: {}", e))?,
+        };
+
+        let mut images = 0;
+        let per_page = COLUMNS * ROWS;
+        for (page_index, page_cards) in proxies.chunks(per_page).enumerate() {
+            let layer = if page_index == 0 {
+                document.get_page(first_page).get_layer(first_layer)
+            } else {
+                let (page, layer) = document.add_page(Mm(page_width), Mm(page_height), "Cards");
+                document.get_page(page).get_layer(layer)
+            };
+
+            for (slot, card) in page_cards.iter().enumerate() {
+                let (x, y) = layout.card_origin(slot);
+                let image = image_dir
+                    .as_deref()
+                    .and_then(|dir| find_card_image(dir, card));
+                match image.and_then(|path| load_jpeg(&path)) {
+                    Some(image) => {
+                        draw_image(&layer, image, x, y, layout.bleed);
+                        images += 1;
+                    }
+                    None => draw_text_proxy(
+                        &layer,
+                        &fonts,
+                        &card.name,
+                        card.card_details.as_ref(),
+                        x,
+                        y,
+                    ),
+                }
+            }
+
+            if self.cut_marks {
+                draw_cut_marks(&layer, &layout, page_cards.len());
+            }
+        }
+
+        let file = std::fs::File::create(&self.out)
+            .map_err(|e| eyre!("Failed to create '{}': {}", self.out.display(), e))?;
+        document
+            .save(&mut BufWriter::new(file))
+            .map_err(|e| eyre!("Failed to write '{}': {}", self.out.display(), e))?;
+
+        println!(
+            "Wrote {} proxies ({} with images) on {} pages to {}",
+            proxies.len(),
+            images,
+            proxies.len().div_ceil(per_page),
+            self.out.display()
+        );
+
+        Ok(())
+    }
+}
+
+struct Fonts {
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+}
+
+/// Position of the 3x3 card grid on a page, in millimetres from the bottom left
+#[derive(Debug, Clone, Copy)]
+struct SheetLayout {
+    margin_x: f32,
+    margin_y: f32,
+    bleed: f32,
+}
+
+impl SheetLayout {
+    fn new(page_width: f32, page_height: f32, bleed: f32) -> Result<Self> {
+        if bleed < 0.0 {
+            return Err(eyre!("Bleed cannot be negative"));
+        }
+        let grid_width = COLUMNS as f32 * (CARD_WIDTH + 2.0 * bleed);
+        let grid_height = ROWS as f32 * (CARD_HEIGHT + 2.0 * bleed);
+        if grid_width > page_width || grid_height > page_height {
+            return Err(eyre!(
+                "A {}x{} grid with {} mm bleed does not fit on the page",
+                COLUMNS,
+                ROWS,
+                bleed
+            ));
+        }
+
+        Ok(Self {
+            margin_x: (page_width - grid_width) / 2.0,
+            margin_y: (page_height - grid_height) / 2.0,
+            bleed,
+        })
+    }
+
+    /// Bottom left corner of the card (without bleed) in a grid slot, filled
+    /// left to right and top to bottom
+    fn card_origin(&self, slot: usize) -> (f32, f32) {
+        let column = (slot % COLUMNS) as f32;
+        let row = (slot / COLUMNS) as f32;
+        let cell_width = CARD_WIDTH + 2.0 * self.bleed;
+        let cell_height = CARD_HEIGHT + 2.0 * self.bleed;
+        let top = self.margin_y + ROWS as f32 * cell_height;
+
+        (
+            self.margin_x + column * cell_width + self.bleed,
+            top - (row + 1.0) * cell_height + self.bleed,
+        )
+    }
+}
+
+/// Default card image directory, next to the other caches
+fn default_image_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("mtg").join("images"))
+}
+
+fn find_card_image(dir: &Path, card: &mtg_core::DeckCard) -> Option<PathBuf> {
+    let mut candidates = Vec::new();
+    let set = card.set_code.clone().or_else(|| {
+        card.card_details
+            .as_ref()
+            .map(|details| details.set.clone())
+    });
+    let number = card.collector_number.clone().or_else(|| {
+        card.card_details
+            .as_ref()
+            .map(|details| details.collector_number.clone())
+    });
+    if let (Some(set), Some(number)) = (set, number) {
+        candidates.push(f!("{}-{}.jpg", set.to_lowercase(), number));
+    }
+    candidates.push(f!("{}.jpg", card.name.replace('/', "_")));
+
+    candidates
+        .into_iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+/// Width, height and color components of a JPEG, read from its frame header
+fn jpeg_dimensions(data: &[u8]) -> Option<(u32, u32, u8)> {
+    if data.get(..2)? != [0xFF, 0xD8] {
+        return None;
+    }
+
+    let mut index = 2;
+    while index + 4 <= data.len() {
+        if data[index] != 0xFF {
+            return None;
+        }
+        let marker = data[index + 1];
+        let length = u16::from_be_bytes([data[index + 2], data[index + 3]]) as usize;
+
+        // Start of frame markers, except DHT (C4), JPG (C8) and DAC (CC)
+        if (0xC0..=0xCF).contains(&marker) && ![0xC4, 0xC8, 0xCC].contains(&marker) {
+            let frame = data.get(index + 4..index + 10)?;
+            let height = u16::from_be_bytes([frame[1], frame[2]]) as u32;
+            let width = u16::from_be_bytes([frame[3], frame[4]]) as u32;
+            return Some((width, height, frame[5]));
+        }
+
+        index += 2 + length;
+    }
+
+    None
+}
+
+fn load_jpeg(path: &Path) -> Option<ImageXObject> {
+    let data = std::fs::read(path).ok()?;
+    let (width, height, components) = jpeg_dimensions(&data)?;
+    let color_space = match components {
+        1 => ColorSpace::Greyscale,
+        4 => ColorSpace::Cmyk,
+        _ => ColorSpace::Rgb,
+    };
+
+    Some(ImageXObject {
+        width: Px(width as usize),
+        height: Px(height as usize),
+        color_space,
+        bits_per_component: ColorBits::Bit8,
+        interpolate: true,
+        image_data: data,
+        image_filter: Some(ImageFilter::DCT),
+        smask: None,
+        clipping_bbox: None,
+    })
+}
+
+/// Draw an image stretched over the card and its bleed
+fn draw_image(layer: &PdfLayerReference, image: ImageXObject, x: f32, y: f32, bleed: f32) {
+    let width = CARD_WIDTH + 2.0 * bleed;
+    let height = CARD_HEIGHT + 2.0 * bleed;
+    // At this dpi the image is exactly as wide as the card
+    let dpi = image.width.0 as f32 * 25.4 / width;
+    let natural_height = image.height.0 as f32 * 25.4 / dpi;
+
+    Image::from(image).add_to_layer(
+        layer.clone(),
+        ImageTransform {
+            translate_x: Some(Mm(x - bleed)),
+            translate_y: Some(Mm(y - bleed)),
+            scale_x: Some(1.0),
+            scale_y: Some(height / natural_height),
+            dpi: Some(dpi),
+            ..Default::default()
+        },
+    );
+}
+
+fn line(points: &[(f32, f32)], is_closed: bool) -> Line {
+    Line {
+        points: points
+            .iter()
+            .map(|(x, y)| (Point::new(Mm(*x), Mm(*y)), false))
+            .collect(),
+        is_closed,
+    }
+}
+
+/// Approximate width of Helvetica text in millimetres
+fn text_width(text: &str, font_size: f32) -> f32 {
+    text.chars().count() as f32 * font_size * 0.5 * PT
+}
+
+fn draw_text_proxy(
+    layer: &PdfLayerReference,
+    fonts: &Fonts,
+    name: &str,
+    card: Option<&ScryfallCard>,
+    x: f32,
+    y: f32,
+) {
+    let padding = 4.0;
+    let top = y + CARD_HEIGHT;
+
+    layer.set_outline_color(Color::Rgb(Rgb::new(0.6, 0.6, 0.6, None)));
+    layer.set_outline_thickness(0.5);
+    layer.add_line(line(
+        &[(x, y), (x + CARD_WIDTH, y), (x + CARD_WIDTH, top), (x, top)],
+        true,
+    ));
+    layer.set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+
+    let mana_cost = card
+        .and_then(|card| card.mana_cost.as_deref())
+        .unwrap_or("");
+    let cost_width = text_width(mana_cost, 8.0);
+    let name_chars = ((CARD_WIDTH - 2.0 * padding - cost_width - 2.0) / (9.0 * 0.55 * PT)) as usize;
+    let name_line = wrap_text_to_width(name, name_chars.max(8));
+    layer.use_text(
+        &name_line[0],
+        9.0,
+        Mm(x + padding),
+        Mm(top - 8.0),
+        &fonts.bold,
+    );
+    if !mana_cost.is_empty() {
+        layer.use_text(
+            mana_cost,
+            8.0,
+            Mm(x + CARD_WIDTH - padding - cost_width),
+            Mm(top - 8.0),
+            &fonts.regular,
+        );
+    }
+    layer.add_line(line(
+        &[
+            (x + padding, top - 10.5),
+            (x + CARD_WIDTH - padding, top - 10.5),
+        ],
+        false,
+    ));
+
+    let Some(card) = card else {
+        return;
+    };
+
+    layer.use_text(
+        &card.type_line,
+        7.5,
+        Mm(x + padding),
+        Mm(top - 15.0),
+        &fonts.bold,
+    );
+
+    // Shrink the rules text until it fits above the power and toughness
+    let oracle_text = card.oracle_text.as_deref().unwrap_or("");
+    let available = CARD_HEIGHT - 20.0 - 12.0;
+    let mut font_size = 7.5;
+    let lines = loop {
+        let chars = ((CARD_WIDTH - 2.0 * padding) / (font_size * 0.5 * PT)) as usize;
+        let lines: Vec<String> = oracle_text
+            .lines()
+            .flat_map(|paragraph| wrap_text_to_width(paragraph, chars))
+            .collect();
+        if lines.len() as f32 * font_size * 1.25 * PT <= available || font_size <= 5.0 {
+            break lines;
+        }
+        font_size -= 0.5;
+    };
+
+    let line_height = font_size * 1.25 * PT;
+    for (index, text) in lines.iter().enumerate() {
+        layer.use_text(
+            text,
+            font_size,
+            Mm(x + padding),
+            Mm(top - 21.0 - index as f32 * line_height),
+            &fonts.regular,
+        );
+    }
+
+    let corner = match (&card.power, &card.toughness, &card.loyalty) {
+        (Some(power), Some(toughness), _) => Some(f!("{}/{}", power, toughness)),
+        (_, _, Some(loyalty)) => Some(f!("[{}]", loyalty)),
+        _ => None,
+    };
+    if let Some(corner) = corner {
+        layer.use_text(
+            &corner,
+            10.0,
+            Mm(x + CARD_WIDTH - padding - text_width(&corner, 10.0)),
+            Mm(y + 5.0),
+            &fonts.bold,
+        );
+    }
+}
+
+/// Short lines outside each card corner, along the card edges
+fn draw_cut_marks(layer: &PdfLayerReference, layout: &SheetLayout, cards: usize) {
+    layer.set_outline_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+    layer.set_outline_thickness(0.25);
+
+    let gap = layout.bleed.max(1.0);
+    for slot in 0..cards {
+        let (x, y) = layout.card_origin(slot);
+        for (corner_x, direction_x) in [(x, -1.0), (x + CARD_WIDTH, 1.0)] {
+            for (corner_y, direction_y) in [(y, -1.0), (y + CARD_HEIGHT, 1.0)] {
+                let start_x = corner_x + direction_x * gap;
+                let start_y = corner_y + direction_y * gap;
+                layer.add_line(line(
+                    &[
+                        (start_x, corner_y),
+                        (start_x + direction_x * CUT_MARK_LENGTH, corner_y),
+                    ],
+                    false,
+                ));
+                layer.add_line(line(
+                    &[
+                        (corner_x, start_y),
+                        (corner_x, start_y + direction_y * CUT_MARK_LENGTH),
+                    ],
+                    false,
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sheet_layout() {
+        let layout = SheetLayout::new(215.9, 279.4, 0.0).unwrap();
+        let (x, y) = layout.card_origin(0);
+        assert!((x - 13.45).abs() < 0.01);
+        assert!((y - (279.4 - 7.7 - CARD_HEIGHT)).abs() < 0.01);
+
+        let (x, y) = layout.card_origin(8);
+        assert!((x - (13.45 + 2.0 * CARD_WIDTH)).abs() < 0.01);
+        assert!((y - 7.7).abs() < 0.01);
+
+        let bleed = SheetLayout::new(210.0, 297.0, 3.0).unwrap();
+        let (x, _) = bleed.card_origin(1);
+        assert!((x - (1.5 + 69.0 + 3.0)).abs() < 0.01);
+        assert!(SheetLayout::new(215.9, 279.4, 5.0).is_err());
+    }
+
+    #[test]
+    fn test_jpeg_dimensions() {
+        let jpeg = [
+            0xFF, 0xD8, // start of image
+            0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, // APP0 segment
+            0xFF, 0xC0, 0x00, 0x11, 0x08, 0x03, 0xAC, 0x02, 0xA2, 0x03, // frame 674x940, RGB
+        ];
+        assert_eq!(jpeg_dimensions(&jpeg), Some((674, 940, 3)));
+        assert_eq!(jpeg_dimensions(b"not a jpeg"), None);
+    }
+}
@@ -37,6 +37,10 @@ pub enum Commands {
         #[clap(value_name = "ID_OR_URL")]
         identifier: String,
 
+        /// Archetype definitions file used to tag decks (defaults to ~/.config/mtg/archetypes.json)
+        #[clap(long, env = "MTG_ARCHETYPES_FILE", value_name = "FILE")]
+        definitions: Option<String>,
+
         /// Output format (pretty table or JSON)
         #[clap(long, default_value = "pretty")]
         output: String,
@@ -63,6 +67,10 @@ pub async fn run(command: Commands, global: crate::Global) -> Result<()> {
             };
             list::run(format, limit, actual_skip, output, global).await
         }
-        Commands::Show { identifier, output } => show::run(identifier, output, global).await,
+        Commands::Show {
+            identifier,
+            definitions,
+            output,
+        } => show::run(identifier, definitions, output, global).await,
     }
 }
@@ -1,5 +1,6 @@
 use crate::prelude::*;
 use mtg_core::cache::{CacheStore, CachedHttpClient, DiskCache, DiskCacheBuilder};
+use mtg_core::decks::ArchetypeReport;
 use mtg_core::{ParsedDeck, ParsedDecksResponse, RankedDecksClient};
 
 /// Try to fetch a single deck by its ID from cache
@@ -105,7 +106,12 @@ async fn try_fetch_single_deck(deck_id: &str, cache: &DiskCache) -> Result<Parse
     }
 }
 
-pub async fn run(identifier: String, output: String, global: crate::Global) -> Result<()> {
+pub async fn run(
+    identifier: String,
+    definitions: Option<String>,
+    output: String,
+    global: crate::Global,
+) -> Result<()> {
     // Create cache and HTTP client - use same prefixes as list command
     let cache = DiskCacheBuilder::new().prefix("ranked_list").build()?;
 
@@ -146,22 +152,39 @@ pub async fn run(identifier: String, output: String, global: crate::Global) -> R
         return Err(eyre!("No deck lists found on the page"));
     }
 
+    let archetypes = crate::decks::archetypes::label_decks(&response.decks, definitions)?;
+
     // Output results
     match output.as_str() {
-        "json" => output_parsed_decks_json(&response)?,
-        "pretty" => output_parsed_decks_pretty(&response)?,
-        _ => output_parsed_decks_pretty(&response)?,
+        "json" => output_parsed_decks_json(&response, &archetypes)?,
+        "pretty" => output_parsed_decks_pretty(&response, &archetypes)?,
+        _ => output_parsed_decks_pretty(&response, &archetypes)?,
     }
 
     Ok(())
 }
 
-fn output_parsed_decks_json(response: &ParsedDecksResponse) -> Result<()> {
-    aprintln!("{}", serde_json::to_string_pretty(&response)?);
+fn output_parsed_decks_json(
+    response: &ParsedDecksResponse,
+    archetypes: &ArchetypeReport,
+) -> Result<()> {
+    let mut output = serde_json::to_value(response)?;
+    if let Some(decks) = output.get_mut("decks").and_then(|v| v.as_array_mut()) {
+        for (deck_json, deck) in decks.iter_mut().zip(&response.decks) {
+            if let Some(archetype) = archetypes.deck(&deck.id) {
+                deck_json["archetype"] = serde_json::json!(archetype.archetype);
+                deck_json["archetype_confidence"] = serde_json::json!(archetype.confidence);
+            }
+        }
+    }
+    aprintln!("{}", serde_json::to_string_pretty(&output)?);
     Ok(())
 }
 
-fn output_parsed_decks_pretty(response: &ParsedDecksResponse) -> Result<()> {
+fn output_parsed_decks_pretty(
+    response: &ParsedDecksResponse,
+    archetypes: &ArchetypeReport,
+) -> Result<()> {
     aprintln!("=== DECK LISTS FROM {} ===\n", response.url);
     aprintln!("Found {} deck(s)\n", response.decks.len());
 
@@ -184,6 +207,13 @@ fn output_parsed_decks_pretty(response: &ParsedDecksResponse) -> Result<()> {
         if let Some(format) = &deck.format {
             aprintln!("Format: {}", format);
         }
+        if let Some(archetype) = archetypes.deck(&deck.id) {
+            aprintln!(
+                "Archetype: {} ({:.0}% confidence)",
+                archetype.archetype,
+                archetype.confidence * 100.0
+            );
+        }
 
         aprintln!();
 
@@ -0,0 +1,187 @@
+use clap::Args;
+use clap_stdin::MaybeStdin;
+use color_eyre::owo_colors::OwoColorize;
+
+use super::utils::{convert_cli_deck_list_to_core, read_deck_input, resolve_deck_list};
+use crate::prelude::*;
+use mtg_core::decks::sideboard::format_sideboard_plans;
+use mtg_core::decks::utils::format_deck_list;
+use mtg_core::decks::{
+    parse_sideboard_plans, validate_plan, DeckLibrary, PlanValidation, SideboardPlan,
+};
+
+#[derive(Args, Debug)]
+pub struct SideboardArgs {
+    /// Deck to board with: a library deck name, deck list, deck ID, or '-' for stdin
+    #[clap(value_name = "DECK_LIST_OR_ID")]
+    input: Option<MaybeStdin<String>>,
+
+    /// Read deck list from file
+    #[clap(short, long, value_name = "FILE")]
+    file: Option<String>,
+
+    /// Sideboard plan file (defaults to the plans saved with a library deck)
+    #[clap(short, long, value_name = "FILE")]
+    plan: Option<String>,
+
+    /// Save the plan file with the library deck
+    #[clap(long, requires = "plan")]
+    save: bool,
+
+    /// Only show the plan against this archetype
+    #[clap(short, long)]
+    matchup: Option<String>,
+
+    /// Output format (pretty, JSON or the plain post-board deck lists)
+    #[clap(long, default_value = "pretty")]
+    output: String,
+}
+
+impl SideboardArgs {
+    pub async fn run(self, global: &crate::Global) -> Result<()> {
+        let deck_content = read_deck_input(self.input, self.file)?;
+
+        let library = DeckLibrary::open_default();
+        let name = deck_content.trim();
+        let library_deck = if !name.contains('\n') && library.contains(name) {
+            Some(library.get(name)?)
+        } else {
+            None
+        };
+
+        let deck_list = match &library_deck {
+            Some(deck) => deck.latest().deck_list.clone(),
+            None => {
+                let (deck_list, _) = resolve_deck_list(&deck_content, global).await?;
+                convert_cli_deck_list_to_core(&deck_list)
+            }
+        };
+
+        let plans = match (&self.plan, &library_deck) {
+            (Some(path), _) => {
+                let content = std::fs::read_to_string(path)
+                    .map_err(|e| eyre!("Failed to read plan file '{}': {}", path, e))?;
+                parse_sideboard_plans(&content)?
+            }
+            (None, Some(deck)) => deck.sideboard_plans.clone(),
+            (None, None) => {
+                return Err(eyre!(
+                    "No sideboard plan given. Use --plan FILE or a library deck with saved plans"
+                ))
+            }
+        };
+
+        if plans.is_empty() {
+            return Err(eyre!("No sideboard plans found"));
+        }
+
+        let all_validations: Vec<PlanValidation> = plans
+            .iter()
+            .map(|plan| validate_plan(&deck_list, plan))
+            .collect();
+
+        let validations: Vec<&PlanValidation> = all_validations
+            .iter()
+            .filter(|validation| match &self.matchup {
+                Some(matchup) => validation.plan.archetype.eq_ignore_ascii_case(matchup),
+                None => true,
+            })
+            .collect();
+
+        if validations.is_empty() {
+            return Err(eyre!(
+                "No plan against '{}'. Plans: {}",
+                self.matchup.unwrap_or_default(),
+                plans
+                    .iter()
+                    .map(|plan| plan.archetype.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        if self.save {
+            let deck = library_deck.as_ref().ok_or_else(|| {
+                eyre!("--save needs a library deck name, save the deck with 'mtg decks save' first")
+            })?;
+            let (valid, invalid): (Vec<&PlanValidation>, Vec<&PlanValidation>) = all_validations
+                .iter()
+                .partition(|validation| validation.is_valid());
+            for validation in &invalid {
+                aeprintln!(
+                    "Not saving the plan against '{}', it is not valid for this deck",
+                    validation.plan.archetype
+                );
+            }
+            if !valid.is_empty() {
+                let saved: Vec<SideboardPlan> = valid
+                    .iter()
+                    .map(|validation| validation.plan.clone())
+                    .collect();
+                library.set_sideboard_plans(&deck.slug, saved)?;
+                aeprintln!(
+                    "Saved {} sideboard plan(s) with '{}'",
+                    valid.len(),
+                    deck.name
+                );
+            }
+        }
+
+        match self.output.as_str() {
+            "json" => println!("{}", serde_json::to_string_pretty(&validations)?),
+            "text" => {
+                for validation in &validations {
+                    println!("// vs {}", validation.plan.archetype);
+                    println!("{}", format_deck_list(&validation.deck_list));
+                }
+            }
+            _ => {
+                for validation in &validations {
+                    print_validation(validation);
+                }
+            }
+        }
+
+        if validations.iter().any(|validation| !validation.is_valid()) {
+            return Err(eyre!("Some sideboard plans are not valid for this deck"));
+        }
+
+        Ok(())
+    }
+}
+
+fn print_validation(validation: &PlanValidation) {
+    let plan: &SideboardPlan = &validation.plan;
+    println!("\n{}", format!("vs {}", plan.archetype).bold().underline());
+
+    if let Some(notes) = &plan.notes {
+        println!("{}", notes.italic());
+    }
+
+    // The plan itself, in the format it is written in
+    let formatted = format_sideboard_plans(std::slice::from_ref(plan));
+    for line in formatted.lines().skip(1) {
+        if line.starts_with('+') {
+            println!("  {}", line.green());
+        } else if line.starts_with('-') {
+            println!("  {}", line.red());
+        }
+    }
+
+    if validation.is_valid() {
+        println!("{}", "Plan is valid".green());
+    } else {
+        for issue in &validation.issues {
+            println!("{} {}", "Problem:".red().bold(), issue);
+        }
+    }
+
+    let main: u32 = validation
+        .deck_list
+        .main_deck
+        .iter()
+        .map(|card| card.quantity)
+        .sum();
+    println!("\n{} ({} cards)", "Post-board deck".bold(), main);
+    print!("{}", format_deck_list(&validation.deck_list));
+}
@@ -0,0 +1,193 @@
+use clap::Args;
+use clap_stdin::MaybeStdin;
+use prettytable::{Cell, Row};
+
+use super::utils::{convert_cli_deck_list_to_core, load_deck_with_details};
+use crate::prelude::*;
+use mtg_core::decks::{simulate_deck, KeepHeuristic, SimulationConfig, SimulationReport};
+
+#[derive(Args, Debug)]
+pub struct SimulateArgs {
+    /// Deck list input (use '-' for stdin, provide deck list as string, deck ID, or omit to read from stdin)
+    #[clap(value_name = "DECK_LIST_OR_ID")]
+    input: Option<MaybeStdin<String>>,
+
+    /// Read deck list from file
+    #[clap(short, long, value_name = "FILE")]
+    file: Option<String>,
+
+    /// Number of games to simulate
+    #[clap(short, long, default_value = "10000")]
+    iterations: u32,
+
+    /// Seed for the random number generator (same seed, same results)
+    #[clap(long, default_value = "0")]
+    seed: u64,
+
+    /// Simulate being on the draw instead of on the play
+    #[clap(long)]
+    draw: bool,
+
+    /// Number of turns to simulate
+    #[clap(short, long, default_value = "6")]
+    turns: u32,
+
+    /// Minimum lands in a keepable hand
+    #[clap(long, default_value = "2")]
+    min_lands: u32,
+
+    /// Maximum lands in a keepable hand
+    #[clap(long, default_value = "5")]
+    max_lands: u32,
+
+    /// Always keep hands of this size or smaller
+    #[clap(long, default_value = "5")]
+    min_hand_size: u32,
+
+    /// Output format (pretty table or JSON)
+    #[clap(long, default_value = "pretty")]
+    format: String,
+}
+
+impl SimulateArgs {
+    pub async fn run(self, global: &crate::Global) -> Result<()> {
+        let deck_list = load_deck_with_details(self.input, self.file, global).await?;
+        let core_deck_list = convert_cli_deck_list_to_core(&deck_list);
+
+        let config = SimulationConfig {
+            iterations: self.iterations,
+            seed: self.seed,
+            on_the_play: !self.draw,
+            turns: self.turns,
+            keep: KeepHeuristic {
+                min_lands: self.min_lands,
+                max_lands: self.max_lands,
+                min_hand_size: self.min_hand_size,
+            },
+        };
+
+        let report = simulate_deck(&core_deck_list, &config)?;
+
+        match self.format.as_str() {
+            "json" => println!("{}", serde_json::to_string_pretty(&report)?),
+            _ => output_pretty(&report),
+        }
+
+        Ok(())
+    }
+}
+
+fn percent(rate: f64) -> String {
+    format!("{:.1}%", rate * 100.0)
+}
+
+fn output_pretty(report: &SimulationReport) {
+    println!("=== GOLDFISH SIMULATION ===\n");
+
+    let mut summary_table = new_table();
+    summary_table.add_row(Row::new(vec![Cell::new("Metric"), Cell::new("Value")]));
+    summary_table.add_row(Row::new(vec![
+        Cell::new("Games Simulated"),
+        Cell::new(&report.iterations.to_string()),
+    ]));
+    summary_table.add_row(Row::new(vec![
+        Cell::new("Seed"),
+        Cell::new(&report.seed.to_string()),
+    ]));
+    summary_table.add_row(Row::new(vec![
+        Cell::new("Play/Draw"),
+        Cell::new(if report.on_the_play { "Play" } else { "Draw" }),
+    ]));
+    summary_table.add_row(Row::new(vec![
+        Cell::new("Deck Size"),
+        Cell::new(&report.deck_size.to_string()),
+    ]));
+    summary_table.add_row(Row::new(vec![
+        Cell::new("Lands"),
+        Cell::new(&report.land_count.to_string()),
+    ]));
+    summary_table.add_row(Row::new(vec![
+        Cell::new("Average Opening Lands"),
+        Cell::new(&format!("{:.2}", report.average_opening_lands)),
+    ]));
+    summary_table.add_row(Row::new(vec![
+        Cell::new("Average Mulligans"),
+        Cell::new(&format!("{:.2}", report.average_mulligans)),
+    ]));
+
+    println!("Summary:");
+    summary_table.printstd();
+    println!();
+
+    println!("Lands in Opening Seven:");
+    let mut lands_table = new_table();
+    lands_table.add_row(Row::new(vec![Cell::new("Lands"), Cell::new("Hands")]));
+    for (lands, rate) in &report.opening_hand_lands {
+        lands_table.add_row(Row::new(vec![
+            Cell::new(&lands.to_string()),
+            Cell::new(&percent(*rate)),
+        ]));
+    }
+    lands_table.printstd();
+    println!();
+
+    println!("Mulligans:");
+    let mut keep_table = new_table();
+    keep_table.add_row(Row::new(vec![
+        Cell::new("Hand Size"),
+        Cell::new("Keep Rate"),
+        Cell::new("Games Kept"),
+    ]));
+    for (size, rate) in report.keep_rates.iter().rev() {
+        let final_rate = report.final_hand_sizes.get(size).copied().unwrap_or(0.0);
+        keep_table.add_row(Row::new(vec![
+            Cell::new(&size.to_string()),
+            Cell::new(&percent(*rate)),
+            Cell::new(&percent(final_rate)),
+        ]));
+    }
+    keep_table.printstd();
+    println!();
+
+    println!("Land Drops:");
+    let mut drops_table = new_table();
+    drops_table.add_row(Row::new(vec![Cell::new("Turn"), Cell::new("Hit Rate")]));
+    for turn in &report.land_drops {
+        drops_table.add_row(Row::new(vec![
+            Cell::new(&turn.turn.to_string()),
+            Cell::new(&percent(turn.rate)),
+        ]));
+    }
+    drops_table.printstd();
+    println!();
+
+    if !report.castable_on_curve.is_empty() {
+        println!("Castable on Curve:");
+        let mut curve_table = new_table();
+        curve_table.add_row(Row::new(vec![
+            Cell::new("Card"),
+            Cell::new("Cost"),
+            Cell::new("Copies"),
+            Cell::new("Mana Available"),
+            Cell::new("Cast on Curve"),
+        ]));
+        for card in &report.castable_on_curve {
+            curve_table.add_row(Row::new(vec![
+                Cell::new(&card.name),
+                Cell::new(&card.mana_cost),
+                Cell::new(&card.copies.to_string()),
+                Cell::new(&percent(card.castable_rate)),
+                Cell::new(&percent(card.on_curve_rate)),
+            ]));
+        }
+        curve_table.printstd();
+        println!();
+    }
+
+    if !report.cards_without_details.is_empty() {
+        aeprintln!(
+            "Warning: no card details for {}, simulated as nonland cards that are never cast",
+            report.cards_without_details.join(", ")
+        );
+    }
+}
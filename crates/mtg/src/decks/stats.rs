@@ -2,201 +2,43 @@ use clap_stdin::MaybeStdin;
 use prettytable::{Cell, Row};
 
 use super::utils::{
-    calculate_deck_stats_cli, convert_core_deck_list_to_cli, convert_parsed_deck_to_cli_deck_list,
-    fetch_card_details_with_global,
+    calculate_deck_stats_cli, convert_cli_deck_list_to_core, load_deck_with_details,
 };
-use super::DeckList;
 use crate::prelude::*;
-use mtg_core::decks::stats::{
-    cache_deck_with_details, convert_arena_deck_to_named, is_arena_deck_id, is_deck_id,
-    load_arena_deck_from_cache, load_deck_from_cache,
-};
-use mtg_core::parse_deck_list;
+use mtg_core::decks::{find_budget_alternatives, BudgetReport, Currency, DeckPricing};
 
 pub async fn run(
     input: Option<MaybeStdin<String>>,
     file: Option<String>,
     format: String,
+    budget: bool,
+    currency: String,
     global: crate::Global,
 ) -> Result<()> {
-    // Get deck list content
-    let deck_content = if let Some(file_path) = file {
-        std::fs::read_to_string(&file_path)
-            .map_err(|e| eyre!("Failed to read file '{}': {}", file_path, e))?
-    } else if let Some(input_maybe_stdin) = input {
-        input_maybe_stdin.to_string()
-    } else {
-        // If no input provided, read from stdin
-        use std::io::Read;
-        let mut buffer = String::new();
-        std::io::stdin()
-            .read_to_string(&mut buffer)
-            .map_err(|e| eyre!("Failed to read from stdin: {}", e))?;
-        buffer
-    };
-
-    // Check if content is empty
-    if deck_content.trim().is_empty() {
-        return Err(eyre!(
-            "Deck list is empty. Please provide a valid deck list."
-        ));
-    }
-
-    // Check if the input is an Arena deck ID (UUID format)
-    let (deck_list, is_arena_deck) = if is_arena_deck_id(&deck_content) {
-        aeprintln!("Detected MTG Arena deck ID: {}", deck_content.trim());
-
-        // Try to fetch Arena deck from cache
-        match load_arena_deck_from_cache(deck_content.trim()).await {
-            Ok((arena_deck, deck_name)) => {
-                aeprintln!("Found Arena deck: {}", deck_name);
+    let currency: Currency = currency.parse()?;
+    let deck_with_details = load_deck_with_details(input, file, &global).await?;
 
-                // Convert Arena card IDs to actual card names
-                let scryfall_client = global.create_scryfall_client()?;
-                let converted_deck =
-                    convert_arena_deck_to_named(arena_deck, &deck_name, &scryfall_client).await?;
-                (convert_core_deck_list_to_cli(&converted_deck), true)
-            }
-            Err(e) => {
-                return Err(e);
-            }
-        }
-    } else if is_deck_id(&deck_content) {
-        // Try to fetch deck from cache (regular deck ID)
-        match load_deck_from_cache(&deck_content).await {
-            Ok(deck) => (convert_core_deck_list_to_cli(&deck), false),
-            Err(_) => {
-                // If not found as deck, try as article ID
-                let decks = crate::decks::ranked::fetch_decks_from_article(&deck_content, &global)
-                    .await
-                    .map_err(|_| {
-                        eyre!(
-                            "ID '{}' not found as deck or article ID",
-                            deck_content.trim()
-                        )
-                    })?;
-
-                if decks.is_empty() {
-                    return Err(eyre!("No decks found in article"));
-                }
-
-                // If multiple decks, inform user and use the first one
-                if decks.len() > 1 {
-                    eprintln!(
-                        "Note: Article contains {} decks. Analyzing the first deck (ID: {})",
-                        decks.len(),
-                        decks[0].id
-                    );
-                    eprintln!("To analyze other decks, use their specific IDs:");
-                    for (i, deck) in decks.iter().enumerate().skip(1) {
-                        let title = deck.title.as_deref().unwrap_or("Untitled");
-                        eprintln!("  {} - {} ({})", i + 1, deck.id, title);
-                    }
-                    eprintln!();
-                }
-
-                // Convert ParsedDeck to DeckList
-                (convert_parsed_deck_to_cli_deck_list(&decks[0]), false)
-            }
-        }
-    } else {
-        // Parse deck list normally
-        let core_deck_list = parse_deck_list(&deck_content)?;
-        (convert_core_deck_list_to_cli(&core_deck_list), false)
-    };
+    // Calculate statistics
+    let stats = calculate_deck_stats_cli(&deck_with_details)?;
 
-    // For Arena decks, we already have card details from the conversion
-    let deck_with_details = if is_arena_deck {
-        deck_list
+    let budget_report = if budget {
+        let scryfall_client = global.create_scryfall_client()?;
+        let core_deck_list = convert_cli_deck_list_to_core(&deck_with_details);
+        Some(find_budget_alternatives(&core_deck_list, currency, &scryfall_client).await?)
     } else {
-        // Check if we already have card details cached
-        let deck_has_details = deck_list
-            .main_deck
-            .iter()
-            .any(|card| card.card_details.is_some())
-            || deck_list
-                .sideboard
-                .iter()
-                .any(|card| card.card_details.is_some());
-
-        if deck_has_details {
-            // We already have card details, use them
-            deck_list
-        } else {
-            // Fetch card details for non-Arena decks
-            let deck_with_fetched_details =
-                fetch_card_details_with_global(deck_list, &global).await?;
-
-            // Cache the deck with card details for future use
-            if let Ok(deck_id) = extract_deck_id_from_input(&deck_content) {
-                let core_deck = convert_cli_deck_list_to_core(&deck_with_fetched_details);
-                if let Err(e) = cache_deck_with_details(&deck_id, &core_deck).await {
-                    // Log error but don't fail the command
-                    eprintln!("Warning: Failed to cache deck with details: {}", e);
-                }
-            }
-
-            deck_with_fetched_details
-        }
+        None
     };
 
-    // Calculate statistics
-    let stats = calculate_deck_stats_cli(&deck_with_details)?;
-
     // Output results
     match format.as_str() {
-        "json" => output_json(&deck_with_details, &stats)?,
-        "pretty" => output_pretty(&deck_with_details, &stats)?,
-        _ => output_pretty(&deck_with_details, &stats)?,
+        "json" => output_json(&deck_with_details, &stats, budget_report.as_ref())?,
+        "pretty" => output_pretty(&deck_with_details, &stats, budget_report.as_ref())?,
+        _ => output_pretty(&deck_with_details, &stats, budget_report.as_ref())?,
     }
 
     Ok(())
 }
 
-/// Extract deck ID from input string if it's a deck ID
-fn extract_deck_id_from_input(input: &str) -> Result<String> {
-    let trimmed = input.trim();
-    if is_deck_id(trimmed) {
-        Ok(trimmed.to_string())
-    } else {
-        Err(eyre!("Input is not a deck ID"))
-    }
-}
-
-/// Convert CLI DeckList to mtg_core DeckList
-fn convert_cli_deck_list_to_core(cli_deck_list: &DeckList) -> mtg_core::DeckList {
-    mtg_core::DeckList {
-        main_deck: cli_deck_list
-            .main_deck
-            .iter()
-            .map(|card| mtg_core::DeckCard {
-                quantity: card.quantity,
-                name: card.name.clone(),
-                set_code: card.set_code.clone(),
-                collector_number: card.collector_number.clone(),
-                card_details: card
-                    .card_details
-                    .as_ref()
-                    .map(crate::scryfall::convert_cli_card_to_core),
-            })
-            .collect(),
-        sideboard: cli_deck_list
-            .sideboard
-            .iter()
-            .map(|card| mtg_core::DeckCard {
-                quantity: card.quantity,
-                name: card.name.clone(),
-                set_code: card.set_code.clone(),
-                collector_number: card.collector_number.clone(),
-                card_details: card
-                    .card_details
-                    .as_ref()
-                    .map(crate::scryfall::convert_cli_card_to_core),
-            })
-            .collect(),
-    }
-}
-
 /// Safe padding calculation to prevent underflow
 fn safe_padding(total_width: usize, used_width: usize) -> String {
     if used_width >= total_width {
@@ -347,7 +189,7 @@ fn format_card_display(card: &super::DeckCard, quantity: u32) -> String {
 }
 
 /// Wrap text to fit within a specified width
-fn wrap_text_to_width(text: &str, width: usize) -> Vec<String> {
+pub(super) fn wrap_text_to_width(text: &str, width: usize) -> Vec<String> {
     let mut lines = Vec::new();
     let mut current_line = String::new();
 
@@ -374,7 +216,11 @@ fn wrap_text_to_width(text: &str, width: usize) -> Vec<String> {
     lines
 }
 
-fn output_pretty(deck_list: &super::DeckList, stats: &mtg_core::DeckStats) -> Result<()> {
+fn output_pretty(
+    deck_list: &super::DeckList,
+    stats: &mtg_core::DeckStats,
+    budget: Option<&BudgetReport>,
+) -> Result<()> {
     println!("=== DECK ANALYSIS ===\n");
 
     // Basic stats
@@ -483,6 +329,25 @@ fn output_pretty(deck_list: &super::DeckList, stats: &mtg_core::DeckStats) -> Re
         println!();
     }
 
+    // Roles
+    if !stats.roles.roles.is_empty() {
+        output_roles(&stats.roles);
+    }
+
+    // Mana base
+    if !stats.mana_base.sources.is_empty() || !stats.mana_base.requirements.is_empty() {
+        output_mana_base(&stats.mana_base);
+    }
+
+    // Pricing
+    if !stats.pricing.totals.is_empty() {
+        output_pricing(&stats.pricing);
+    }
+
+    if let Some(budget) = budget {
+        output_budget(budget);
+    }
+
     // Format legality
     if !stats.format_legality.is_empty() {
         println!("Format Legality:");
@@ -542,8 +407,213 @@ fn output_pretty(deck_list: &super::DeckList, stats: &mtg_core::DeckStats) -> Re
     Ok(())
 }
 
-fn output_json(deck_list: &super::DeckList, stats: &mtg_core::DeckStats) -> Result<()> {
-    let output = serde_json::json!({
+fn output_roles(roles: &mtg_core::decks::RoleBreakdown) {
+    println!("Roles:");
+    let mut table = new_table();
+    table.add_row(Row::new(vec![
+        Cell::new("Role"),
+        Cell::new("Cards"),
+        Cell::new("Curve"),
+        Cell::new("Examples"),
+    ]));
+
+    for role in &roles.roles {
+        let curve: Vec<String> = role
+            .curve
+            .iter()
+            .map(|(mv, count)| f!("{mv}:{count}"))
+            .collect();
+        let mut examples = role.cards.iter().take(3).cloned().collect::<Vec<_>>();
+        if role.cards.len() > 3 {
+            examples.push(f!("+{} more", role.cards.len() - 3));
+        }
+        table.add_row(Row::new(vec![
+            Cell::new(role.role.label()),
+            Cell::new(&role.count.to_string()),
+            Cell::new(&curve.join(" ")),
+            Cell::new(&examples.join(", ")),
+        ]));
+    }
+
+    table.printstd();
+    println!();
+}
+
+fn output_mana_base(mana_base: &mtg_core::decks::ManaBaseReport) {
+    println!("Mana Sources:");
+    let mut sources_table = new_table();
+    sources_table.add_row(Row::new(vec![
+        Cell::new("Color"),
+        Cell::new("Lands"),
+        Cell::new("Multicolor"),
+        Cell::new("Fetches"),
+        Cell::new("Rocks/Dorks"),
+        Cell::new("Total"),
+    ]));
+    for sources in &mana_base.sources {
+        sources_table.add_row(Row::new(vec![
+            Cell::new(&sources.color),
+            Cell::new(&sources.lands.to_string()),
+            Cell::new(&sources.multicolor_lands.to_string()),
+            Cell::new(&sources.fetches.to_string()),
+            Cell::new(&sources.rocks.to_string()),
+            Cell::new(&sources.total.to_string()),
+        ]));
+    }
+    sources_table.printstd();
+    println!();
+
+    if !mana_base.requirements.is_empty() {
+        println!("Colored Requirements:");
+        let mut requirements_table = new_table();
+        requirements_table.add_row(Row::new(vec![
+            Cell::new("Color"),
+            Cell::new("Turn"),
+            Cell::new("Pips"),
+            Cell::new("Card"),
+            Cell::new("Sources"),
+            Cell::new("Recommended"),
+            Cell::new("OK"),
+        ]));
+        for requirement in &mana_base.requirements {
+            requirements_table.add_row(Row::new(vec![
+                Cell::new(&requirement.color),
+                Cell::new(&requirement.turn.to_string()),
+                Cell::new(&requirement.pips.to_string()),
+                Cell::new(&requirement.card),
+                Cell::new(&requirement.available_sources.to_string()),
+                Cell::new(&requirement.recommended_sources.to_string()),
+                Cell::new(if requirement.supported { "✓" } else { "✗" }),
+            ]));
+        }
+        requirements_table.printstd();
+        println!();
+    }
+
+    if !mana_base.under_supported_colors.is_empty() {
+        println!(
+            "Under-supported colors: {}\n",
+            mana_base.under_supported_colors.join(", ")
+        );
+    }
+}
+
+fn output_pricing(pricing: &DeckPricing) {
+    println!("Deck Price:");
+    let mut totals_table = new_table();
+    totals_table.add_row(Row::new(vec![
+        Cell::new("Currency"),
+        Cell::new("Main Deck"),
+        Cell::new("Sideboard"),
+        Cell::new("Total"),
+    ]));
+    for total in &pricing.totals {
+        totals_table.add_row(Row::new(vec![
+            Cell::new(&total.currency.to_string()),
+            Cell::new(&total.currency.format(total.main_deck)),
+            Cell::new(&total.currency.format(total.sideboard)),
+            Cell::new(&total.currency.format(total.total)),
+        ]));
+    }
+    totals_table.printstd();
+    println!();
+
+    if let Some(currency) = pricing.totals.first().map(|total| total.currency) {
+        if !pricing.most_expensive.is_empty() {
+            println!("Most Expensive Cards ({currency}):");
+            let mut expensive_table = new_table();
+            expensive_table.add_row(Row::new(vec![
+                Cell::new("Card"),
+                Cell::new("Set"),
+                Cell::new("Qty"),
+                Cell::new("Each"),
+                Cell::new("Total"),
+            ]));
+            for card in &pricing.most_expensive {
+                expensive_table.add_row(Row::new(vec![
+                    Cell::new(&card.name),
+                    Cell::new(&card.set_code.clone().unwrap_or_default().to_uppercase()),
+                    Cell::new(&card.quantity.to_string()),
+                    Cell::new(&currency.format(card.unit_price)),
+                    Cell::new(&currency.format(card.total_price)),
+                ]));
+            }
+            expensive_table.printstd();
+            println!();
+        }
+    }
+
+    if !pricing.cards_without_prices.is_empty() {
+        println!(
+            "Cards without prices: {}\n",
+            pricing.cards_without_prices.join(", ")
+        );
+    }
+}
+
+fn output_budget(budget: &BudgetReport) {
+    let currency = budget.currency;
+    println!("Budget Report ({currency}):");
+    println!(
+        "Current: {}  Cheapest printings: {}  Savings: {}\n",
+        currency.format(budget.current_total),
+        currency.format(budget.budget_total),
+        currency.format(budget.savings)
+    );
+    if !budget.unpriced.is_empty() {
+        println!(
+            "Not in the totals (no {currency} price): {}\n",
+            budget.unpriced.join(", ")
+        );
+    }
+
+    if budget.suggestions.is_empty() {
+        println!("Every card already uses its cheapest printing.\n");
+        return;
+    }
+
+    let mut budget_table = new_table();
+    budget_table.add_row(Row::new(vec![
+        Cell::new("Card"),
+        Cell::new("Qty"),
+        Cell::new("Current"),
+        Cell::new("Cheapest Printing"),
+        Cell::new("Price"),
+        Cell::new("Savings"),
+    ]));
+    for suggestion in &budget.suggestions {
+        let current = match (&suggestion.current_set, suggestion.current_price) {
+            (Some(set), Some(price)) => {
+                format!("{} {}", set.to_uppercase(), currency.format(price))
+            }
+            (None, Some(price)) => currency.format(price),
+            (Some(set), None) => set.to_uppercase(),
+            (None, None) => "-".to_string(),
+        };
+        budget_table.add_row(Row::new(vec![
+            Cell::new(&suggestion.name),
+            Cell::new(&suggestion.quantity.to_string()),
+            Cell::new(&current),
+            Cell::new(&format!(
+                "{} ({}) {}",
+                suggestion.cheapest_set_name,
+                suggestion.cheapest_set.to_uppercase(),
+                suggestion.cheapest_collector_number
+            )),
+            Cell::new(&currency.format(suggestion.cheapest_price)),
+            Cell::new(&currency.format(suggestion.savings)),
+        ]));
+    }
+    budget_table.printstd();
+    println!();
+}
+
+fn output_json(
+    deck_list: &super::DeckList,
+    stats: &mtg_core::DeckStats,
+    budget: Option<&BudgetReport>,
+) -> Result<()> {
+    let mut output = serde_json::json!({
         "deck_list": deck_list,
         "statistics": {
             "total_cards": stats.total_cards,
@@ -556,9 +626,16 @@ fn output_json(deck_list: &super::DeckList, stats: &mtg_core::DeckStats) -> Resu
             "type_distribution": stats.type_distribution,
             "rarity_distribution": stats.rarity_distribution,
             "format_legality": stats.format_legality,
+            "mana_base": stats.mana_base,
+            "pricing": stats.pricing,
+            "roles": stats.roles,
         }
     });
 
+    if let Some(budget) = budget {
+        output["budget"] = serde_json::to_value(budget)?;
+    }
+
     println!("{}", serde_json::to_string_pretty(&output)?);
     Ok(())
 }
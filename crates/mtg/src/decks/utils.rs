@@ -1,4 +1,12 @@
+use clap_stdin::MaybeStdin;
+
 use crate::prelude::*;
+use mtg_core::decks::roles::{analyze_roles, default_roles_path, RoleOverrides};
+use mtg_core::decks::stats::{
+    cache_deck_with_details, convert_arena_deck_to_named, is_arena_deck_id, is_deck_id,
+    load_arena_deck_from_cache, load_deck_from_cache,
+};
+use mtg_core::parse_deck_list;
 
 /// Convert mtg_core DeckCard to CLI DeckCard
 fn convert_core_deck_card_to_cli(core_card: &mtg_core::DeckCard) -> super::DeckCard {
@@ -21,7 +29,10 @@ fn convert_cli_deck_card_to_core(cli_card: &super::DeckCard) -> mtg_core::DeckCa
         name: cli_card.name.clone(),
         set_code: cli_card.set_code.clone(),
         collector_number: cli_card.collector_number.clone(),
-        card_details: None, // We'll fetch this separately
+        card_details: cli_card
+            .card_details
+            .as_ref()
+            .map(crate::scryfall::convert_cli_card_to_core),
     }
 }
 
@@ -42,7 +53,7 @@ pub fn convert_core_deck_list_to_cli(core_deck_list: &mtg_core::DeckList) -> sup
 }
 
 /// Convert CLI DeckList to mtg_core DeckList
-fn convert_cli_deck_list_to_core(cli_deck_list: &super::DeckList) -> mtg_core::DeckList {
+pub fn convert_cli_deck_list_to_core(cli_deck_list: &super::DeckList) -> mtg_core::DeckList {
     mtg_core::DeckList {
         main_deck: cli_deck_list
             .main_deck
@@ -69,10 +80,17 @@ pub async fn fetch_card_details_with_global(
     Ok(convert_core_deck_list_to_cli(&core_deck_with_details))
 }
 
-/// Calculate deck stats using mtg_core but with CLI types
+/// Calculate deck stats using mtg_core but with CLI types, applying the user's role tags
 pub fn calculate_deck_stats_cli(deck_list: &super::DeckList) -> Result<mtg_core::DeckStats> {
     let core_deck_list = convert_cli_deck_list_to_core(deck_list);
-    mtg_core::calculate_deck_stats(&core_deck_list)
+    let mut stats = mtg_core::calculate_deck_stats(&core_deck_list)?;
+
+    let overrides = RoleOverrides::load_or_default(&default_roles_path())?;
+    if !overrides.cards.is_empty() {
+        stats.roles = analyze_roles(&core_deck_list, &overrides);
+    }
+
+    Ok(stats)
 }
 
 /// Convert mtg_core ParsedDeck to CLI DeckList
@@ -90,3 +108,178 @@ pub fn convert_parsed_deck_to_cli_deck_list(parsed_deck: &mtg_core::ParsedDeck)
             .collect(),
     }
 }
+
+/// Read deck input from a file, an argument, or stdin
+pub fn read_deck_input(input: Option<MaybeStdin<String>>, file: Option<String>) -> Result<String> {
+    let deck_content = if let Some(file_path) = file {
+        std::fs::read_to_string(&file_path)
+            .map_err(|e| eyre!("Failed to read file '{}': {}", file_path, e))?
+    } else if let Some(input_maybe_stdin) = input {
+        input_maybe_stdin.to_string()
+    } else {
+        // If no input provided, read from stdin
+        use std::io::Read;
+        let mut buffer = String::new();
+        std::io::stdin()
+            .read_to_string(&mut buffer)
+            .map_err(|e| eyre!("Failed to read from stdin: {}", e))?;
+        buffer
+    };
+
+    // Check if content is empty
+    if deck_content.trim().is_empty() {
+        return Err(eyre!(
+            "Deck list is empty. Please provide a valid deck list."
+        ));
+    }
+
+    Ok(deck_content)
+}
+
+/// Resolve deck input (text, deck ID, article ID, Arena deck ID or library deck name)
+/// to a deck list without fetching card details.
+///
+/// Returns the deck list and whether it came from Arena, whose decks already have details.
+pub async fn resolve_deck_list(
+    deck_content: &str,
+    global: &crate::Global,
+) -> Result<(super::DeckList, bool)> {
+    let deck_content = deck_content.to_string();
+
+    // Check if the input is an Arena deck ID (UUID format)
+    let resolved = if is_arena_deck_id(&deck_content) {
+        aeprintln!("Detected MTG Arena deck ID: {}", deck_content.trim());
+
+        // Try to fetch Arena deck from cache
+        match load_arena_deck_from_cache(deck_content.trim()).await {
+            Ok((arena_deck, deck_name)) => {
+                aeprintln!("Found Arena deck: {}", deck_name);
+
+                // Convert Arena card IDs to actual card names
+                let scryfall_client = global.create_scryfall_client()?;
+                let converted_deck =
+                    convert_arena_deck_to_named(arena_deck, &deck_name, &scryfall_client).await?;
+                (convert_core_deck_list_to_cli(&converted_deck), true)
+            }
+            Err(e) => {
+                return Err(e);
+            }
+        }
+    } else if is_deck_id(&deck_content) {
+        // Try to fetch deck from cache (regular deck ID)
+        match load_deck_from_cache(&deck_content).await {
+            Ok(deck) => (convert_core_deck_list_to_cli(&deck), false),
+            Err(_) => {
+                // If not found as deck, try as article ID
+                let decks = crate::decks::ranked::fetch_decks_from_article(&deck_content, global)
+                    .await
+                    .map_err(|_| {
+                        eyre!(
+                            "ID '{}' not found as deck or article ID",
+                            deck_content.trim()
+                        )
+                    })?;
+
+                if decks.is_empty() {
+                    return Err(eyre!("No decks found in article"));
+                }
+
+                // If multiple decks, inform user and use the first one
+                if decks.len() > 1 {
+                    eprintln!(
+                        "Note: Article contains {} decks. Analyzing the first deck (ID: {})",
+                        decks.len(),
+                        decks[0].id
+                    );
+                    eprintln!("To analyze other decks, use their specific IDs:");
+                    for (i, deck) in decks.iter().enumerate().skip(1) {
+                        let title = deck.title.as_deref().unwrap_or("Untitled");
+                        eprintln!("  {} - {} ({})", i + 1, deck.id, title);
+                    }
+                    eprintln!();
+                }
+
+                // Convert ParsedDeck to DeckList
+                (convert_parsed_deck_to_cli_deck_list(&decks[0]), false)
+            }
+        }
+    } else {
+        // A deck list always wins, the library is only asked for input that is not one
+        match parse_deck_list(&deck_content) {
+            Ok(core_deck_list) => (convert_core_deck_list_to_cli(&core_deck_list), false),
+            Err(e) => {
+                let library_deck = find_library_deck(&deck_content).ok_or(e)?;
+                aeprintln!(
+                    "Using library deck: {} (revision {})",
+                    library_deck.name,
+                    library_deck.latest().number
+                );
+                (
+                    convert_core_deck_list_to_cli(&library_deck.latest().deck_list),
+                    false,
+                )
+            }
+        }
+    };
+
+    Ok(resolved)
+}
+
+/// Find a library deck when the input is a single line naming a saved deck
+fn find_library_deck(deck_content: &str) -> Option<mtg_core::decks::LibraryDeck> {
+    let name = deck_content.trim();
+    if name.contains('\n') {
+        return None;
+    }
+
+    let library = mtg_core::decks::DeckLibrary::open_default();
+    if library.contains(name) {
+        library.get(name).ok()
+    } else {
+        None
+    }
+}
+
+/// Load a deck list (text, deck ID, article ID, Arena deck ID or library deck name) with card details
+pub async fn load_deck_with_details(
+    input: Option<MaybeStdin<String>>,
+    file: Option<String>,
+    global: &crate::Global,
+) -> Result<super::DeckList> {
+    let deck_content = read_deck_input(input, file)?;
+    let (deck_list, is_arena_deck) = resolve_deck_list(&deck_content, global).await?;
+
+    // For Arena decks, we already have card details from the conversion
+    if is_arena_deck {
+        return Ok(deck_list);
+    }
+
+    // Check if we already have card details cached
+    let deck_has_details = deck_list
+        .main_deck
+        .iter()
+        .any(|card| card.card_details.is_some())
+        || deck_list
+            .sideboard
+            .iter()
+            .any(|card| card.card_details.is_some());
+
+    if deck_has_details {
+        // We already have card details, use them
+        return Ok(deck_list);
+    }
+
+    // Fetch card details for non-Arena decks
+    let deck_with_fetched_details = fetch_card_details_with_global(deck_list, global).await?;
+
+    // Cache the deck with card details for future use
+    if is_deck_id(&deck_content) {
+        let core_deck = convert_cli_deck_list_to_core(&deck_with_fetched_details);
+        if let Err(e) = cache_deck_with_details(deck_content.trim(), &core_deck).await {
+            // Log error but don't fail the command
+            eprintln!("Warning: Failed to cache deck with details: {}", e);
+        }
+    }
+
+    Ok(deck_with_fetched_details)
+}
@@ -0,0 +1,279 @@
+use clap::Args;
+use clap_stdin::MaybeStdin;
+use color_eyre::owo_colors::OwoColorize;
+use prettytable::{Cell, Row};
+use std::collections::HashSet;
+
+use super::utils::{convert_cli_deck_list_to_core, load_deck_with_details};
+use crate::prelude::*;
+use mtg_core::cache::DiskCacheBuilder;
+use mtg_core::collection::{parse_arena_wildcards, wildcard_cost, Collection, WildcardReport};
+use mtg_core::{DeckList, ParsedDeck};
+
+#[derive(Args, Debug)]
+pub struct WildcardsArgs {
+    /// Deck list input (use '-' for stdin, provide deck list as string, deck ID, or library deck name)
+    #[clap(value_name = "DECK_LIST_OR_ID")]
+    input: Option<MaybeStdin<String>>,
+
+    /// Read deck list from file
+    #[clap(short, long, value_name = "FILE")]
+    file: Option<String>,
+
+    /// MTG Arena Player.log to read the wildcards on hand from
+    #[clap(long, env = "MTGA_PLAYER_LOG", value_name = "FILE")]
+    log: Option<String>,
+
+    /// Rank the cached ranked decks by the wildcards needed to complete them
+    #[clap(long)]
+    ranked: bool,
+
+    /// Only rank cached decks of this format
+    #[clap(long, requires = "ranked")]
+    format: Option<String>,
+
+    /// Number of ranked decks to show
+    #[clap(long, default_value = "10")]
+    limit: usize,
+
+    /// Output format (pretty or json)
+    #[clap(long, default_value = "pretty")]
+    output: String,
+}
+
+impl WildcardsArgs {
+    pub async fn run(self, global: &crate::Global) -> Result<()> {
+        if self.input.is_none() && self.file.is_none() && !self.ranked {
+            return Err(eyre!("Provide a deck or use --ranked to rank cached decks"));
+        }
+
+        let on_hand = match &self.log {
+            Some(path) => {
+                let log = std::fs::read_to_string(path)
+                    .map_err(|e| eyre!("Failed to read log file '{}': {}", path, e))?;
+                let wildcards = parse_arena_wildcards(&log);
+                if wildcards.is_none() {
+                    aeprintln!("No wildcard inventory found in '{}'", path);
+                }
+                wildcards
+            }
+            None => None,
+        };
+
+        let collection = Collection::load_default()?;
+        let mut output = serde_json::Map::new();
+
+        if self.input.is_some() || self.file.is_some() {
+            let deck_list = load_deck_with_details(self.input, self.file, global).await?;
+            let report = wildcard_cost(
+                &convert_cli_deck_list_to_core(&deck_list),
+                &collection,
+                on_hand,
+            );
+
+            if self.output == "json" {
+                output.insert("deck".to_string(), serde_json::to_value(&report)?);
+            } else {
+                print_report(&report);
+            }
+        }
+
+        if self.ranked {
+            let ranked = rank_cached_decks(self.format.as_deref(), &collection, on_hand, global)
+                .await?
+                .into_iter()
+                .take(self.limit)
+                .collect::<Vec<_>>();
+
+            if self.output == "json" {
+                let decks: Vec<serde_json::Value> = ranked
+                    .iter()
+                    .map(|(deck, report)| {
+                        serde_json::json!({
+                            "id": deck.id,
+                            "title": deck.title,
+                            "format": deck.format,
+                            "wildcards": report,
+                        })
+                    })
+                    .collect();
+                output.insert("ranked".to_string(), serde_json::Value::Array(decks));
+            } else {
+                print_ranked(&ranked);
+            }
+        }
+
+        if self.output == "json" {
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+
+        Ok(())
+    }
+}
+
+/// Wildcard reports for every cached ranked deck, cheapest first
+async fn rank_cached_decks(
+    format: Option<&str>,
+    collection: &Collection,
+    on_hand: Option<mtg_core::collection::Wildcards>,
+    global: &crate::Global,
+) -> Result<Vec<(ParsedDeck, WildcardReport)>> {
+    let cache = DiskCacheBuilder::new().prefix("ranked_list").build()?;
+    let mut seen = HashSet::new();
+    let decks: Vec<ParsedDeck> = cache
+        .values::<ParsedDeck>()
+        .await?
+        .into_iter()
+        .filter(|deck| !deck.main_deck.is_empty() && seen.insert(deck.id.clone()))
+        .filter(|deck| match format {
+            Some(format) => deck
+                .format
+                .as_deref()
+                .is_some_and(|f| f.to_lowercase().contains(&format.to_lowercase())),
+            None => true,
+        })
+        .collect();
+
+    if decks.is_empty() {
+        return Err(eyre!(
+            "No cached ranked decks found. Load some with 'mtg decks ranked show <ARTICLE>' first"
+        ));
+    }
+
+    aeprintln!("Pricing {} cached decks in wildcards...", decks.len());
+    let client = global.create_scryfall_client()?;
+    let mut ranked = Vec::new();
+    for deck in decks {
+        let deck_list = match mtg_core::decks::utils::fetch_card_details(
+            DeckList {
+                main_deck: deck.main_deck.clone(),
+                sideboard: deck.sideboard.clone(),
+            },
+            &client,
+        )
+        .await
+        {
+            Ok(deck_list) => deck_list,
+            Err(e) => {
+                aeprintln!("Skipping deck {}: {}", deck.id, e);
+                continue;
+            }
+        };
+        let report = wildcard_cost(&deck_list, collection, on_hand);
+        ranked.push((deck, report));
+    }
+
+    ranked.sort_by_key(|(_, report)| report.cost_key());
+    Ok(ranked)
+}
+
+fn print_report(report: &WildcardReport) {
+    println!("\n{}", "Wildcards Needed".bold().underline());
+
+    let mut table = new_table();
+    let mut header = vec![Cell::new("Rarity"), Cell::new("Needed")];
+    if report.on_hand.is_some() {
+        header.push(Cell::new("On Hand"));
+        header.push(Cell::new("Short"));
+    }
+    table.add_row(Row::new(header));
+
+    let on_hand = report.on_hand.map(|w| w.by_rarity());
+    let shortfall = report.shortfall.map(|w| w.by_rarity());
+    for (index, (rarity, needed)) in report.needed.by_rarity().iter().enumerate() {
+        let mut cells = vec![Cell::new(rarity), Cell::new(&needed.to_string())];
+        if let (Some(on_hand), Some(shortfall)) = (&on_hand, &shortfall) {
+            cells.push(Cell::new(&on_hand[index].1.to_string()));
+            let short = shortfall[index].1;
+            cells.push(if short > 0 {
+                Cell::new(&short.to_string()).style_spec("Fr")
+            } else {
+                Cell::new("0")
+            });
+        }
+        table.add_row(Row::new(cells));
+    }
+    table.printstd();
+
+    if !report.cards.is_empty() {
+        println!("\n{}", "Cards to Craft".bold());
+        for card in &report.cards {
+            println!(
+                "{} {} ({})",
+                card.missing,
+                card.name,
+                card.rarity.as_deref().unwrap_or("unknown rarity")
+            );
+        }
+    }
+
+    if !report.uncounted.is_empty() {
+        println!(
+            "\n{}",
+            "Not Counted (no wildcard for the rarity)".bold().yellow()
+        );
+        for card in &report.uncounted {
+            println!(
+                "{} {} ({})",
+                card.missing,
+                card.name,
+                card.rarity.as_deref().unwrap_or("unknown rarity")
+            );
+        }
+    }
+
+    if !report.not_on_arena.is_empty() {
+        println!("\n{}", "Not on Arena".bold().red());
+        for name in &report.not_on_arena {
+            println!("{}", name);
+        }
+    }
+
+    if report.on_hand.is_some() {
+        if report.is_craftable() {
+            println!("\n{}", "You can craft this deck now".green());
+        } else if report.not_on_arena.is_empty() && report.uncounted.is_empty() {
+            println!("\n{}", "Not enough wildcards to craft this deck".yellow());
+        }
+    }
+}
+
+fn print_ranked(ranked: &[(ParsedDeck, WildcardReport)]) {
+    println!(
+        "\n{}",
+        "Cheapest Cached Decks to Complete".bold().underline()
+    );
+
+    let mut table = new_table();
+    table.add_row(Row::new(vec![
+        Cell::new("#"),
+        Cell::new("Deck"),
+        Cell::new("ID"),
+        Cell::new("C"),
+        Cell::new("U"),
+        Cell::new("R"),
+        Cell::new("M"),
+        Cell::new("Not on Arena"),
+    ]));
+    for (index, (deck, report)) in ranked.iter().enumerate() {
+        let shortfall = report.shortfall.unwrap_or(report.needed);
+        let cell = |needed: u32, short: u32| {
+            if short > 0 {
+                Cell::new(&needed.to_string()).style_spec("Fr")
+            } else {
+                Cell::new(&needed.to_string())
+            }
+        };
+        table.add_row(Row::new(vec![
+            Cell::new(&(index + 1).to_string()),
+            Cell::new(deck.title.as_deref().unwrap_or("Unknown")),
+            Cell::new(&deck.id),
+            cell(report.needed.common, shortfall.common),
+            cell(report.needed.uncommon, shortfall.uncommon),
+            cell(report.needed.rare, shortfall.rare),
+            cell(report.needed.mythic, shortfall.mythic),
+            Cell::new(&report.not_on_arena.len().to_string()),
+        ]));
+    }
+    table.printstd();
+}
@@ -18,24 +18,6 @@ pub enum Error {
     Json(#[from] serde_json::Error),
 }
 
-/// Comprehensive Scryfall API error response structure
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ScryfallError {
-    /// Always "error"
-    pub object: String,
-    /// HTTP status code
-    pub status: u16,
-    /// Computer-friendly error code
-    pub code: String,
-    /// Human-readable explanation
-    pub details: String,
-    /// Additional context for the error (e.g., "ambiguous")
-    #[serde(rename = "type")]
-    pub error_type: Option<String>,
-    /// Non-fatal warnings
-    pub warnings: Option<Vec<String>>,
-}
-
 /// Enhanced API error types with structured information
 #[derive(thiserror::Error, Debug, Clone, Serialize, Deserialize)]
 pub enum ScryfallApiError {
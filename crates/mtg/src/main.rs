@@ -6,9 +6,7 @@ use clap::Parser;
 use std::io::Write;
 
 mod api;
-// Companion module is only available on macOS and Windows since MTG Arena
-// is not available on Linux platforms
-#[cfg(any(target_os = "macos", target_os = "windows"))]
+mod collection;
 mod companion;
 mod completions;
 mod decks;
@@ -17,6 +15,7 @@ mod gatherer;
 mod mcp;
 mod prelude;
 mod scryfall;
+mod sealed;
 
 #[derive(Debug, clap::Parser)]
 #[command(author, version, about, long_about = "Magic The Gathering API CLI")]
@@ -229,6 +228,12 @@ pub enum SubCommands {
     /// Analyze Magic: The Gathering deck lists
     Decks(crate::decks::App),
 
+    /// Track the cards you own and check which cards decks need
+    Collection(crate::collection::App),
+
+    /// Open sealed or draft boosters generated from Scryfall set data
+    Sealed(crate::sealed::App),
+
     /// Manage cache data
     Cache {
         #[command(subcommand)]
@@ -242,7 +247,6 @@ pub enum SubCommands {
     },
 
     /// Track and analyze MTG Arena log files
-    #[cfg(any(target_os = "macos", target_os = "windows"))]
     Companion(crate::companion::App),
 }
 
@@ -398,6 +402,8 @@ async fn main() -> Result<()> {
 
         SubCommands::Completions(sub_app) => crate::completions::run(sub_app, app.global).await,
         SubCommands::Decks(sub_app) => crate::decks::run(sub_app, app.global).await,
+        SubCommands::Collection(sub_app) => crate::collection::run(sub_app, app.global).await,
+        SubCommands::Sealed(sub_app) => crate::sealed::run(sub_app, app.global).await,
         SubCommands::Cache { command } => execute_cache_command(command, &app.global).await,
         SubCommands::Mcp { command } => match command {
             Some(McpCommands::Stdio) | None => crate::mcp::run_mcp_server(app.global).await,
@@ -405,7 +411,6 @@ async fn main() -> Result<()> {
                 crate::mcp::run_sse_server(app.global, host, port).await
             }
         },
-        #[cfg(any(target_os = "macos", target_os = "windows"))]
         SubCommands::Companion(sub_app) => crate::companion::run(sub_app, app.global).await,
     }
     .map_err(|err: color_eyre::eyre::Report| eyre!(err))
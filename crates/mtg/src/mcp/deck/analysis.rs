@@ -12,13 +12,22 @@ impl Mcp {
     pub fn tool() -> Tool {
         Tool {
             name: "analyze_deck_list".to_string(),
-            description: Some("Analyze a Magic: The Gathering deck list and provide comprehensive statistics including mana curve, type distribution, format legality, and more.".to_string()),
+            description: Some("Analyze a Magic: The Gathering deck list and provide comprehensive statistics including mana curve, type distribution, mana base, card roles (removal, card draw, ramp, threats...), deck price, format legality, and more. Set 'budget' to suggest the cheapest printing of every card.".to_string()),
             input_schema: json!({
                 "type": "object",
                 "properties": {
                     "deck_list": {
                         "type": "string",
                         "description": "Deck list in standard format with 'Deck' and 'Sideboard' sections. Format: 'quantity cardname (set) collector_number'"
+                    },
+                    "budget": {
+                        "type": "boolean",
+                        "description": "Suggest the cheapest printing of every card (default: false)"
+                    },
+                    "currency": {
+                        "type": "string",
+                        "enum": ["usd", "eur", "tix"],
+                        "description": "Currency for the budget suggestions (default: usd)"
                     }
                 },
                 "required": ["deck_list"]
@@ -34,10 +43,30 @@ impl Mcp {
                 let args = request.arguments.as_ref().unwrap_or(&empty_args);
 
                 if let Some(deck_list) = args.get("deck_list").and_then(|v| v.as_str()) {
+                    let budget = args
+                        .get("budget")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    let currency = args
+                        .get("currency")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("usd")
+                        .parse::<mtg_core::decks::Currency>();
+                    let currency = match currency {
+                        Ok(currency) => currency,
+                        Err(e) => return tool_text_response!(format!("Error: {}", e)),
+                    };
+
                     // Create a default Global config
                     let global = crate::Global::new();
 
-                    match crate::decks::analyze_deck_list_mcp(deck_list, global).await {
+                    match crate::decks::analyze_deck_list_mcp(
+                        deck_list,
+                        budget.then_some(currency),
+                        global,
+                    )
+                    .await
+                    {
                         Ok(analysis) => tool_text_response!(analysis),
                         Err(e) => tool_text_response!(format!("Failed to analyze deck: {}", e)),
                     }
@@ -526,6 +526,7 @@ pub struct Card {
     colors: Option<Vec<String>>,
     pub color_identity: Vec<String>,
     keywords: Option<Vec<String>>,
+    produced_mana: Option<Vec<String>>,
     pub legalities: Value,
     games: Vec<String>,
     reserved: bool,
@@ -1224,6 +1225,7 @@ pub fn convert_core_card_to_cli(core_card: &mtg_core::scryfall::Card) -> Card {
         colors: core_card.colors.clone(),
         color_identity: core_card.color_identity.clone(),
         keywords: core_card.keywords.clone(),
+        produced_mana: core_card.produced_mana.clone(),
         legalities: core_card.legalities.clone(),
         games: core_card.games.clone(),
         reserved: core_card.reserved,
@@ -1296,6 +1298,7 @@ pub fn convert_cli_card_to_core(cli_card: &Card) -> mtg_core::scryfall::Card {
         colors: cli_card.colors.clone(),
         color_identity: cli_card.color_identity.clone(),
         keywords: cli_card.keywords.clone(),
+        produced_mana: cli_card.produced_mana.clone(),
         legalities: cli_card.legalities.clone(),
         games: cli_card.games.clone(),
         reserved: cli_card.reserved,
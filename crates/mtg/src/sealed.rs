@@ -0,0 +1,158 @@
+use crate::prelude::*;
+use color_eyre::owo_colors::OwoColorize;
+use mtg_core::decks::utils::format_deck_list;
+use mtg_core::sealed::{
+    fetch_set_cards, generate_pool, BoosterCard, BoosterKind, BoosterSheets, SealedConfig,
+    SealedPool,
+};
+use prettytable::{Cell, Row};
+
+#[derive(Debug, clap::Parser)]
+pub struct App {
+    /// Set code to open boosters from (e.g. dsk, blb)
+    pub set: String,
+
+    /// Number of boosters to open
+    #[clap(long, short, default_value = "6")]
+    pub packs: u32,
+
+    /// Booster kind (play or draft)
+    #[clap(long, short, default_value = "play")]
+    pub kind: String,
+
+    /// Seed for the random number generator (same seed, same pool)
+    #[clap(long)]
+    pub seed: Option<u64>,
+
+    /// Chance of a mythic in the rare slot (default 1/7 for play, 1/8 for draft boosters)
+    #[clap(long)]
+    pub mythic_rate: Option<f64>,
+
+    /// Show the contents of each booster instead of the combined pool
+    #[clap(long)]
+    pub show_packs: bool,
+
+    /// Write the pool as a deck list to this file
+    #[clap(long, short, value_name = "FILE")]
+    pub export: Option<String>,
+
+    /// Output format (pretty, json, or text for a deck list)
+    #[clap(long, default_value = "pretty")]
+    pub output: String,
+}
+
+pub async fn run(app: App, global: crate::Global) -> Result<()> {
+    if app.packs == 0 {
+        return Err(eyre!("Open at least one booster"));
+    }
+
+    let config = SealedConfig {
+        kind: app.kind.parse::<BoosterKind>()?,
+        packs: app.packs,
+        seed: app.seed,
+        mythic_rate: app.mythic_rate,
+    };
+
+    let client = global.create_scryfall_client()?;
+    aeprintln!("Loading the cards of {}...", app.set.to_uppercase());
+    let cards = fetch_set_cards(&client, &app.set).await?;
+    let sheets = BoosterSheets::from_cards(&app.set, cards)?;
+    let pool = generate_pool(&sheets, &config);
+
+    if let Some(path) = &app.export {
+        std::fs::write(path, format_deck_list(&pool.to_deck_list()))
+            .map_err(|e| eyre!("Failed to write '{}': {}", path, e))?;
+        aeprintln!("Pool written to {}", path);
+    }
+
+    match app.output.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&pool)?),
+        "text" => print!("{}", format_deck_list(&pool.to_deck_list())),
+        _ => output_pretty(&pool, app.show_packs),
+    }
+
+    Ok(())
+}
+
+fn output_pretty(pool: &SealedPool, show_packs: bool) {
+    println!(
+        "\n{}",
+        f!(
+            "{} {} {} boosters",
+            pool.packs.len(),
+            pool.set.to_uppercase(),
+            pool.kind
+        )
+        .bold()
+        .underline()
+    );
+
+    if show_packs {
+        for (index, pack) in pool.packs.iter().enumerate() {
+            println!("\n{}", f!("Booster {}", index + 1).bold());
+            print_cards(pack.cards.iter().map(|card| (1, card)).collect());
+        }
+    } else {
+        let deck_list = pool.to_deck_list();
+        let mut cards: Vec<(u32, &BoosterCard)> = deck_list
+            .main_deck
+            .iter()
+            .filter_map(|entry| {
+                pool.cards()
+                    .find(|card| card.name == entry.name)
+                    .map(|card| (entry.quantity, card))
+            })
+            .collect();
+        cards.sort_by_key(|(_, card)| (rarity_order(&card.rarity), card.name.clone()));
+        println!();
+        print_cards(cards);
+    }
+
+    let rarities: Vec<String> = pool
+        .by_rarity()
+        .iter()
+        .map(|(rarity, count)| f!("{count} {rarity}"))
+        .collect();
+    println!("\n{} cards: {}", pool.total_cards(), rarities.join(", "));
+    println!(
+        "Seed: {} (use --seed {} to open the same pool again)",
+        pool.seed, pool.seed
+    );
+}
+
+fn print_cards(cards: Vec<(u32, &BoosterCard)>) {
+    let mut table = new_table();
+    table.add_row(Row::new(vec![
+        Cell::new("Qty"),
+        Cell::new("Name"),
+        Cell::new("Rarity"),
+        Cell::new("Set"),
+        Cell::new("Number"),
+    ]));
+    for (quantity, card) in cards {
+        let rarity = match card.rarity.as_str() {
+            "mythic" => Cell::new(&card.rarity).style_spec("Fr"),
+            "rare" => Cell::new(&card.rarity).style_spec("Fy"),
+            _ => Cell::new(&card.rarity),
+        };
+        table.add_row(Row::new(vec![
+            Cell::new(&quantity.to_string()),
+            Cell::new(&card.name),
+            rarity,
+            Cell::new(&card.set_code.to_uppercase()),
+            Cell::new(&card.collector_number),
+        ]));
+    }
+    table.printstd();
+}
+
+/// Rarest cards first
+fn rarity_order(rarity: &str) -> u8 {
+    match rarity {
+        "mythic" => 0,
+        "rare" => 1,
+        "uncommon" => 2,
+        "common" => 3,
+        _ => 4,
+    }
+}
@@ -138,4 +138,41 @@ mod tests {
             .build();
         assert!(cache.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_values_skips_other_entries() {
+        use crate::cache::CacheStore;
+
+        let temp_dir = TempDir::new().unwrap();
+        let cache = DiskCacheBuilder::new()
+            .base_path(temp_dir.path())
+            .prefix("values")
+            .build()
+            .unwrap();
+
+        cache
+            .insert("a", serde_json::json!({"n": 1}))
+            .await
+            .unwrap();
+        cache
+            .insert("b", serde_json::json!({"n": 2}))
+            .await
+            .unwrap();
+        cache.insert("c", serde_json::json!("text")).await.unwrap();
+
+        #[derive(serde::Deserialize)]
+        struct Entry {
+            n: u32,
+        }
+
+        let mut values: Vec<u32> = cache
+            .values::<Entry>()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.n)
+            .collect();
+        values.sort();
+        assert_eq!(values, vec![1, 2]);
+    }
 }
@@ -161,6 +161,44 @@ impl DiskCache {
         Ok(prefixes)
     }
 
+    /// All cached values that deserialize as `V`, skipping any other entries.
+    ///
+    /// Keys are stored hashed, so this is the only way to enumerate entries of
+    /// one kind (for example every parsed deck) under a prefix.
+    pub async fn values<V>(&self) -> Result<Vec<V>>
+    where
+        V: for<'de> Deserialize<'de>,
+    {
+        let mut values = Vec::new();
+        let path = match &self.prefix {
+            Some(prefix) => self.base_path.join(prefix),
+            None => self.base_path.clone(),
+        };
+
+        if !path.exists() {
+            return Ok(values);
+        }
+
+        let mut stack = vec![path];
+        while let Some(current_path) = stack.pop() {
+            let mut entries = fs::read_dir(&current_path).await?;
+
+            while let Some(entry) = entries.next_entry().await? {
+                let entry_path = entry.path();
+                if entry.metadata().await?.is_dir() {
+                    stack.push(entry_path);
+                } else if entry_path.extension().and_then(|s| s.to_str()) == Some("cache") {
+                    let data = fs::read(&entry_path).await?;
+                    if let Ok(entry) = self.serializer.deserialize::<CacheEntry<V>>(&data) {
+                        values.push(entry.value);
+                    }
+                }
+            }
+        }
+
+        Ok(values)
+    }
+
     /// Get cache statistics
     pub async fn stats(&self, prefix: Option<&str>) -> Result<CacheStats> {
         let path = match prefix {
@@ -0,0 +1,191 @@
+use super::CollectionCard;
+use color_eyre::{eyre::eyre, Result};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Header names used by common collection tools, most specific first
+const QUANTITY_COLUMNS: &[&str] = &["quantity", "count", "qty", "amount"];
+const NAME_COLUMNS: &[&str] = &["name", "card name", "card"];
+const SET_COLUMNS: &[&str] = &["set code", "edition code", "edition", "set"];
+const NUMBER_COLUMNS: &[&str] = &["collector number", "card number", "number", "cn"];
+const FOIL_COLUMNS: &[&str] = &["foil", "finish", "printing"];
+const RARITY_COLUMNS: &[&str] = &["rarity"];
+
+/// Parse a collection exported as CSV.
+///
+/// Columns are found by their header so exports from Moxfield, Deckbox,
+/// ManaBox, Archidekt, Dragon Shield and TCGplayer all work. Only the name is
+/// required, the quantity defaults to one copy.
+pub fn parse_collection_csv(content: &str) -> Result<Vec<CollectionCard>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| eyre!("Failed to read CSV header: {}", e))?
+        .iter()
+        .map(|header| header.to_lowercase())
+        .collect();
+    let column = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| headers.iter().position(|header| header == name))
+    };
+
+    let name_column = column(NAME_COLUMNS)
+        .ok_or_else(|| eyre!("CSV has no card name column (expected one of: Name, Card Name)"))?;
+    let quantity_column = column(QUANTITY_COLUMNS);
+    let set_column = column(SET_COLUMNS);
+    let number_column = column(NUMBER_COLUMNS);
+    let foil_column = column(FOIL_COLUMNS);
+    let rarity_column = column(RARITY_COLUMNS);
+
+    let mut cards = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| eyre!("Failed to read CSV row {}: {}", index + 2, e))?;
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|column| record.get(column))
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        let Some(name) = field(Some(name_column)) else {
+            continue;
+        };
+        let quantity = match field(quantity_column) {
+            Some(quantity) => quantity.parse().map_err(|_| {
+                eyre!(
+                    "Invalid quantity '{}' for {} on row {}",
+                    quantity,
+                    name,
+                    index + 2
+                )
+            })?,
+            None => 1,
+        };
+
+        cards.push(CollectionCard {
+            name,
+            set_code: field(set_column).map(|set| set.to_lowercase()),
+            collector_number: field(number_column),
+            foil: field(foil_column).is_some_and(|value| is_foil(&value)),
+            rarity: field(rarity_column).map(|rarity| normalize_rarity(&rarity)),
+            arena_id: None,
+            arena: false,
+            quantity,
+        });
+    }
+
+    Ok(cards)
+}
+
+fn is_foil(value: &str) -> bool {
+    !matches!(
+        value.to_lowercase().as_str(),
+        "" | "normal" | "nonfoil" | "non-foil" | "false" | "no" | "0"
+    )
+}
+
+/// Rarity in Scryfall's spelling (`common`, `uncommon`, `rare`, `mythic`)
+fn normalize_rarity(rarity: &str) -> String {
+    match rarity.to_lowercase().as_str() {
+        "c" => "common".to_string(),
+        "u" => "uncommon".to_string(),
+        "r" => "rare".to_string(),
+        "m" | "mythic rare" => "mythic".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Extract the card collection from an MTG Arena log.
+///
+/// Arena logs the collection as a JSON object of card ids (`grpId`) to copies
+/// in the response to `PlayerInventory.GetPlayerCards`, either on the same line
+/// or the next one, sometimes wrapped in a `payload` object. The last
+/// collection in the log wins.
+pub fn parse_arena_collection(log: &str) -> Option<BTreeMap<u32, u32>> {
+    let mut collection = None;
+    let mut lines = log.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        if !line.contains("GetPlayerCards") {
+            continue;
+        }
+
+        let json = match line.find('{') {
+            Some(start) => Some(&line[start..]),
+            None => lines
+                .peek()
+                .map(|next| next.trim())
+                .filter(|next| next.starts_with('{')),
+        };
+
+        if let Some(cards) = json.and_then(parse_card_counts) {
+            collection = Some(cards);
+        }
+    }
+
+    collection
+}
+
+fn parse_card_counts(json: &str) -> Option<BTreeMap<u32, u32>> {
+    let value: Value = serde_json::from_str(json).ok()?;
+    let object = value.get("payload").unwrap_or(&value).as_object()?;
+
+    let cards: BTreeMap<u32, u32> = object
+        .iter()
+        .filter_map(|(id, count)| Some((id.parse().ok()?, count.as_u64()? as u32)))
+        .filter(|(_, count)| *count > 0)
+        .collect();
+
+    (!cards.is_empty()).then_some(cards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_collection_csv() {
+        let moxfield = "\"Count\",\"Tradelist Count\",\"Name\",\"Edition\",\"Condition\",\"Language\",\"Foil\",\"Collector Number\"\n\
+            \"4\",\"0\",\"Lightning Bolt\",\"m10\",\"Near Mint\",\"English\",\"\",\"146\"\n\
+            \"1\",\"0\",\"Fable of the Mirror-Breaker // Reflection of Kiki-Jiki\",\"NEO\",\"Near Mint\",\"English\",\"foil\",\"141\"\n";
+        let cards = parse_collection_csv(moxfield).unwrap();
+
+        assert_eq!(cards.len(), 2);
+        assert_eq!(cards[0].quantity, 4);
+        assert_eq!(cards[0].set_code.as_deref(), Some("m10"));
+        assert_eq!(cards[0].collector_number.as_deref(), Some("146"));
+        assert!(!cards[0].foil);
+        assert!(cards[1].foil);
+        assert_eq!(cards[1].set_code.as_deref(), Some("neo"));
+
+        let manabox = "Name,Set code,Set name,Collector number,Foil,Rarity,Quantity\n\
+            Opt,XLN,Ixalan,65,normal,common,3\n\
+            \"Sheoldred, the Apocalypse\",DMU,Dominaria United,107,normal,mythic,1\n";
+        let cards = parse_collection_csv(manabox).unwrap();
+        assert_eq!(cards[0].quantity, 3);
+        assert_eq!(cards[1].name, "Sheoldred, the Apocalypse");
+        assert_eq!(cards[1].rarity.as_deref(), Some("mythic"));
+
+        assert!(parse_collection_csv("Quantity,Set\n1,m10\n").is_err());
+        assert!(parse_collection_csv("Name,Quantity\nOpt,many\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_arena_collection() {
+        let log = "[UnityCrossThreadLogger]==> PlayerInventory.GetPlayerCardsV3 {\"id\":1}\n\
+            [UnityCrossThreadLogger]<== PlayerInventory.GetPlayerCardsV3(1)\n\
+            {\"payload\":{\"67330\":4,\"70141\":1,\"1\":0}}\n\
+            [UnityCrossThreadLogger]<== PlayerInventory.GetPlayerCardsV3(2) {\"67330\":4,\"70141\":2}\n";
+
+        let cards = parse_arena_collection(log).unwrap();
+        assert_eq!(cards.len(), 2);
+        assert_eq!(cards[&70141], 2);
+
+        assert!(parse_arena_collection("no inventory here").is_none());
+    }
+}
@@ -0,0 +1,383 @@
+use crate::companion::ArenaCard;
+use crate::decks::mana::basic_land_color;
+use crate::decks::DeckList;
+use crate::paths;
+use crate::scryfall::Card;
+use chrono::{DateTime, Utc};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+pub mod import;
+pub mod wildcards;
+
+pub use import::{parse_arena_collection, parse_collection_csv};
+pub use wildcards::{parse_arena_wildcards, wildcard_cost, WildcardReport, Wildcards};
+
+/// Owned copies of one printing of a card
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionCard {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub set_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collector_number: Option<String>,
+    #[serde(default)]
+    pub foil: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rarity: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arena_id: Option<u32>,
+    /// Imported from the MTG Arena inventory, which is replaced on every Arena import
+    #[serde(default)]
+    pub arena: bool,
+    pub quantity: u32,
+}
+
+impl CollectionCard {
+    /// A collection entry for a Scryfall printing
+    pub fn from_card(card: &Card, quantity: u32) -> Self {
+        Self {
+            name: card.name.clone(),
+            set_code: Some(card.set.clone()),
+            collector_number: Some(card.collector_number.clone()),
+            foil: false,
+            rarity: Some(card.rarity.clone()),
+            arena_id: card.arena_id,
+            arena: false,
+            quantity,
+        }
+    }
+
+    /// A collection entry for a card of the Arena inventory
+    pub fn from_arena_card(card: &ArenaCard, quantity: u32) -> Self {
+        Self {
+            name: card.name.clone(),
+            set_code: card.set.as_deref().map(str::to_lowercase),
+            collector_number: card.collector_number.clone(),
+            foil: false,
+            rarity: card.rarity.clone(),
+            arena_id: Some(card.grp_id),
+            arena: true,
+            quantity,
+        }
+    }
+
+    fn key(&self) -> (String, String, String, bool, bool) {
+        (
+            self.name.to_lowercase(),
+            self.set_code.clone().unwrap_or_default().to_lowercase(),
+            self.collector_number.clone().unwrap_or_default(),
+            self.foil,
+            self.arena,
+        )
+    }
+}
+
+/// Cards a player owns, stored per printing
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Collection {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub cards: Vec<CollectionCard>,
+}
+
+/// Default location of the collection file, `MTG_COLLECTION_FILE` overrides it
+pub fn default_collection_path() -> PathBuf {
+    paths::data_file("MTG_COLLECTION_FILE", "collection.json")
+}
+
+impl Collection {
+    /// Load a collection, an empty one when the file does not exist yet
+    pub fn load(path: &Path) -> Result<Self> {
+        paths::read_json_or_default(path, "collection")
+    }
+
+    pub fn load_default() -> Result<Self> {
+        Self::load(&default_collection_path())
+    }
+
+    pub fn save(&mut self, path: &Path) -> Result<()> {
+        self.updated_at = Some(Utc::now());
+        self.cards.sort_by_key(|card| card.key());
+        paths::write_json_atomic(path, self)
+    }
+
+    pub fn save_default(&mut self) -> Result<()> {
+        self.save(&default_collection_path())
+    }
+
+    /// Add cards, summing the copies of printings already in the collection
+    pub fn merge(&mut self, cards: impl IntoIterator<Item = CollectionCard>) {
+        let mut index: HashMap<_, usize> = self
+            .cards
+            .iter()
+            .enumerate()
+            .map(|(i, card)| (card.key(), i))
+            .collect();
+
+        for card in cards {
+            if card.quantity == 0 {
+                continue;
+            }
+            match index.get(&card.key()) {
+                Some(&i) => {
+                    let existing = &mut self.cards[i];
+                    existing.quantity += card.quantity;
+                    existing.rarity = existing.rarity.take().or(card.rarity);
+                    existing.arena_id = existing.arena_id.or(card.arena_id);
+                }
+                None => {
+                    index.insert(card.key(), self.cards.len());
+                    self.cards.push(card);
+                }
+            }
+        }
+    }
+
+    /// Replace the whole collection with new cards
+    pub fn replace(&mut self, cards: impl IntoIterator<Item = CollectionCard>) {
+        self.cards.clear();
+        self.merge(cards);
+    }
+
+    /// Replace the cards of an earlier Arena import with a new inventory snapshot.
+    ///
+    /// Arena logs the whole inventory every time, so adding it up like a CSV import would
+    /// count the same cards again. Cards imported from other sources are kept.
+    pub fn replace_arena(&mut self, cards: impl IntoIterator<Item = CollectionCard>) {
+        self.cards.retain(|card| !card.arena);
+        self.merge(cards.into_iter().map(|card| CollectionCard {
+            arena: true,
+            ..card
+        }));
+    }
+
+    /// Only the cards of the Arena inventory, without the ones imported from other sources
+    pub fn arena(&self) -> Collection {
+        Collection {
+            updated_at: self.updated_at,
+            cards: self
+                .cards
+                .iter()
+                .filter(|card| card.arena)
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Copies owned of a card across all printings
+    pub fn owned(&self, name: &str) -> u32 {
+        self.cards
+            .iter()
+            .filter(|card| card.name.eq_ignore_ascii_case(name))
+            .map(|card| card.quantity)
+            .sum()
+    }
+
+    /// Number of distinct card names
+    pub fn unique_cards(&self) -> usize {
+        let mut names: Vec<String> = self.cards.iter().map(|c| c.name.to_lowercase()).collect();
+        names.sort();
+        names.dedup();
+        names.len()
+    }
+
+    pub fn total_cards(&self) -> u32 {
+        self.cards.iter().map(|card| card.quantity).sum()
+    }
+
+    /// Total copies per rarity, `unknown` when the rarity was never recorded
+    pub fn by_rarity(&self) -> BTreeMap<String, u32> {
+        let mut counts = BTreeMap::new();
+        for card in &self.cards {
+            let rarity = card.rarity.clone().unwrap_or_else(|| "unknown".to_string());
+            *counts.entry(rarity).or_default() += card.quantity;
+        }
+        counts
+    }
+
+    fn rarity_of(&self, name: &str) -> Option<String> {
+        self.cards
+            .iter()
+            .find(|card| card.name.eq_ignore_ascii_case(name) && card.rarity.is_some())
+            .and_then(|card| card.rarity.clone())
+    }
+
+    /// Cards of a deck the collection does not have enough copies of.
+    ///
+    /// Main deck and sideboard copies are added up, basic lands are skipped.
+    /// Rarities come from the deck's card details, falling back to the ones
+    /// recorded in the collection.
+    pub fn missing(&self, deck_list: &DeckList) -> MissingReport {
+        let mut needed: Vec<(String, u32, Option<String>)> = Vec::new();
+        for card in deck_list.main_deck.iter().chain(&deck_list.sideboard) {
+            if basic_land_color(&card.name).is_some() || card.name == "Wastes" {
+                continue;
+            }
+            let rarity = card.card_details.as_ref().map(|c| c.rarity.clone());
+            match needed
+                .iter_mut()
+                .find(|(name, _, _)| name.eq_ignore_ascii_case(&card.name))
+            {
+                Some(entry) => {
+                    entry.1 += card.quantity;
+                    entry.2 = entry.2.take().or(rarity);
+                }
+                None => needed.push((card.name.clone(), card.quantity, rarity)),
+            }
+        }
+
+        let mut report = MissingReport::default();
+        for (name, needed, rarity) in needed {
+            let owned = self.owned(&name);
+            if owned >= needed {
+                continue;
+            }
+
+            let rarity = rarity.or_else(|| self.rarity_of(&name));
+            let missing = needed - owned;
+            *report
+                .by_rarity
+                .entry(rarity.clone().unwrap_or_else(|| "unknown".to_string()))
+                .or_default() += missing;
+            report.total_missing += missing;
+            report.cards.push(MissingCard {
+                name,
+                needed,
+                owned,
+                missing,
+                rarity,
+            });
+        }
+
+        report
+    }
+}
+
+/// A card of a deck that is not owned in full
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissingCard {
+    pub name: String,
+    pub needed: u32,
+    pub owned: u32,
+    pub missing: u32,
+    pub rarity: Option<String>,
+}
+
+/// What a collection lacks to build a deck
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MissingReport {
+    pub cards: Vec<MissingCard>,
+    /// Missing copies per rarity
+    pub by_rarity: BTreeMap<String, u32>,
+    pub total_missing: u32,
+}
+
+impl MissingReport {
+    pub fn is_buildable(&self) -> bool {
+        self.total_missing == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decks::DeckCard;
+
+    fn owned(quantity: u32, name: &str, set_code: &str, rarity: &str) -> CollectionCard {
+        CollectionCard {
+            name: name.to_string(),
+            set_code: Some(set_code.to_string()),
+            collector_number: None,
+            foil: false,
+            rarity: Some(rarity.to_string()),
+            arena_id: None,
+            arena: false,
+            quantity,
+        }
+    }
+
+    fn entry(quantity: u32, name: &str) -> DeckCard {
+        DeckCard {
+            quantity,
+            name: name.to_string(),
+            set_code: None,
+            collector_number: None,
+            card_details: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_and_owned() {
+        let mut collection = Collection::default();
+        collection.merge(vec![
+            owned(2, "Lightning Bolt", "m10", "common"),
+            owned(1, "Lightning Bolt", "2xm", "uncommon"),
+        ]);
+        collection.merge(vec![owned(1, "lightning bolt", "M10", "common")]);
+
+        assert_eq!(collection.cards.len(), 2);
+        assert_eq!(collection.owned("Lightning Bolt"), 4);
+        assert_eq!(collection.unique_cards(), 1);
+        assert_eq!(collection.by_rarity()["common"], 3);
+
+        collection.replace(vec![owned(1, "Opt", "xln", "common")]);
+        assert_eq!(collection.owned("Lightning Bolt"), 0);
+        assert_eq!(collection.total_cards(), 1);
+    }
+
+    #[test]
+    fn test_arena_import_twice() {
+        let mut collection = Collection::default();
+        collection.merge(vec![owned(2, "Lightning Bolt", "m10", "common")]);
+
+        let snapshot = vec![
+            owned(4, "Lightning Bolt", "m10", "common"),
+            owned(1, "Sheoldred, the Apocalypse", "dmu", "mythic"),
+        ];
+        collection.replace_arena(snapshot.clone());
+        collection.replace_arena(snapshot);
+
+        // The paper copies stay next to the Arena ones, which are only counted once
+        assert_eq!(collection.owned("Lightning Bolt"), 6);
+        assert_eq!(collection.owned("Sheoldred, the Apocalypse"), 1);
+
+        collection.replace_arena(vec![owned(3, "Lightning Bolt", "m10", "common")]);
+        assert_eq!(collection.owned("Lightning Bolt"), 5);
+        assert_eq!(collection.owned("Sheoldred, the Apocalypse"), 0);
+    }
+
+    #[test]
+    fn test_missing() {
+        let mut collection = Collection::default();
+        collection.merge(vec![
+            owned(3, "Lightning Bolt", "m10", "common"),
+            owned(1, "Ragavan, Nimble Pilferer", "mh2", "mythic"),
+        ]);
+
+        let deck = DeckList {
+            main_deck: vec![
+                entry(4, "Lightning Bolt"),
+                entry(4, "Ragavan, Nimble Pilferer"),
+                entry(20, "Mountain"),
+            ],
+            sideboard: vec![entry(2, "Abrade")],
+        };
+
+        let report = collection.missing(&deck);
+        assert!(!report.is_buildable());
+        assert_eq!(report.total_missing, 6);
+        assert_eq!(report.by_rarity["common"], 1);
+        assert_eq!(report.by_rarity["mythic"], 3);
+        assert_eq!(report.by_rarity["unknown"], 2);
+        assert!(!report.cards.iter().any(|card| card.name == "Mountain"));
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("collection.json");
+        collection.save(&path).unwrap();
+        assert_eq!(Collection::load(&path).unwrap().owned("Lightning Bolt"), 3);
+    }
+}
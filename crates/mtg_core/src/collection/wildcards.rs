@@ -0,0 +1,288 @@
+use super::Collection;
+use crate::companion::inventory::parse_inventory_line;
+use crate::decks::DeckList;
+use crate::scryfall::Card;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Arena wildcards, one count per rarity
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Wildcards {
+    pub common: u32,
+    pub uncommon: u32,
+    pub rare: u32,
+    pub mythic: u32,
+}
+
+impl Wildcards {
+    pub fn total(&self) -> u32 {
+        self.common + self.uncommon + self.rare + self.mythic
+    }
+
+    /// Add copies of a card of the given rarity, returning false for rarities
+    /// that have no wildcard
+    pub fn add(&mut self, rarity: &str, copies: u32) -> bool {
+        match rarity {
+            "common" => self.common += copies,
+            "uncommon" => self.uncommon += copies,
+            "rare" => self.rare += copies,
+            "mythic" => self.mythic += copies,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Wildcards still needed after spending the ones on hand
+    pub fn shortfall(&self, on_hand: &Wildcards) -> Wildcards {
+        Wildcards {
+            common: self.common.saturating_sub(on_hand.common),
+            uncommon: self.uncommon.saturating_sub(on_hand.uncommon),
+            rare: self.rare.saturating_sub(on_hand.rare),
+            mythic: self.mythic.saturating_sub(on_hand.mythic),
+        }
+    }
+
+    /// Counts per rarity, in rarity order
+    pub fn by_rarity(&self) -> [(&'static str, u32); 4] {
+        [
+            ("common", self.common),
+            ("uncommon", self.uncommon),
+            ("rare", self.rare),
+            ("mythic", self.mythic),
+        ]
+    }
+}
+
+/// Find the wildcards on hand in an MTG Arena log.
+///
+/// Arena logs the player's [`InventoryInfo`](crate::companion::InventoryInfo) with the wildcard counts, the last
+/// inventory in the log wins.
+pub fn parse_arena_wildcards(log: &str) -> Option<Wildcards> {
+    log.lines()
+        .rev()
+        .filter(|line| line.contains("InventoryInfo"))
+        .filter_map(parse_inventory_line)
+        .find_map(|inventory| inventory.wildcards())
+}
+
+/// Whether a card can be played on MTG Arena.
+///
+/// A single Scryfall printing may not be the Arena one, so cards with a
+/// status in the Arena-only Timeless or Historic formats count as well.
+pub fn is_on_arena(card: &Card) -> bool {
+    if card.games.iter().any(|game| game == "arena") || card.arena_id.is_some() {
+        return true;
+    }
+
+    ["timeless", "historic"].iter().any(|format| {
+        card.legalities
+            .get(format)
+            .and_then(Value::as_str)
+            .is_some_and(|status| status != "not_legal")
+    })
+}
+
+/// A card that has to be crafted with wildcards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WildcardCard {
+    pub name: String,
+    pub rarity: Option<String>,
+    pub missing: u32,
+}
+
+/// Wildcards needed to build a deck on Arena
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WildcardReport {
+    pub needed: Wildcards,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_hand: Option<Wildcards>,
+    /// Wildcards needed beyond the ones on hand
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shortfall: Option<Wildcards>,
+    pub cards: Vec<WildcardCard>,
+    /// Cards that cannot be crafted because they are not on Arena
+    pub not_on_arena: Vec<String>,
+    /// Cards of a rarity without wildcards (`special`, `bonus`), not in `needed`
+    #[serde(default)]
+    pub uncounted: Vec<WildcardCard>,
+}
+
+impl WildcardReport {
+    /// Whether the deck can be completed with the wildcards on hand
+    pub fn is_craftable(&self) -> bool {
+        self.not_on_arena.is_empty()
+            && self.uncounted.is_empty()
+            && self.shortfall.is_some_and(|s| s.total() == 0)
+    }
+
+    /// Sort key for comparing decks: rares and mythics first, then all wildcards
+    pub fn cost_key(&self) -> (usize, u32, u32) {
+        (
+            self.not_on_arena.len(),
+            self.needed.rare + self.needed.mythic,
+            self.needed.total(),
+        )
+    }
+}
+
+/// Work out the wildcards needed to build a deck from a collection.
+///
+/// Only the Arena cards of the collection count, paper copies can't be played on Arena.
+/// Rarities and Arena availability come from the deck's card details; cards
+/// without details are listed with an unknown rarity and need no wildcard.
+/// Cards of a rarity that has no wildcard are listed in `uncounted`.
+pub fn wildcard_cost(
+    deck_list: &DeckList,
+    collection: &Collection,
+    on_hand: Option<Wildcards>,
+) -> WildcardReport {
+    let missing = collection.arena().missing(deck_list);
+    let mut report = WildcardReport {
+        on_hand,
+        ..Default::default()
+    };
+
+    for card in missing.cards {
+        let details = deck_list
+            .main_deck
+            .iter()
+            .chain(&deck_list.sideboard)
+            .find(|entry| entry.name.eq_ignore_ascii_case(&card.name))
+            .and_then(|entry| entry.card_details.as_ref());
+
+        if details.is_some_and(|details| !is_on_arena(details)) {
+            report.not_on_arena.push(card.name);
+            continue;
+        }
+
+        // Arena only limits copies to four, extra copies are never crafted
+        let copies = card.missing.min(4u32.saturating_sub(card.owned));
+        let counted = match &card.rarity {
+            Some(rarity) => report.needed.add(rarity, copies),
+            None => true,
+        };
+        let card = WildcardCard {
+            name: card.name,
+            rarity: card.rarity,
+            missing: copies,
+        };
+        if counted {
+            report.cards.push(card);
+        } else {
+            report.uncounted.push(card);
+        }
+    }
+
+    report.shortfall = on_hand.map(|on_hand| report.needed.shortfall(&on_hand));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection::CollectionCard;
+    use crate::decks::test_utils::card;
+    use crate::decks::DeckCard;
+
+    fn entry(quantity: u32, name: &str, rarity: &str, on_arena: bool) -> DeckCard {
+        let mut card = card(name, Some("{R}"), 1.0, "Instant");
+        card.rarity = rarity.to_string();
+        card.games = if on_arena {
+            vec!["arena".to_string(), "paper".to_string()]
+        } else {
+            vec!["paper".to_string()]
+        };
+        card.legalities = serde_json::json!({"timeless": "not_legal"});
+
+        DeckCard {
+            quantity,
+            name: name.to_string(),
+            set_code: None,
+            collector_number: None,
+            card_details: Some(card),
+        }
+    }
+
+    #[test]
+    fn test_parse_arena_wildcards() {
+        let log = "[UnityCrossThreadLogger]<== StartHook(1) {\"InventoryInfo\":{\"Gems\":100,\"WildCardCommons\":20,\"WildCardUnCommons\":10,\"WildCardRares\":3,\"WildCardMythics\":1}}\n\
+            [UnityCrossThreadLogger]<== StartHook(2) {\"InventoryInfo\":{\"WildCardCommons\":21,\"WildCardUnCommons\":10,\"WildCardRares\":2,\"WildCardMythics\":1}}\n";
+
+        assert_eq!(
+            parse_arena_wildcards(log),
+            Some(Wildcards {
+                common: 21,
+                uncommon: 10,
+                rare: 2,
+                mythic: 1,
+            })
+        );
+        assert_eq!(parse_arena_wildcards("nothing"), None);
+    }
+
+    #[test]
+    fn test_wildcard_cost() {
+        let mut collection = Collection::default();
+        collection.merge(vec![CollectionCard {
+            name: "Sheoldred, the Apocalypse".to_string(),
+            set_code: None,
+            collector_number: None,
+            foil: false,
+            rarity: Some("mythic".to_string()),
+            arena_id: None,
+            arena: true,
+            quantity: 1,
+        }]);
+        // A paper playset does not help on Arena
+        collection.merge(vec![CollectionCard {
+            name: "Cut Down".to_string(),
+            set_code: Some("dmu".to_string()),
+            collector_number: None,
+            foil: false,
+            rarity: Some("uncommon".to_string()),
+            arena_id: None,
+            arena: false,
+            quantity: 4,
+        }]);
+
+        let deck = DeckList {
+            main_deck: vec![
+                entry(4, "Sheoldred, the Apocalypse", "mythic", true),
+                entry(4, "Cut Down", "uncommon", true),
+                entry(4, "Dark Ritual", "common", false),
+                entry(20, "Swamp", "common", true),
+            ],
+            sideboard: vec![
+                entry(2, "Duress", "common", true),
+                entry(1, "Lightning Bolt", "special", true),
+            ],
+        };
+
+        let on_hand = Wildcards {
+            common: 5,
+            uncommon: 2,
+            rare: 0,
+            mythic: 4,
+        };
+        let report = wildcard_cost(&deck, &collection, Some(on_hand));
+
+        assert_eq!(
+            report.needed,
+            Wildcards {
+                common: 2,
+                uncommon: 4,
+                rare: 0,
+                mythic: 3,
+            }
+        );
+        assert_eq!(report.not_on_arena, vec!["Dark Ritual".to_string()]);
+        assert_eq!(report.uncounted.len(), 1);
+        assert_eq!(report.uncounted[0].name, "Lightning Bolt");
+        assert!(!report
+            .cards
+            .iter()
+            .any(|card| card.name == "Lightning Bolt"));
+        assert_eq!(report.shortfall.unwrap().uncommon, 2);
+        assert!(!report.is_craftable());
+    }
+}
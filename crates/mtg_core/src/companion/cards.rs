@@ -0,0 +1,531 @@
+//! Arena card lookups by grpId.
+//!
+//! [`ArenaCardDatabase`] reads the `Raw_CardDatabase_*.mtga` SQLite file Arena downloads next
+//! to its install, which knows every card the client can show, digital-only and rebalanced
+//! ones included. [`ArenaCardResolver`] tries a persistent grpId map first, then the database,
+//! then Scryfall, and remembers whatever it found.
+
+use crate::paths;
+use crate::scryfall::{Card, ScryfallClient};
+use color_eyre::{eyre::eyre, Result};
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// What we know about an Arena card
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArenaCard {
+    pub grp_id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub type_line: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mana_cost: Option<String>,
+    #[serde(default)]
+    pub cmc: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oracle_text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toughness: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loyalty: Option<String>,
+    /// Color letters, e.g. `["W", "U"]`
+    #[serde(default)]
+    pub colors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub set: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collector_number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rarity: Option<String>,
+    /// Alchemy rebalanced version of another card
+    #[serde(default)]
+    pub rebalanced: bool,
+    #[serde(default)]
+    pub digital_only: bool,
+}
+
+impl From<&Card> for ArenaCard {
+    fn from(card: &Card) -> Self {
+        Self {
+            grp_id: card.arena_id.unwrap_or_default(),
+            name: card.name.clone(),
+            type_line: card.type_line.clone(),
+            mana_cost: card.mana_cost.clone(),
+            cmc: card.cmc,
+            oracle_text: card.oracle_text.clone(),
+            power: card.power.clone(),
+            toughness: card.toughness.clone(),
+            loyalty: card.loyalty.clone(),
+            colors: card.colors.clone().unwrap_or_default(),
+            set: Some(card.set.to_uppercase()),
+            collector_number: Some(card.collector_number.clone()),
+            rarity: Some(card.rarity.clone()),
+            rebalanced: card.name.starts_with("A-"),
+            digital_only: card.digital,
+        }
+    }
+}
+
+/// Arena's local card database
+pub struct ArenaCardDatabase {
+    connection: Connection,
+    path: PathBuf,
+    /// Table and text column of the English card texts, which moved between client versions
+    localizations: (String, String),
+    columns: HashSet<String>,
+}
+
+impl std::fmt::Debug for ArenaCardDatabase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArenaCardDatabase")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl ArenaCardDatabase {
+    /// Open a card database file, read-only
+    pub fn open(path: &Path) -> Result<Self> {
+        let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| eyre!("Failed to open card database '{}': {}", path.display(), e))?;
+
+        let tables: Vec<String> = connection
+            .prepare("SELECT name FROM sqlite_master WHERE type = 'table'")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        if !tables.iter().any(|table| table == "Cards") {
+            return Err(eyre!(
+                "'{}' is not an Arena card database (no Cards table)",
+                path.display()
+            ));
+        }
+        let localizations = if tables.iter().any(|table| table == "Localizations_enUS") {
+            ("Localizations_enUS".to_string(), "Loc".to_string())
+        } else if tables.iter().any(|table| table == "Localizations") {
+            ("Localizations".to_string(), "enUS".to_string())
+        } else {
+            return Err(eyre!(
+                "'{}' has no English card texts (no Localizations table)",
+                path.display()
+            ));
+        };
+        let columns = connection
+            .prepare("SELECT name FROM pragma_table_info('Cards')")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+
+        Ok(Self {
+            connection,
+            path: path.to_path_buf(),
+            localizations,
+            columns,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Look up a card, `None` when the database does not have it
+    pub fn card(&self, grp_id: u32) -> Result<Option<ArenaCard>> {
+        // Columns come and go between client versions, missing ones read as NULL
+        let column = |name: &str| {
+            if self.columns.contains(name) {
+                name.to_string()
+            } else {
+                format!("NULL AS {name}")
+            }
+        };
+        let query = format!(
+            "SELECT {} FROM Cards WHERE GrpId = ?1",
+            [
+                "TitleId",
+                "TypeTextId",
+                "SubtypeTextId",
+                "OldSchoolManaText",
+                "Power",
+                "Toughness",
+                "Colors",
+                "ExpansionCode",
+                "CollectorNumber",
+                "Rarity",
+                "IsRebalanced",
+                "IsDigitalOnly",
+            ]
+            .map(column)
+            .join(", ")
+        );
+
+        let row = self
+            .connection
+            .query_row(&query, [grp_id], |row| {
+                Ok((
+                    row.get::<_, Option<i64>>(0)?,
+                    row.get::<_, Option<i64>>(1)?,
+                    row.get::<_, Option<i64>>(2)?,
+                    text_value(row, 3)?,
+                    text_value(row, 4)?,
+                    text_value(row, 5)?,
+                    text_value(row, 6)?,
+                    text_value(row, 7)?,
+                    text_value(row, 8)?,
+                    row.get::<_, Option<i64>>(9)?,
+                    row.get::<_, Option<bool>>(10)?,
+                    row.get::<_, Option<bool>>(11)?,
+                ))
+            })
+            .optional()
+            .map_err(|e| {
+                eyre!(
+                    "Failed to read card {} from the card database: {}",
+                    grp_id,
+                    e
+                )
+            })?;
+        let Some((
+            title,
+            types,
+            subtypes,
+            mana,
+            power,
+            toughness,
+            colors,
+            set,
+            number,
+            rarity,
+            rebalanced,
+            digital,
+        )) = row
+        else {
+            return Ok(None);
+        };
+
+        let Some(name) = title.map(|id| self.text(id)).transpose()?.flatten() else {
+            return Ok(None);
+        };
+        let types = types.map(|id| self.text(id)).transpose()?.flatten();
+        let subtypes = subtypes.map(|id| self.text(id)).transpose()?.flatten();
+        let type_line = match (types, subtypes) {
+            (Some(types), Some(subtypes)) if !subtypes.is_empty() => {
+                format!("{types} — {subtypes}")
+            }
+            (Some(types), _) => types,
+            (None, _) => String::new(),
+        };
+        let (mana_cost, cmc) = match mana.as_deref().map(mana_cost) {
+            Some((cost, cmc)) if !cost.is_empty() => (Some(cost), cmc),
+            _ => (None, 0.0),
+        };
+
+        Ok(Some(ArenaCard {
+            grp_id,
+            name,
+            type_line,
+            mana_cost,
+            cmc,
+            oracle_text: None,
+            power: power.filter(|power| !power.is_empty()),
+            toughness: toughness.filter(|toughness| !toughness.is_empty()),
+            loyalty: None,
+            colors: colors.as_deref().map(color_letters).unwrap_or_default(),
+            set: set.filter(|set| !set.is_empty()),
+            collector_number: number.filter(|number| !number.is_empty()),
+            rarity: rarity.and_then(rarity_name).map(str::to_string),
+            rebalanced: rebalanced.unwrap_or(false),
+            digital_only: digital.unwrap_or(false),
+        }))
+    }
+
+    /// English text of a localization id, the unformatted variant when there are several
+    fn text(&self, id: i64) -> Result<Option<String>> {
+        let (table, column) = &self.localizations;
+        let query =
+            format!("SELECT {column} FROM {table} WHERE LocId = ?1 ORDER BY Formatted LIMIT 1");
+        let text: Option<String> = self
+            .connection
+            .query_row(&query, [id], |row| row.get(0))
+            .optional()?;
+        Ok(text.map(|text| strip_markup(&text)))
+    }
+}
+
+/// A column as text; Arena stores some numbers, like powers and single colors, as either
+fn text_value(row: &rusqlite::Row, index: usize) -> rusqlite::Result<Option<String>> {
+    use rusqlite::types::ValueRef;
+    Ok(match row.get_ref(index)? {
+        ValueRef::Text(text) => Some(String::from_utf8_lossy(text).into_owned()),
+        ValueRef::Integer(number) => Some(number.to_string()),
+        ValueRef::Real(number) => Some(number.to_string()),
+        ValueRef::Null | ValueRef::Blob(_) => None,
+    })
+}
+
+/// The newest `Raw_CardDatabase_*.mtga` file in a directory
+pub fn find_card_database(dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("Raw_CardDatabase") && name.ends_with(".mtga"))
+        })
+        .max_by_key(|path| {
+            std::fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .unwrap_or(std::time::SystemTime::UNIX_EPOCH)
+        })
+}
+
+/// Arena's mana text, e.g. `o2oWoW` or `oXo(R/G)`, as a Scryfall mana cost and its mana value
+fn mana_cost(text: &str) -> (String, f64) {
+    let mut cost = String::new();
+    let mut cmc = 0.0;
+    for symbol in text.split('o').filter(|symbol| !symbol.is_empty()) {
+        let symbol = symbol.trim_start_matches('(').trim_end_matches(')');
+        cost.push_str(&format!("{{{symbol}}}"));
+        cmc += match symbol.parse::<f64>() {
+            Ok(generic) => generic,
+            Err(_) if matches!(symbol, "X" | "Y" | "Z") => 0.0,
+            Err(_) => 1.0,
+        };
+    }
+    (cost, cmc)
+}
+
+/// Arena color ids (`1,3`) as letters
+fn color_letters(colors: &str) -> Vec<String> {
+    colors
+        .split(',')
+        .filter_map(|color| match color.trim() {
+            "1" => Some("W"),
+            "2" => Some("U"),
+            "3" => Some("B"),
+            "4" => Some("R"),
+            "5" => Some("G"),
+            _ => None,
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Arena rarity ids in Scryfall's spelling
+fn rarity_name(rarity: i64) -> Option<&'static str> {
+    match rarity {
+        1 | 2 => Some("common"),
+        3 => Some("uncommon"),
+        4 => Some("rare"),
+        5 => Some("mythic"),
+        _ => None,
+    }
+}
+
+/// Drop the `<i>`/`<nobr>` style tags Arena keeps in some texts
+fn strip_markup(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => plain.push(c),
+            _ => {}
+        }
+    }
+    plain
+}
+
+/// Default location of the grpId map, `MTG_ARENA_CARDS_FILE` overrides it
+pub fn default_card_map_path() -> PathBuf {
+    paths::data_file("MTG_ARENA_CARDS_FILE", "arena_cards.json")
+}
+
+/// Cards resolved so far by grpId, kept between runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArenaCardMap {
+    #[serde(default)]
+    pub cards: BTreeMap<u32, ArenaCard>,
+}
+
+impl ArenaCardMap {
+    /// Load the map, an empty one when the file does not exist yet
+    pub fn load(path: &Path) -> Result<Self> {
+        paths::read_json_or_default(path, "card map")
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        paths::write_json_atomic(path, self)
+    }
+}
+
+/// Resolves grpIds from the persistent map, Arena's card database and Scryfall, in that order
+#[derive(Debug, Default)]
+pub struct ArenaCardResolver {
+    map: ArenaCardMap,
+    map_path: Option<PathBuf>,
+    database: Option<ArenaCardDatabase>,
+    client: Option<ScryfallClient>,
+    /// Cards nobody knows, not asked for again this run
+    missing: HashSet<u32>,
+    dirty: bool,
+}
+
+impl ArenaCardResolver {
+    /// A resolver without any source, add them with the `with_*` methods
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep resolved cards in a map file, loading the cards resolved in earlier runs
+    pub fn with_map(mut self, path: &Path) -> Result<Self> {
+        self.map = ArenaCardMap::load(path)?;
+        self.map_path = Some(path.to_path_buf());
+        Ok(self)
+    }
+
+    pub fn with_database(mut self, database: ArenaCardDatabase) -> Self {
+        self.database = Some(database);
+        self
+    }
+
+    pub fn with_client(mut self, client: ScryfallClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    pub fn database(&self) -> Option<&ArenaCardDatabase> {
+        self.database.as_ref()
+    }
+
+    pub async fn resolve(&mut self, grp_id: u32) -> Result<ArenaCard> {
+        if let Some(card) = self.map.cards.get(&grp_id) {
+            return Ok(card.clone());
+        }
+        if self.missing.contains(&grp_id) {
+            return Err(eyre!("Unknown Arena card {}", grp_id));
+        }
+
+        let mut found = None;
+        if let Some(database) = &self.database {
+            found = database.card(grp_id)?;
+        }
+        if found.is_none() {
+            if let Some(client) = &self.client {
+                found = client
+                    .get_card_by_arena_id(grp_id)
+                    .await
+                    .ok()
+                    .map(|card| ArenaCard {
+                        grp_id,
+                        ..ArenaCard::from(&card)
+                    });
+            }
+        }
+
+        match found {
+            Some(card) => {
+                self.map.cards.insert(grp_id, card.clone());
+                self.dirty = true;
+                Ok(card)
+            }
+            None => {
+                self.missing.insert(grp_id);
+                Err(eyre!("Unknown Arena card {}", grp_id))
+            }
+        }
+    }
+
+    /// Write newly resolved cards to the map file
+    pub fn save(&mut self) -> Result<()> {
+        if let (true, Some(path)) = (self.dirty, &self.map_path) {
+            self.map.save(path)?;
+            self.dirty = false;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A card database with the tables and columns the resolver reads
+    fn test_database(dir: &Path) -> PathBuf {
+        let path = dir.join("Raw_CardDatabase_test.mtga");
+        let connection = Connection::open(&path).unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE Cards (GrpId INTEGER, TitleId INTEGER, TypeTextId INTEGER,
+                    SubtypeTextId INTEGER, OldSchoolManaText TEXT, Power TEXT, Toughness TEXT,
+                    Colors TEXT, ExpansionCode TEXT, CollectorNumber TEXT, Rarity INTEGER,
+                    IsRebalanced INTEGER, IsDigitalOnly INTEGER);
+                CREATE TABLE Localizations_enUS (LocId INTEGER, Formatted INTEGER, Loc TEXT);
+                INSERT INTO Cards VALUES
+                    (91002, 10, 11, 12, 'oR', 1, 1, 4, 'BLB', '138', 3, 0, 0),
+                    (87000, 20, 21, 0, 'o2o(W/U)oX', '', '', '1,2', 'Y24', '5', 4, 1, 1);
+                INSERT INTO Localizations_enUS VALUES
+                    (10, 1, 'Heartfire <i>Hero</i>'), (10, 0, 'Heartfire Hero'),
+                    (11, 0, 'Creature'), (12, 0, 'Mouse Soldier'),
+                    (20, 0, 'Arcane Study'), (21, 0, 'Sorcery');",
+            )
+            .unwrap();
+        path
+    }
+
+    #[test]
+    fn test_card_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = test_database(dir.path());
+        assert_eq!(find_card_database(dir.path()), Some(path.clone()));
+
+        let database = ArenaCardDatabase::open(&path).unwrap();
+        let hero = database.card(91002).unwrap().unwrap();
+        assert_eq!(hero.name, "Heartfire Hero");
+        assert_eq!(hero.type_line, "Creature — Mouse Soldier");
+        assert_eq!(hero.mana_cost.as_deref(), Some("{R}"));
+        assert_eq!(hero.cmc, 1.0);
+        assert_eq!(hero.colors, vec!["R"]);
+        assert_eq!(hero.rarity.as_deref(), Some("uncommon"));
+        assert_eq!(hero.power.as_deref(), Some("1"));
+
+        let study = database.card(87000).unwrap().unwrap();
+        assert_eq!(study.type_line, "Sorcery");
+        assert_eq!(study.mana_cost.as_deref(), Some("{2}{W/U}{X}"));
+        assert_eq!(study.cmc, 3.0);
+        assert_eq!(study.colors, vec!["W", "U"]);
+        assert!(study.rebalanced && study.digital_only);
+        assert_eq!(study.power, None);
+
+        assert_eq!(database.card(1).unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_resolver_keeps_cards() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = ArenaCardDatabase::open(&test_database(dir.path())).unwrap();
+        let map_path = dir.path().join("cards.json");
+
+        let mut resolver = ArenaCardResolver::new()
+            .with_map(&map_path)
+            .unwrap()
+            .with_database(database);
+        assert_eq!(
+            resolver.resolve(91002).await.unwrap().name,
+            "Heartfire Hero"
+        );
+        assert!(resolver.resolve(1).await.is_err());
+        resolver.save().unwrap();
+
+        // A later run without the database still knows the card
+        let mut resolver = ArenaCardResolver::new().with_map(&map_path).unwrap();
+        assert_eq!(
+            resolver.resolve(91002).await.unwrap().set.as_deref(),
+            Some("BLB")
+        );
+        assert!(resolver.resolve(87000).await.is_err());
+    }
+}
@@ -0,0 +1,428 @@
+use super::events::ArenaEvent;
+use super::history::format_from_event;
+use crate::paths;
+use chrono::NaiveDateTime;
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// One pick of a draft: the pack we were shown and what we took from it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DraftPickRecord {
+    pub pack: u32,
+    pub pick: u32,
+    /// Cards in the pack when it reached us, empty when the log did not show it
+    #[serde(default)]
+    pub pack_cards: Vec<u32>,
+    #[serde(default)]
+    pub picked: Vec<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<NaiveDateTime>,
+}
+
+/// A recorded Arena draft
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DraftRecord {
+    pub draft_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub set: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<NaiveDateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub completed: bool,
+    /// Picks in the order they were made
+    #[serde(default)]
+    pub picks: Vec<DraftPickRecord>,
+    /// Card names by grpId, filled in once they have been looked up
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub names: BTreeMap<u32, String>,
+}
+
+/// A card of a pick log, with its name when it is known
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PickLogCard {
+    pub grp_id: u32,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// One pick of a [`PickLog`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PickLogEntry {
+    pub pack: u32,
+    pub pick: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<NaiveDateTime>,
+    pub pack_cards: Vec<PickLogCard>,
+    pub picked: Vec<PickLogCard>,
+}
+
+/// Self-contained pick log of a draft, every card with its grpId and name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PickLog {
+    pub draft_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub set: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<NaiveDateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<NaiveDateTime>,
+    pub picks: Vec<PickLogEntry>,
+    pub pool: Vec<PickLogCard>,
+}
+
+impl DraftRecord {
+    fn new(draft_id: &str, timestamp: Option<NaiveDateTime>) -> Self {
+        let mut record = Self {
+            draft_id: draft_id.to_string(),
+            started_at: timestamp,
+            ..Default::default()
+        };
+        // Quick drafts are keyed by their event name, e.g. `QuickDraft_BLB_20241018`, while
+        // premier drafts only have an opaque id
+        if draft_id.contains('_') {
+            record.event_id = Some(draft_id.to_string());
+            record.format = Some(format_from_event(draft_id));
+            record.set = draft_id
+                .split('_')
+                .nth(1)
+                .filter(|set| !set.is_empty() && set.chars().all(|c| c.is_ascii_alphanumeric()))
+                .map(str::to_uppercase);
+        }
+        record
+    }
+
+    /// Drafts are told apart by id and start, quick drafts of one event share their id
+    fn same_draft(&self, other: &DraftRecord) -> bool {
+        self.draft_id == other.draft_id && self.started_at == other.started_at
+    }
+
+    /// Every card picked, in pick order
+    pub fn pool(&self) -> Vec<u32> {
+        self.picks
+            .iter()
+            .flat_map(|pick| pick.picked.iter().copied())
+            .collect()
+    }
+
+    /// Card name, `Card #grpId` when it has not been looked up
+    pub fn card_name(&self, grp_id: u32) -> String {
+        self.names
+            .get(&grp_id)
+            .cloned()
+            .unwrap_or_else(|| format!("Card #{grp_id}"))
+    }
+
+    /// Every grpId shown in the draft, picked or not
+    pub fn grp_ids(&self) -> Vec<u32> {
+        let mut ids: Vec<u32> = self
+            .picks
+            .iter()
+            .flat_map(|pick| pick.pack_cards.iter().chain(&pick.picked).copied())
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    /// The pool as a deck list, one `quantity name` line per card, most copies first
+    pub fn deck_list(&self) -> String {
+        let mut counts: BTreeMap<String, u32> = BTreeMap::new();
+        for grp_id in self.pool() {
+            *counts.entry(self.card_name(grp_id)).or_default() += 1;
+        }
+        let mut cards: Vec<(String, u32)> = counts.into_iter().collect();
+        cards.sort_by_key(|(_, quantity)| std::cmp::Reverse(*quantity));
+
+        let mut list = String::from("Deck\n");
+        for (name, quantity) in cards {
+            list.push_str(&format!("{quantity} {name}\n"));
+        }
+        list
+    }
+
+    pub fn pick_log(&self) -> PickLog {
+        let card = |grp_id: &u32| PickLogCard {
+            grp_id: *grp_id,
+            name: self.names.get(grp_id).cloned(),
+        };
+        PickLog {
+            draft_id: self.draft_id.clone(),
+            event_id: self.event_id.clone(),
+            format: self.format.clone(),
+            set: self.set.clone(),
+            started_at: self.started_at,
+            completed_at: self.completed_at,
+            picks: self
+                .picks
+                .iter()
+                .map(|pick| PickLogEntry {
+                    pack: pick.pack,
+                    pick: pick.pick,
+                    timestamp: pick.timestamp,
+                    pack_cards: pick.pack_cards.iter().map(card).collect(),
+                    picked: pick.picked.iter().map(card).collect(),
+                })
+                .collect(),
+            pool: self.pool().iter().map(card).collect(),
+        }
+    }
+}
+
+/// Builds [`DraftRecord`]s from an event stream.
+///
+/// The record is returned after every pick and once more when the draft completes, so the
+/// stored draft is always up to date. Store records with [`DraftHistory::upsert`].
+#[derive(Debug, Default)]
+pub struct DraftRecorder {
+    current: Option<DraftRecord>,
+    /// Packs seen but not picked from yet, by pack and pick number
+    packs: BTreeMap<(u32, u32), Vec<u32>>,
+}
+
+impl DraftRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The draft being recorded, if any
+    pub fn current(&self) -> Option<&DraftRecord> {
+        self.current.as_ref()
+    }
+
+    pub fn push(&mut self, event: &ArenaEvent) -> Option<DraftRecord> {
+        match event {
+            ArenaEvent::DraftPack {
+                draft_id,
+                pack,
+                pick,
+                cards,
+                timestamp,
+            } => {
+                self.draft(draft_id.as_deref(), *pack, *pick, *timestamp);
+                self.packs.insert((*pack, *pick), cards.clone());
+                None
+            }
+            ArenaEvent::DraftPick {
+                draft_id,
+                pack,
+                pick,
+                cards,
+                timestamp,
+            } => {
+                self.draft(draft_id.as_deref(), *pack, *pick, *timestamp)?;
+                let pack_cards = self.packs.remove(&(*pack, *pick)).unwrap_or_default();
+                let record = self.current.as_mut()?;
+                let entry = DraftPickRecord {
+                    pack: *pack,
+                    pick: *pick,
+                    pack_cards,
+                    picked: cards.clone(),
+                    timestamp: *timestamp,
+                };
+                // A repeated pick, e.g. after a reconnect, replaces the first one
+                match record
+                    .picks
+                    .iter_mut()
+                    .find(|existing| existing.pack == *pack && existing.pick == *pick)
+                {
+                    Some(existing) => *existing = entry,
+                    None => record.picks.push(entry),
+                }
+                Some(record.clone())
+            }
+            ArenaEvent::DraftCompleted {
+                draft_id,
+                timestamp,
+            } => {
+                let record = self.current.as_mut()?;
+                if draft_id.is_some() && draft_id.as_deref() != Some(record.draft_id.as_str()) {
+                    return None;
+                }
+                if record.completed {
+                    return None;
+                }
+                record.completed = true;
+                record.completed_at = *timestamp;
+                self.packs.clear();
+                Some(record.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// The draft an event belongs to, starting a new one when needed
+    fn draft(
+        &mut self,
+        draft_id: Option<&str>,
+        pack: u32,
+        pick: u32,
+        timestamp: Option<NaiveDateTime>,
+    ) -> Option<&mut DraftRecord> {
+        let first_pick = pack <= 1 && pick <= 1;
+        let start = match (&self.current, draft_id) {
+            (None, Some(_)) => true,
+            (None, None) => false,
+            // Quick drafts of the same event share their id, a first pick starts a new draft
+            (Some(current), Some(id)) => {
+                current.draft_id != id || (first_pick && !current.picks.is_empty())
+            }
+            (Some(current), None) => first_pick && !current.picks.is_empty(),
+        };
+        if start {
+            self.packs.clear();
+            self.current = Some(DraftRecord::new(draft_id?, timestamp));
+        }
+        let record = self.current.as_mut()?;
+        if record.started_at.is_none() {
+            record.started_at = timestamp;
+        }
+        Some(record)
+    }
+}
+
+/// Record every draft of an event stream, e.g. a whole log file
+pub fn record_drafts(events: &[ArenaEvent]) -> Vec<DraftRecord> {
+    let mut recorder = DraftRecorder::new();
+    let mut records: Vec<DraftRecord> = Vec::new();
+    for record in events.iter().filter_map(|event| recorder.push(event)) {
+        match records.iter_mut().find(|r| r.same_draft(&record)) {
+            Some(existing) => *existing = record,
+            None => records.push(record),
+        }
+    }
+    records
+}
+
+/// Default location of the draft history file, `MTG_DRAFTS_FILE` overrides it
+pub fn default_drafts_path() -> PathBuf {
+    paths::data_file("MTG_DRAFTS_FILE", "drafts.json")
+}
+
+/// Recorded drafts, oldest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DraftHistory {
+    #[serde(default)]
+    pub drafts: Vec<DraftRecord>,
+}
+
+impl DraftHistory {
+    /// Load the history, an empty one when the file does not exist yet
+    pub fn load(path: &Path) -> Result<Self> {
+        paths::read_json_or_default(path, "draft history")
+    }
+
+    pub fn save(&mut self, path: &Path) -> Result<()> {
+        self.drafts.sort_by(|a, b| {
+            a.started_at
+                .cmp(&b.started_at)
+                .then(a.draft_id.cmp(&b.draft_id))
+        });
+        paths::write_json_atomic(path, self)
+    }
+
+    /// Add a draft, replacing an earlier record of it. Names looked up before are kept.
+    /// Returns whether the draft is new.
+    pub fn upsert(&mut self, mut record: DraftRecord) -> bool {
+        match self
+            .drafts
+            .iter_mut()
+            .find(|existing| existing.same_draft(&record))
+        {
+            Some(existing) => {
+                for (grp_id, name) in &existing.names {
+                    record.names.entry(*grp_id).or_insert_with(|| name.clone());
+                }
+                *existing = record;
+                false
+            }
+            None => {
+                self.drafts.push(record);
+                true
+            }
+        }
+    }
+
+    /// The draft whose id starts with `id`, the latest one when no id is given
+    pub fn find(&self, id: Option<&str>) -> Option<&DraftRecord> {
+        self.drafts
+            .iter()
+            .rev()
+            .find(|draft| id.is_none_or(|id| draft.draft_id.starts_with(id)))
+    }
+
+    pub fn find_mut(&mut self, id: Option<&str>) -> Option<&mut DraftRecord> {
+        self.drafts
+            .iter_mut()
+            .rev()
+            .find(|draft| id.is_none_or(|id| draft.draft_id.starts_with(id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::companion::parse_log;
+
+    #[test]
+    fn test_record_drafts_from_fixture() {
+        let drafts = record_drafts(&parse_log(include_str!("fixtures/draft.log")));
+        assert_eq!(drafts.len(), 2);
+
+        let quick = &drafts[0];
+        assert_eq!(quick.draft_id, "QuickDraft_BLB_20241018");
+        assert_eq!(quick.format.as_deref(), Some("Quick Draft"));
+        assert_eq!(quick.set.as_deref(), Some("BLB"));
+        assert!(quick.completed);
+        assert_eq!(quick.picks.len(), 1);
+        assert_eq!(quick.picks[0].pack_cards, vec![91001, 91002, 91003]);
+        assert_eq!(quick.pool(), vec![91002]);
+        assert_eq!(
+            quick.started_at.map(|t| t.to_string()).as_deref(),
+            Some("2024-10-18 20:00:05")
+        );
+
+        let premier = &drafts[1];
+        assert_eq!(premier.draft_id, "draft-42");
+        assert_eq!(premier.event_id, None);
+        assert_eq!(premier.pool(), vec![92003]);
+        assert_eq!(premier.picks[0].pack_cards.len(), 3);
+    }
+
+    #[test]
+    fn test_exports_and_upsert_keep_names() {
+        let mut draft = record_drafts(&parse_log(include_str!("fixtures/draft.log")))
+            .into_iter()
+            .next()
+            .unwrap();
+        draft.names.insert(91002, "Heartfire Hero".to_string());
+        assert_eq!(draft.deck_list(), "Deck\n1 Heartfire Hero\n");
+
+        let log = draft.pick_log();
+        assert_eq!(log.picks[0].pack_cards.len(), 3);
+        assert_eq!(log.pool[0].name.as_deref(), Some("Heartfire Hero"));
+        assert_eq!(log.picks[0].pack_cards[0].name, None);
+
+        let mut history = DraftHistory::default();
+        assert!(history.upsert(draft.clone()));
+        draft.names.clear();
+        assert!(!history.upsert(draft));
+        assert_eq!(history.drafts.len(), 1);
+        assert_eq!(history.drafts[0].card_name(91002), "Heartfire Hero");
+        assert_eq!(
+            history.find(Some("Quick")).unwrap().set.as_deref(),
+            Some("BLB")
+        );
+    }
+}
@@ -0,0 +1,343 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Seat of a player in a match, as assigned by the game rules engine (GRE)
+pub type SeatId = u32;
+
+/// A typed event read from an MTG Arena log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ArenaEvent {
+    /// The client logged in
+    Authenticated {
+        client_id: String,
+        screen_name: String,
+    },
+    /// `STATE CHANGED` lines of the client state machine
+    StateChanged {
+        from: String,
+        to: String,
+    },
+    /// The client moved between scenes (business event)
+    SceneChanged {
+        from: String,
+        to: String,
+    },
+
+    /// Matchmaking found an opponent
+    MatchCreated {
+        match_id: String,
+        event_id: Option<String>,
+        opponent_name: Option<String>,
+        /// Ranking class and tier, e.g. `Gold 2` or `Mythic`
+        opponent_rank: Option<String>,
+    },
+    /// The match room reached the playing state
+    MatchStarted {
+        match_id: String,
+        event_id: Option<String>,
+        players: Vec<MatchPlayer>,
+        timestamp: Option<NaiveDateTime>,
+    },
+    /// The match room closed with its final result
+    MatchCompleted {
+        match_id: String,
+        winning_team: Option<u32>,
+        reason: Option<String>,
+        games: Vec<GameResult>,
+        timestamp: Option<NaiveDateTime>,
+    },
+    /// Summary the client reports once a match is over (business event)
+    MatchReport(MatchReport),
+
+    /// A deck was sent to the game rules engine for the next game
+    DeckSubmitted {
+        seat: Option<SeatId>,
+        main: Vec<DeckEntry>,
+        sideboard: Vec<DeckEntry>,
+    },
+    /// A deck was chosen for an event or saved in the deck builder
+    DeckSelected(ArenaDeck),
+
+    GameStarted {
+        game_number: u32,
+        local_seat: Option<SeatId>,
+    },
+    DieRoll {
+        rolls: Vec<DieRoll>,
+    },
+    Mulligan {
+        seat: SeatId,
+        count: u32,
+    },
+    TurnStarted {
+        turn: u32,
+        active_seat: SeatId,
+    },
+    PhaseChanged {
+        turn: u32,
+        active_seat: SeatId,
+        phase: String,
+        step: Option<String>,
+    },
+    LifeChanged {
+        seat: SeatId,
+        from: i32,
+        to: i32,
+    },
+    ZoneTransfer(ZoneTransfer),
+    Damage {
+        source: CardRef,
+        target: DamageTarget,
+        amount: u32,
+    },
+    Attacking {
+        card: CardRef,
+    },
+    Blocking {
+        card: CardRef,
+    },
+    Tapped {
+        card: CardRef,
+        tapped: bool,
+    },
+    Counters {
+        card: CardRef,
+        counter: String,
+        change: i32,
+    },
+    Timer(TimerUpdate),
+    GameOver {
+        game_number: u32,
+        results: Vec<GameResult>,
+    },
+
+    DraftPack {
+        draft_id: Option<String>,
+        pack: u32,
+        pick: u32,
+        cards: Vec<u32>,
+        timestamp: Option<NaiveDateTime>,
+    },
+    DraftPick {
+        draft_id: Option<String>,
+        pack: u32,
+        pick: u32,
+        cards: Vec<u32>,
+        timestamp: Option<NaiveDateTime>,
+    },
+    DraftCompleted {
+        draft_id: Option<String>,
+        timestamp: Option<NaiveDateTime>,
+    },
+
+    RankUpdated {
+        constructed: Option<Rank>,
+        limited: Option<Rank>,
+    },
+    Quests {
+        quests: Vec<Quest>,
+    },
+}
+
+impl ArenaEvent {
+    /// Short category used to filter events (match, game, turns, life, cards, combat, ...)
+    pub fn category(&self) -> &'static str {
+        match self {
+            ArenaEvent::Authenticated { .. } => "auth",
+            ArenaEvent::StateChanged { .. } | ArenaEvent::SceneChanged { .. } => "client",
+            ArenaEvent::MatchCreated { .. }
+            | ArenaEvent::MatchStarted { .. }
+            | ArenaEvent::MatchCompleted { .. }
+            | ArenaEvent::MatchReport(_) => "match",
+            ArenaEvent::DeckSubmitted { .. } | ArenaEvent::DeckSelected(_) => "deck",
+            ArenaEvent::GameStarted { .. }
+            | ArenaEvent::DieRoll { .. }
+            | ArenaEvent::GameOver { .. } => "game",
+            ArenaEvent::Mulligan { .. } => "mulligans",
+            ArenaEvent::TurnStarted { .. } | ArenaEvent::PhaseChanged { .. } => "turns",
+            ArenaEvent::LifeChanged { .. } => "life",
+            ArenaEvent::ZoneTransfer(_) => "cards",
+            ArenaEvent::Damage { .. }
+            | ArenaEvent::Attacking { .. }
+            | ArenaEvent::Blocking { .. } => "combat",
+            ArenaEvent::Tapped { .. } | ArenaEvent::Counters { .. } => "actions",
+            ArenaEvent::Timer(_) => "timers",
+            ArenaEvent::DraftPack { .. }
+            | ArenaEvent::DraftPick { .. }
+            | ArenaEvent::DraftCompleted { .. } => "draft",
+            ArenaEvent::RankUpdated { .. } => "rank",
+            ArenaEvent::Quests { .. } => "quests",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchPlayer {
+    pub seat: SeatId,
+    pub team: u32,
+    pub name: String,
+    pub user_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameResult {
+    /// `game` or `match`
+    pub scope: String,
+    pub winning_team: Option<u32>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MatchReport {
+    pub match_id: String,
+    pub event_id: Option<String>,
+    pub seat: Option<SeatId>,
+    pub team: Option<u32>,
+    pub winning_team: Option<u32>,
+    pub reason: Option<String>,
+    pub turns: Option<u32>,
+    pub seconds: Option<u32>,
+    pub starting_team: Option<u32>,
+    pub mulligans: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeckEntry {
+    pub grp_id: u32,
+    pub quantity: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArenaDeck {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub format: Option<String>,
+    pub event: Option<String>,
+    pub main: Vec<DeckEntry>,
+    pub sideboard: Vec<DeckEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DieRoll {
+    pub seat: SeatId,
+    pub value: u32,
+}
+
+/// A game object, identified by its instance id and, when visible, its card (grpId)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CardRef {
+    pub instance_id: u32,
+    pub grp_id: Option<u32>,
+    pub owner: Option<SeatId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DamageTarget {
+    Player(SeatId),
+    Card(CardRef),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Zone {
+    Library,
+    Hand,
+    Battlefield,
+    Graveyard,
+    Exile,
+    Stack,
+    Command,
+    Sideboard,
+    Revealed,
+    Limbo,
+    Pending,
+    Unknown,
+}
+
+impl Zone {
+    /// Parse a GRE zone type such as `ZoneType_Battlefield`
+    pub fn from_gre(zone_type: &str) -> Self {
+        match zone_type.trim_start_matches("ZoneType_") {
+            "Library" => Zone::Library,
+            "Hand" => Zone::Hand,
+            "Battlefield" => Zone::Battlefield,
+            "Graveyard" => Zone::Graveyard,
+            "Exile" => Zone::Exile,
+            "Stack" => Zone::Stack,
+            "Command" => Zone::Command,
+            "Sideboard" => Zone::Sideboard,
+            "Revealed" => Zone::Revealed,
+            "Limbo" => Zone::Limbo,
+            "Pending" => Zone::Pending,
+            _ => Zone::Unknown,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Zone::Library => "Library",
+            Zone::Hand => "Hand",
+            Zone::Battlefield => "Battlefield",
+            Zone::Graveyard => "Graveyard",
+            Zone::Exile => "Exile",
+            Zone::Stack => "Stack",
+            Zone::Command => "Command",
+            Zone::Sideboard => "Sideboard",
+            Zone::Revealed => "Revealed",
+            Zone::Limbo => "Limbo",
+            Zone::Pending => "Pending",
+            Zone::Unknown => "Unknown",
+        }
+    }
+}
+
+/// A card moving between zones (`AnnotationType_ZoneTransfer`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ZoneTransfer {
+    pub card: CardRef,
+    pub from: Zone,
+    pub to: Zone,
+    /// GRE category such as `Draw`, `PlayLand`, `CastSpell`, `Resolve` or `Destroy`
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimerUpdate {
+    pub seat: SeatId,
+    pub timers: Vec<Timer>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Timer {
+    /// Timer type without its prefix, e.g. `ActivePlayer` or `Inactivity`
+    pub kind: String,
+    pub running: bool,
+    pub duration_sec: u32,
+    pub elapsed_sec: u32,
+    pub warning_sec: Option<u32>,
+}
+
+impl Timer {
+    pub fn remaining_sec(&self) -> u32 {
+        self.duration_sec.saturating_sub(self.elapsed_sec)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rank {
+    pub class: String,
+    pub level: Option<u32>,
+    pub step: Option<u32>,
+    pub won: u32,
+    pub lost: u32,
+    pub drawn: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Quest {
+    pub name: String,
+    pub progress: u32,
+    pub goal: u32,
+}
@@ -0,0 +1,636 @@
+use super::events::{ArenaDeck, ArenaEvent, MatchPlayer, SeatId};
+use super::opponent::{OpponentTracker, SeenCard};
+use crate::paths;
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// One game of a recorded match, from the local player's point of view
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub game_number: u32,
+    /// Whether the local player took the first turn
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_play: Option<bool>,
+    #[serde(default)]
+    pub mulligans: u32,
+    #[serde(default)]
+    pub opponent_mulligans: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub won: Option<bool>,
+    #[serde(default)]
+    pub turns: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// A completed Arena match
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MatchRecord {
+    pub match_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deck_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deck_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opponent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opponent_rank: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<NaiveDateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_sec: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub won: Option<bool>,
+    #[serde(default)]
+    pub games: Vec<GameRecord>,
+    /// Cards the opponent revealed over the match
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub opponent_cards: Vec<SeenCard>,
+    /// Archetype guessed from the opponent's cards, when tournament decks were available
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opponent_archetype: Option<String>,
+}
+
+impl MatchRecord {
+    /// Whether the local player was on the play in game one
+    pub fn on_play(&self) -> Option<bool> {
+        self.games.first().and_then(|game| game.on_play)
+    }
+
+    pub fn turns(&self) -> u32 {
+        self.games.iter().map(|game| game.turns).sum()
+    }
+
+    pub fn mulligans(&self) -> u32 {
+        self.games.iter().map(|game| game.mulligans).sum()
+    }
+
+    /// Game wins and losses, e.g. `2-1`
+    pub fn score(&self) -> String {
+        let wins = self.games.iter().filter(|g| g.won == Some(true)).count();
+        let losses = self.games.iter().filter(|g| g.won == Some(false)).count();
+        format!("{wins}-{losses}")
+    }
+
+    /// ISO week the match started in, e.g. `2024-W42`
+    pub fn week(&self) -> Option<String> {
+        let week = self.started_at?.date().iso_week();
+        Some(format!("{}-W{:02}", week.year(), week.week()))
+    }
+}
+
+/// Guess the format of an event from its id, e.g. `Traditional_Ladder` or `QuickDraft_BLB_20241018`
+pub fn format_from_event(event_id: &str) -> String {
+    let lower = event_id.to_lowercase();
+    let known = [
+        ("quickdraft", "Quick Draft"),
+        ("premierdraft", "Premier Draft"),
+        ("tradsealed", "Traditional Sealed"),
+        ("traddraft", "Traditional Draft"),
+        ("sealed", "Sealed"),
+        ("draft", "Draft"),
+        ("historic", "Historic"),
+        ("explorer", "Explorer"),
+        ("timeless", "Timeless"),
+        ("alchemy", "Alchemy"),
+        ("brawl", "Brawl"),
+        ("traditional_ladder", "Traditional Standard"),
+        ("ladder", "Standard"),
+        ("play", "Play"),
+    ];
+    known
+        .iter()
+        .find(|(key, _)| lower.contains(key))
+        .map(|(_, format)| format.to_string())
+        .unwrap_or_else(|| event_id.to_string())
+}
+
+/// Builds [`MatchRecord`]s from an event stream.
+///
+/// A record is returned when the match completes and again, with the duration and turn count
+/// filled in, when the client's match report follows. Store records with
+/// [`MatchHistory::upsert`] so the second one replaces the first.
+#[derive(Debug, Default)]
+pub struct MatchRecorder {
+    client_id: Option<String>,
+    deck: Option<ArenaDeck>,
+    local_seat: Option<SeatId>,
+    local_team: Option<u32>,
+    players: Vec<MatchPlayer>,
+    current: Option<MatchRecord>,
+    game: Option<GameRecord>,
+    last: Option<MatchRecord>,
+    opponent_cards: OpponentTracker,
+}
+
+impl MatchRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, event: &ArenaEvent) -> Option<MatchRecord> {
+        self.opponent_cards.push(event);
+        match event {
+            ArenaEvent::Authenticated { client_id, .. } => {
+                self.client_id = Some(client_id.clone());
+            }
+            ArenaEvent::DeckSelected(deck) => self.deck = Some(deck.clone()),
+            ArenaEvent::MatchCreated {
+                match_id,
+                opponent_name,
+                opponent_rank,
+                ..
+            } => {
+                self.current = Some(MatchRecord {
+                    match_id: match_id.clone(),
+                    opponent: opponent_name.clone(),
+                    opponent_rank: opponent_rank.clone(),
+                    ..Default::default()
+                });
+            }
+            ArenaEvent::MatchStarted {
+                match_id,
+                event_id,
+                players,
+                timestamp,
+            } => {
+                let mut record = match self.current.take() {
+                    Some(record) if record.match_id == *match_id => record,
+                    _ => MatchRecord {
+                        match_id: match_id.clone(),
+                        ..Default::default()
+                    },
+                };
+                record.event_id = event_id.clone();
+                record.started_at = *timestamp;
+                let deck = self.deck.as_ref();
+                record.deck_id = deck.and_then(|deck| deck.id.clone());
+                record.deck_name = deck.and_then(|deck| deck.name.clone());
+                record.format = deck
+                    .and_then(|deck| deck.format.clone())
+                    .or_else(|| event_id.as_deref().map(format_from_event));
+                self.current = Some(record);
+                self.game = None;
+
+                // Without a login in the log, the seat comes from the game rules engine later
+                self.players = players.clone();
+                self.local_seat = players
+                    .iter()
+                    .find(|player| player.user_id.is_some() && player.user_id == self.client_id)
+                    .map(|player| player.seat);
+                self.resolve_local_player();
+            }
+            ArenaEvent::GameStarted {
+                game_number,
+                local_seat,
+            } => {
+                self.finish_game();
+                if self.local_seat.is_none() && local_seat.is_some() {
+                    self.local_seat = *local_seat;
+                    self.resolve_local_player();
+                }
+                self.game = Some(GameRecord {
+                    game_number: *game_number,
+                    ..Default::default()
+                });
+            }
+            ArenaEvent::TurnStarted { turn, active_seat } => {
+                if let Some(game) = self.game.as_mut() {
+                    if game.on_play.is_none() && *turn == 1 {
+                        game.on_play = self.local_seat.map(|seat| seat == *active_seat);
+                    }
+                    game.turns = game.turns.max(*turn);
+                }
+            }
+            ArenaEvent::Mulligan { seat, count } => {
+                if let Some(game) = self.game.as_mut() {
+                    if Some(*seat) == self.local_seat {
+                        game.mulligans = game.mulligans.max(*count);
+                    } else {
+                        game.opponent_mulligans = game.opponent_mulligans.max(*count);
+                    }
+                }
+            }
+            ArenaEvent::GameOver { results, .. } => {
+                if let (Some(game), Some(result)) = (self.game.as_mut(), results.first()) {
+                    game.won = match (result.winning_team, self.local_team) {
+                        (Some(winner), Some(team)) => Some(winner == team),
+                        _ => None,
+                    };
+                    game.reason = result.reason.clone();
+                }
+                self.finish_game();
+            }
+            ArenaEvent::MatchCompleted {
+                match_id,
+                winning_team,
+                timestamp,
+                ..
+            } => {
+                self.finish_game();
+                let mut record = self.current.take()?;
+                if record.match_id != *match_id {
+                    return None;
+                }
+                record.won = match (winning_team, self.local_team) {
+                    (Some(winner), Some(team)) => Some(*winner == team),
+                    _ => None,
+                };
+                if let (Some(start), Some(end)) = (record.started_at, timestamp) {
+                    let seconds = (*end - start).num_seconds();
+                    if seconds > 0 {
+                        record.duration_sec = Some(seconds as u32);
+                    }
+                }
+                record.opponent_cards = self.opponent_cards.cards();
+                self.last = Some(record.clone());
+                return Some(record);
+            }
+            ArenaEvent::MatchReport(report) => {
+                let record = self.last.as_mut()?;
+                if record.match_id != report.match_id {
+                    return None;
+                }
+                record.duration_sec = report.seconds.or(record.duration_sec);
+                if record.won.is_none() {
+                    record.won = match (report.winning_team, report.team) {
+                        (Some(winner), Some(team)) => Some(winner == team),
+                        _ => None,
+                    };
+                }
+                if let (Some(turns), Some(game)) = (report.turns, record.games.last_mut()) {
+                    game.turns = game.turns.max(turns);
+                }
+                return self.last.clone();
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Set the local team and the opponent once the local seat is known
+    fn resolve_local_player(&mut self) {
+        let Some(seat) = self.local_seat else {
+            self.local_team = None;
+            return;
+        };
+        self.local_team = self
+            .players
+            .iter()
+            .find(|player| player.seat == seat)
+            .map(|player| player.team);
+        if let (Some(record), Some(opponent)) = (
+            self.current.as_mut(),
+            self.players.iter().find(|player| player.seat != seat),
+        ) {
+            record.opponent = Some(opponent.name.clone());
+        }
+    }
+
+    fn finish_game(&mut self) {
+        if let (Some(record), Some(game)) = (self.current.as_mut(), self.game.take()) {
+            record.games.push(game);
+        }
+    }
+}
+
+/// Record every completed match of a parsed log
+pub fn record_matches(events: &[ArenaEvent]) -> Vec<MatchRecord> {
+    let mut recorder = MatchRecorder::new();
+    let mut records: Vec<MatchRecord> = Vec::new();
+    for record in events.iter().filter_map(|event| recorder.push(event)) {
+        match records.iter_mut().find(|r| r.match_id == record.match_id) {
+            Some(existing) => *existing = record,
+            None => records.push(record),
+        }
+    }
+    records
+}
+
+/// Default location of the match history file, `MTG_HISTORY_FILE` overrides it
+pub fn default_history_path() -> PathBuf {
+    paths::data_file("MTG_HISTORY_FILE", "matches.json")
+}
+
+/// Completed matches, oldest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MatchHistory {
+    #[serde(default)]
+    pub matches: Vec<MatchRecord>,
+}
+
+impl MatchHistory {
+    /// Load the history, an empty one when the file does not exist yet
+    pub fn load(path: &Path) -> Result<Self> {
+        paths::read_json_or_default(path, "match history")
+    }
+
+    pub fn load_default() -> Result<Self> {
+        Self::load(&default_history_path())
+    }
+
+    pub fn save(&mut self, path: &Path) -> Result<()> {
+        self.matches.sort_by(|a, b| {
+            a.started_at
+                .cmp(&b.started_at)
+                .then(a.match_id.cmp(&b.match_id))
+        });
+        paths::write_json_atomic(path, self)
+    }
+
+    pub fn save_default(&mut self) -> Result<()> {
+        self.save(&default_history_path())
+    }
+
+    /// Add a match, replacing an earlier record of it. The opponent's archetype and card
+    /// names looked up before are kept. Returns whether the match is new.
+    pub fn upsert(&mut self, mut record: MatchRecord) -> bool {
+        match self
+            .matches
+            .iter_mut()
+            .find(|existing| existing.match_id == record.match_id)
+        {
+            Some(existing) => {
+                if record.opponent_archetype.is_none() {
+                    record.opponent_archetype = existing.opponent_archetype.take();
+                }
+                for card in record
+                    .opponent_cards
+                    .iter_mut()
+                    .filter(|c| c.name.is_none())
+                {
+                    card.name = existing
+                        .opponent_cards
+                        .iter()
+                        .find(|seen| seen.grp_id == card.grp_id)
+                        .and_then(|seen| seen.name.clone());
+                }
+                *existing = record;
+                false
+            }
+            None => {
+                self.matches.push(record);
+                true
+            }
+        }
+    }
+}
+
+/// Which matches to report on; text filters are case-insensitive substrings
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    pub deck: Option<String>,
+    pub format: Option<String>,
+    pub event: Option<String>,
+    pub opponent: Option<String>,
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+}
+
+impl HistoryFilter {
+    pub fn matches(&self, record: &MatchRecord) -> bool {
+        fn contains(value: &Option<String>, needle: &Option<String>) -> bool {
+            match needle {
+                Some(needle) => value
+                    .as_deref()
+                    .is_some_and(|value| value.to_lowercase().contains(&needle.to_lowercase())),
+                None => true,
+            }
+        }
+
+        let date = record.started_at.map(|started| started.date());
+        contains(&record.deck_name, &self.deck)
+            && contains(&record.format, &self.format)
+            && contains(&record.event_id, &self.event)
+            && contains(&record.opponent, &self.opponent)
+            && self
+                .since
+                .is_none_or(|since| date.is_some_and(|date| date >= since))
+            && self
+                .until
+                .is_none_or(|until| date.is_some_and(|date| date <= until))
+    }
+}
+
+/// Wins and losses of a group of matches or games
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct WinRate {
+    pub wins: u32,
+    pub losses: u32,
+    /// Matches or games without a known result
+    pub unknown: u32,
+}
+
+impl WinRate {
+    fn add(&mut self, won: Option<bool>) {
+        match won {
+            Some(true) => self.wins += 1,
+            Some(false) => self.losses += 1,
+            None => self.unknown += 1,
+        }
+    }
+
+    pub fn played(&self) -> u32 {
+        self.wins + self.losses
+    }
+
+    /// Win percentage of the matches with a result
+    pub fn percent(&self) -> Option<f64> {
+        (self.played() > 0).then(|| self.wins as f64 * 100.0 / self.played() as f64)
+    }
+}
+
+/// How to group a win-rate breakdown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakdown {
+    Deck,
+    Format,
+    /// Per game, on the play or on the draw
+    PlayDraw,
+    Week,
+}
+
+impl std::str::FromStr for Breakdown {
+    type Err = color_eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "deck" => Ok(Breakdown::Deck),
+            "format" => Ok(Breakdown::Format),
+            "play-draw" | "play_draw" | "playdraw" => Ok(Breakdown::PlayDraw),
+            "week" => Ok(Breakdown::Week),
+            other => Err(eyre!(
+                "Unknown breakdown '{}'. Available: deck, format, play-draw, week",
+                other
+            )),
+        }
+    }
+}
+
+/// Overall match win rate
+pub fn win_rate<'a>(records: impl IntoIterator<Item = &'a MatchRecord>) -> WinRate {
+    let mut rate = WinRate::default();
+    for record in records {
+        rate.add(record.won);
+    }
+    rate
+}
+
+/// Win rates grouped by deck, format, week (per match) or play/draw (per game)
+pub fn breakdown<'a>(
+    records: impl IntoIterator<Item = &'a MatchRecord>,
+    by: Breakdown,
+) -> BTreeMap<String, WinRate> {
+    let mut groups: BTreeMap<String, WinRate> = BTreeMap::new();
+    let unknown = || "unknown".to_string();
+
+    for record in records {
+        match by {
+            Breakdown::PlayDraw => {
+                for game in &record.games {
+                    let key = match game.on_play {
+                        Some(true) => "play".to_string(),
+                        Some(false) => "draw".to_string(),
+                        None => unknown(),
+                    };
+                    groups.entry(key).or_default().add(game.won);
+                }
+            }
+            Breakdown::Deck => groups
+                .entry(record.deck_name.clone().unwrap_or_else(unknown))
+                .or_default()
+                .add(record.won),
+            Breakdown::Format => groups
+                .entry(record.format.clone().unwrap_or_else(unknown))
+                .or_default()
+                .add(record.won),
+            Breakdown::Week => groups
+                .entry(record.week().unwrap_or_else(unknown))
+                .or_default()
+                .add(record.won),
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::companion::opponent::Sighting;
+    use crate::companion::parse_log;
+
+    fn fixture_record() -> MatchRecord {
+        let records = record_matches(&parse_log(include_str!("fixtures/match.log")));
+        assert_eq!(records.len(), 1);
+        records.into_iter().next().unwrap()
+    }
+
+    #[test]
+    fn test_record_from_fixture() {
+        let record = fixture_record();
+        assert_eq!(record.match_id, "match-0001");
+        assert_eq!(record.deck_name.as_deref(), Some("Mono Red"));
+        assert_eq!(record.format.as_deref(), Some("Standard"));
+        assert_eq!(record.opponent.as_deref(), Some("Opponent"));
+        assert_eq!(record.opponent_rank.as_deref(), Some("Platinum 4"));
+        assert_eq!(record.won, Some(true));
+        assert_eq!(record.on_play(), Some(true));
+        assert_eq!(record.duration_sec, Some(148));
+        assert_eq!(record.week().as_deref(), Some("2024-W42"));
+        assert_eq!(record.games.len(), 1);
+        assert_eq!(record.games[0].opponent_mulligans, 1);
+        assert_eq!(record.games[0].turns, 3);
+        assert_eq!(record.score(), "1-0");
+    }
+
+    #[test]
+    fn test_upsert_replaces_existing_match() {
+        let mut history = MatchHistory::default();
+        let mut record = fixture_record();
+        assert!(history.upsert(record.clone()));
+        record.won = Some(false);
+        assert!(!history.upsert(record));
+        assert_eq!(history.matches.len(), 1);
+        assert_eq!(history.matches[0].won, Some(false));
+    }
+
+    #[test]
+    fn test_upsert_keeps_resolved_opponent() {
+        let seen = |name: Option<&str>| SeenCard {
+            grp_id: 87_000,
+            name: name.map(String::from),
+            copies: 1,
+            seen: vec![Sighting::Cast],
+        };
+        let mut history = MatchHistory::default();
+        let mut record = fixture_record();
+        record.opponent_cards = vec![seen(Some("Lightning Bolt"))];
+        record.opponent_archetype = Some("Mono Red".to_string());
+        history.upsert(record.clone());
+
+        // A backfill from the log has neither the names nor the archetype
+        record.opponent_cards = vec![seen(None)];
+        record.opponent_archetype = None;
+        assert!(!history.upsert(record));
+        let stored = &history.matches[0];
+        assert_eq!(stored.opponent_archetype.as_deref(), Some("Mono Red"));
+        assert_eq!(
+            stored.opponent_cards[0].name.as_deref(),
+            Some("Lightning Bolt")
+        );
+    }
+
+    #[test]
+    fn test_breakdowns_and_filters() {
+        let win = fixture_record();
+        let mut loss = fixture_record();
+        loss.match_id = "match-0002".to_string();
+        loss.deck_name = Some("Azorius Control".to_string());
+        loss.won = Some(false);
+        loss.games[0].on_play = Some(false);
+        loss.games[0].won = Some(false);
+        let records = [win, loss];
+
+        let rate = win_rate(&records);
+        assert_eq!((rate.wins, rate.losses), (1, 1));
+        assert_eq!(rate.percent(), Some(50.0));
+
+        let by_deck = breakdown(&records, Breakdown::Deck);
+        assert_eq!(by_deck["Mono Red"].wins, 1);
+        assert_eq!(by_deck["Azorius Control"].losses, 1);
+
+        let by_play = breakdown(&records, Breakdown::PlayDraw);
+        assert_eq!(by_play["play"].wins, 1);
+        assert_eq!(by_play["draw"].losses, 1);
+
+        let filter = HistoryFilter {
+            deck: Some("azorius".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(records.iter().filter(|r| filter.matches(r)).count(), 1);
+
+        let filter = HistoryFilter {
+            since: NaiveDate::from_ymd_opt(2024, 10, 19),
+            ..Default::default()
+        };
+        assert_eq!(records.iter().filter(|r| filter.matches(r)).count(), 0);
+    }
+
+    #[test]
+    fn test_format_from_event() {
+        assert_eq!(format_from_event("QuickDraft_BLB_20241018"), "Quick Draft");
+        assert_eq!(
+            format_from_event("Traditional_Ladder"),
+            "Traditional Standard"
+        );
+        assert_eq!(format_from_event("Historic_Ladder"), "Historic");
+        assert_eq!(format_from_event("Cube_Event"), "Cube_Event");
+    }
+}
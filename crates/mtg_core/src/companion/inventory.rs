@@ -0,0 +1,64 @@
+//! The player inventory Arena logs on startup and after every purchase or craft.
+
+use crate::collection::Wildcards;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Currencies, wildcards and cosmetics of the player
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "PascalCase")]
+pub struct InventoryInfo {
+    pub seq_id: Option<u32>,
+    pub changes: Option<Vec<Value>>,
+    pub gems: Option<u32>,
+    pub gold: Option<u32>,
+    pub total_vault_progress: Option<u32>,
+    pub wc_track_position: Option<u32>,
+    pub wild_card_commons: Option<u32>,
+    pub wild_card_un_commons: Option<u32>,
+    pub wild_card_rares: Option<u32>,
+    pub wild_card_mythics: Option<u32>,
+    pub custom_tokens: Option<HashMap<String, u32>>,
+    pub boosters: Option<Vec<Value>>,
+    pub vouchers: Option<HashMap<String, Value>>,
+    pub prize_walls_unlocked: Option<Vec<Value>>,
+    pub cosmetics: Option<Cosmetics>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "PascalCase")]
+pub struct Cosmetics {
+    pub art_styles: Option<Vec<Value>>,
+    pub avatars: Option<Vec<Value>>,
+    pub pets: Option<Vec<Value>>,
+    pub sleeves: Option<Vec<Value>>,
+    pub emotes: Option<Vec<Value>>,
+    pub titles: Option<Vec<Value>>,
+}
+
+impl InventoryInfo {
+    /// The wildcards on hand, `None` when this inventory update does not list them
+    pub fn wildcards(&self) -> Option<Wildcards> {
+        Some(Wildcards {
+            common: self.wild_card_commons?,
+            uncommon: self.wild_card_un_commons.unwrap_or(0),
+            rare: self.wild_card_rares.unwrap_or(0),
+            mythic: self.wild_card_mythics.unwrap_or(0),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InventoryLine {
+    inventory_info: Option<InventoryInfo>,
+}
+
+/// The inventory in a log line, e.g. the `StartHook` response
+pub fn parse_inventory_line(line: &str) -> Option<InventoryInfo> {
+    let start = line.find('{')?;
+    serde_json::from_str::<InventoryLine>(&line[start..])
+        .ok()?
+        .inventory_info
+}
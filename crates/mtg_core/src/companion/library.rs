@@ -0,0 +1,214 @@
+use super::events::{ArenaEvent, DeckEntry, SeatId, Zone};
+use super::state::GameState;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// A card of the submitted deck and how many copies are still in the library
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LibraryCard {
+    pub grp_id: u32,
+    pub remaining: u32,
+    pub in_deck: u32,
+}
+
+/// What is left in the local player's library
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LibraryContents {
+    /// Cards in the library, as the game reports it when the zone is known
+    pub size: u32,
+    /// Every card of the deck, most copies left first
+    pub cards: Vec<LibraryCard>,
+}
+
+impl LibraryContents {
+    /// Work out the library from the deck and every card of ours the game shows outside it.
+    ///
+    /// All of our own cards are visible to us once they leave the library, so whatever is not
+    /// in hand, on the battlefield, in the graveyard, exile or on the stack is still in there.
+    /// This stays right through mulligans, shuffles and cards put back on top.
+    pub fn from_game(deck: &[DeckEntry], state: &GameState, seat: SeatId) -> Self {
+        let mut out: HashMap<u32, u32> = HashMap::new();
+        for object in state.objects.values() {
+            let zone = state
+                .zones
+                .get(&object.zone_id)
+                .map(|zone| zone.zone)
+                .unwrap_or(Zone::Unknown);
+            if object.owner == seat
+                && object.kind == "Card"
+                && !matches!(
+                    zone,
+                    Zone::Library | Zone::Revealed | Zone::Sideboard | Zone::Limbo
+                )
+            {
+                *out.entry(object.grp_id).or_default() += 1;
+            }
+        }
+
+        let mut in_deck: BTreeMap<u32, u32> = BTreeMap::new();
+        for entry in deck {
+            *in_deck.entry(entry.grp_id).or_default() += entry.quantity;
+        }
+        let mut cards: Vec<LibraryCard> = in_deck
+            .into_iter()
+            .map(|(grp_id, in_deck)| LibraryCard {
+                grp_id,
+                remaining: in_deck.saturating_sub(out.get(&grp_id).copied().unwrap_or(0)),
+                in_deck,
+            })
+            .collect();
+        cards.sort_by_key(|card| std::cmp::Reverse(card.remaining));
+
+        let counted: u32 = cards.iter().map(|card| card.remaining).sum();
+        let size = match state.zone_size(Zone::Library, seat) {
+            0 => counted,
+            size => size as u32,
+        };
+        Self { size, cards }
+    }
+
+    /// Chance that the next draw is one of `copies` cards
+    pub fn next_draw(&self, copies: u32) -> f64 {
+        draw_probability(self.size, copies, 1)
+    }
+
+    /// Chance to draw at least one of `copies` cards within `draws` draws
+    pub fn within(&self, copies: u32, draws: u32) -> f64 {
+        draw_probability(self.size, copies, draws)
+    }
+
+    /// Cards left per category, e.g. card type, given a category for each grpId.
+    /// Cards without a category are counted as `Other`.
+    pub fn by_category(&self, categories: &HashMap<u32, String>) -> BTreeMap<String, u32> {
+        let mut counts = BTreeMap::new();
+        for card in self.cards.iter().filter(|card| card.remaining > 0) {
+            let category = categories
+                .get(&card.grp_id)
+                .cloned()
+                .unwrap_or_else(|| "Other".to_string());
+            *counts.entry(category).or_default() += card.remaining;
+        }
+        counts
+    }
+}
+
+/// Hypergeometric chance of drawing at least one of `copies` cards within `draws` draws
+/// from a library of `size` cards
+pub fn draw_probability(size: u32, copies: u32, draws: u32) -> f64 {
+    if copies == 0 || size == 0 || draws == 0 {
+        return 0.0;
+    }
+    let copies = copies.min(size);
+    let draws = draws.min(size);
+    // Chance that every draw misses, one draw at a time
+    let mut miss = 1.0;
+    for i in 0..draws {
+        let left = (size - i) as f64;
+        let misses_left = (size - copies).saturating_sub(i) as f64;
+        miss *= misses_left / left;
+    }
+    1.0 - miss
+}
+
+/// Follows the local player's submitted deck and reports the library whenever a card of ours
+/// enters or leaves it
+#[derive(Debug, Default)]
+pub struct LibraryTracker {
+    deck: Vec<DeckEntry>,
+    local_seat: Option<SeatId>,
+}
+
+impl LibraryTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed an event with the game state after it, returning the library when it changed
+    pub fn push(&mut self, event: &ArenaEvent, state: &GameState) -> Option<LibraryContents> {
+        match event {
+            ArenaEvent::MatchStarted { .. } => {
+                self.deck.clear();
+                self.local_seat = None;
+            }
+            ArenaEvent::DeckSubmitted { seat, main, .. } => {
+                self.deck = main.clone();
+                if seat.is_some() {
+                    self.local_seat = *seat;
+                }
+            }
+            ArenaEvent::GameStarted {
+                local_seat: Some(seat),
+                ..
+            } => self.local_seat = Some(*seat),
+            // Opening hands are dealt without zone transfers, show the library they leave
+            ArenaEvent::TurnStarted { turn: 1, .. } => return self.contents(state),
+            ArenaEvent::ZoneTransfer(transfer)
+                if (transfer.from == Zone::Library || transfer.to == Zone::Library)
+                    && transfer.card.owner.is_some()
+                    && transfer.card.owner == self.local_seat =>
+            {
+                return self.contents(state);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn contents(&self, state: &GameState) -> Option<LibraryContents> {
+        if self.deck.is_empty() {
+            return None;
+        }
+        Some(LibraryContents::from_game(
+            &self.deck,
+            state,
+            self.local_seat?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::companion::ArenaParser;
+
+    #[test]
+    fn test_draw_probability() {
+        assert_eq!(draw_probability(40, 0, 3), 0.0);
+        assert!((draw_probability(40, 4, 1) - 0.1).abs() < 1e-9);
+        // 1 - (36/40 * 35/39)
+        assert!((draw_probability(40, 4, 2) - (1.0 - 36.0 / 40.0 * 35.0 / 39.0)).abs() < 1e-9);
+        assert_eq!(draw_probability(5, 5, 1), 1.0);
+        assert_eq!(draw_probability(5, 1, 10), 1.0);
+    }
+
+    #[test]
+    fn test_library_from_fixture() {
+        let mut parser = ArenaParser::new();
+        let mut tracker = LibraryTracker::new();
+        let mut last = None;
+        for line in include_str!("fixtures/match.log").lines() {
+            for event in parser.push_line(line) {
+                if let Some(library) = tracker.push(&event, parser.game_state()) {
+                    last = Some(library);
+                }
+            }
+        }
+
+        // 3 + 2 cards submitted, one of each played
+        let library = last.expect("the library is reported from turn one");
+        let remaining: HashMap<u32, u32> = library
+            .cards
+            .iter()
+            .map(|card| (card.grp_id, card.remaining))
+            .collect();
+        assert_eq!(remaining[&87521], 2);
+        assert_eq!(remaining[&90012], 1);
+        assert_eq!(library.size, 3);
+        assert!((library.next_draw(2) - 2.0 / 3.0).abs() < 1e-9);
+
+        let categories = HashMap::from([(87521, "Land".to_string())]);
+        let counts = library.by_category(&categories);
+        assert_eq!(counts["Land"], 2);
+        assert_eq!(counts["Other"], 1);
+    }
+}
@@ -0,0 +1,287 @@
+use chrono::NaiveDateTime;
+use serde_json::Value;
+
+const LOGGER_PREFIX: &str = "[UnityCrossThreadLogger]";
+
+/// Largest payload kept while waiting for it to close, anything bigger is dropped
+const MAX_PAYLOAD_BYTES: usize = 16 * 1024 * 1024;
+
+/// Timestamp formats used by the Arena client in log headers
+const TIMESTAMP_FORMATS: [&str; 4] = [
+    "%m/%d/%Y %I:%M:%S %p",
+    "%d/%m/%Y %H:%M:%S",
+    "%Y-%m-%d %H:%M:%S",
+    "%d.%m.%Y %H:%M:%S",
+];
+
+/// A single message of an Arena log, made of a header and its JSON payload
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    /// Message name, e.g. `GreToClientEvent` or `EventSetDeckV2`
+    pub name: String,
+    /// Time of the last header seen before this entry
+    pub timestamp: Option<NaiveDateTime>,
+    pub payload: Value,
+}
+
+/// Splits Arena log lines into entries.
+///
+/// Headers (`==> Name {..}`, `<== Name(id)`, `Match to X: Name`, `STATE CHANGED {..}`) may
+/// carry their payload on the same line or on the following ones, and some payloads are
+/// pretty-printed over several lines. Feed lines in order with [`LogReader::push_line`].
+#[derive(Debug, Default)]
+pub struct LogReader {
+    pending: Option<String>,
+    buffer: String,
+    /// Braces still open in `buffer`, the payload is only parsed once they are closed
+    depth: i64,
+    timestamp: Option<NaiveDateTime>,
+}
+
+impl LogReader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one line of the log, returning the entry it completes, if any
+    pub fn push_line(&mut self, line: &str) -> Option<LogEntry> {
+        let line = line.trim_end_matches(['\r', '\n']);
+
+        let trimmed = line.trim_start();
+        let is_header = line.starts_with(LOGGER_PREFIX)
+            || trimmed.starts_with("<== ")
+            || trimmed.starts_with("==> ");
+
+        if !self.buffer.is_empty() {
+            if is_header {
+                // A new header before the payload closed: the payload was truncated
+                self.reset();
+            } else {
+                return self.append(line);
+            }
+        }
+
+        if trimmed.starts_with('{') {
+            return self.append(trimmed);
+        }
+
+        let rest = match line.find(LOGGER_PREFIX) {
+            Some(index) => &line[index + LOGGER_PREFIX.len()..],
+            None if is_header => trimmed,
+            None => return None,
+        };
+        self.read_header(rest.trim())
+    }
+
+    fn reset(&mut self) {
+        self.buffer.clear();
+        self.depth = 0;
+        self.pending = None;
+    }
+
+    /// Add a line to the payload, parsing it once its braces are balanced
+    fn append(&mut self, line: &str) -> Option<LogEntry> {
+        self.buffer.push_str(line);
+        self.buffer.push('\n');
+        self.depth += brace_depth(line);
+
+        if self.buffer.len() > MAX_PAYLOAD_BYTES {
+            self.reset();
+            return None;
+        }
+        if self.depth > 0 {
+            return None;
+        }
+        self.complete_buffer()
+    }
+
+    fn read_header(&mut self, rest: &str) -> Option<LogEntry> {
+        self.pending = None;
+
+        if let Some(json) = rest.strip_prefix("STATE CHANGED") {
+            return self.entry_with_inline_payload("StateChanged", json);
+        }
+
+        for arrow in ["==> ", "<== "] {
+            if let Some(index) = rest.find(arrow) {
+                let after = &rest[index + arrow.len()..];
+                let end = after.find(['(', ' ', '{']).unwrap_or(after.len());
+                let name = after[..end].to_string();
+                let remainder = after[end..].trim_start();
+                // Skip the request id in `<== Name(id)`
+                let remainder = match remainder.strip_prefix('(') {
+                    Some(inner) => inner.split_once(')').map(|(_, r)| r).unwrap_or(""),
+                    None => remainder,
+                };
+                return self.entry_with_inline_payload(&name, remainder);
+            }
+        }
+
+        // `<timestamp>: Match to <id>: Name` or `<timestamp>: <id> to Match: Name`
+        if rest.contains(" to Match: ") || rest.contains(": Match to ") {
+            if let Some((prefix, _)) = rest.split_once(": ") {
+                self.timestamp = parse_timestamp(prefix).or(self.timestamp);
+            }
+            let header = match rest.find('{') {
+                Some(index) => &rest[..index],
+                None => rest,
+            };
+            let name = header
+                .trim()
+                .rsplit(": ")
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            let remainder = &rest[header.len()..];
+            return self.entry_with_inline_payload(&name, remainder);
+        }
+
+        if let Some(timestamp) = parse_timestamp(rest) {
+            self.timestamp = Some(timestamp);
+            return None;
+        }
+
+        // Bare `Name {json}` messages such as `Draft.Notify`
+        if let Some((name, json)) = rest.split_once(' ') {
+            if json.trim_start().starts_with('{') && !name.contains(':') {
+                return self.entry_with_inline_payload(name, json);
+            }
+        }
+
+        None
+    }
+
+    fn entry_with_inline_payload(&mut self, name: &str, remainder: &str) -> Option<LogEntry> {
+        let remainder = remainder.trim();
+        if remainder.starts_with('{') {
+            self.pending = Some(name.to_string());
+            return self.append(remainder);
+        }
+        self.pending = Some(name.to_string());
+        None
+    }
+
+    fn complete_buffer(&mut self) -> Option<LogEntry> {
+        // Balanced but not JSON: waiting for more lines would never fix it
+        let Ok(payload) = serde_json::from_str::<Value>(self.buffer.trim()) else {
+            self.reset();
+            return None;
+        };
+        self.buffer.clear();
+        self.depth = 0;
+        let name = self
+            .pending
+            .take()
+            .or_else(|| infer_name(&payload))
+            .unwrap_or_default();
+        if name.is_empty() {
+            return None;
+        }
+        Some(LogEntry {
+            name,
+            timestamp: self.timestamp,
+            payload,
+        })
+    }
+}
+
+/// Split a whole log into entries
+pub fn read_entries(log: &str) -> Vec<LogEntry> {
+    let mut reader = LogReader::new();
+    log.lines()
+        .filter_map(|line| reader.push_line(line))
+        .collect()
+}
+
+/// Name of a headerless JSON payload, from its top-level message key
+fn infer_name(payload: &Value) -> Option<String> {
+    const KEYS: [(&str, &str); 3] = [
+        ("greToClientEvent", "GreToClientEvent"),
+        (
+            "matchGameRoomStateChangedEvent",
+            "MatchGameRoomStateChangedEvent",
+        ),
+        ("authenticateResponse", "AuthenticateResponse"),
+    ];
+    KEYS.iter()
+        .find(|(key, _)| payload.get(key).is_some())
+        .map(|(_, name)| name.to_string())
+}
+
+/// Braces opened minus braces closed on a line, ignoring the ones inside strings
+fn brace_depth(line: &str) -> i64 {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in line.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '{' if !in_string => depth += 1,
+            '}' if !in_string => depth -= 1,
+            _ => {}
+        }
+    }
+    depth
+}
+
+fn parse_timestamp(text: &str) -> Option<NaiveDateTime> {
+    let text = text.trim();
+    TIMESTAMP_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inline_and_next_line_payloads() {
+        let log = "[UnityCrossThreadLogger]==> EventJoin {\"id\":\"1\"}\n\
+                   <== EventJoin(1)\n\
+                   {\"Course\":{}}\n";
+        let entries = read_entries(log);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].name, "EventJoin");
+        assert_eq!(entries[1].name, "EventJoin");
+        assert!(entries[1].payload.get("Course").is_some());
+    }
+
+    #[test]
+    fn test_multiline_payload_and_timestamp() {
+        let log = "[UnityCrossThreadLogger]10/18/2024 7:12:33 PM: Match to ABC: GreToClientEvent\n\
+                   {\n  \"greToClientEvent\": {\n    \"greToClientMessages\": []\n  }\n}\n";
+        let entries = read_entries(log);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "GreToClientEvent");
+        assert_eq!(
+            entries[0].timestamp.unwrap().to_string(),
+            "2024-10-18 19:12:33"
+        );
+    }
+
+    #[test]
+    fn test_truncated_payload_is_dropped() {
+        let log = "[UnityCrossThreadLogger]==> EventJoin {\"id\":\n\
+                   [UnityCrossThreadLogger]STATE CHANGED {\"old\":\"A\",\"new\":\"B\"}\n";
+        let entries = read_entries(log);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "StateChanged");
+    }
+
+    #[test]
+    fn test_broken_payload_does_not_swallow_entries() {
+        let log = "{\"unterminated\": [1, 2\n\
+                   <== EventJoin(1)\n\
+                   {\"Course\":{\"brace\":\"}{\"}}\n\
+                   {\"a\":1} trailing\n\
+                   [UnityCrossThreadLogger]STATE CHANGED {\"old\":\"A\",\"new\":\"B\"}\n";
+        let entries = read_entries(log);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].name, "EventJoin");
+        assert_eq!(entries[0].payload["Course"]["brace"], "}{");
+        assert_eq!(entries[1].name, "StateChanged");
+    }
+}
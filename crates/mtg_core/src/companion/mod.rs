@@ -1,3 +1,38 @@
-pub mod event_parser;
-pub mod player_parser;
-pub mod types;
+//! MTG Arena log parsing.
+//!
+//! [`log::LogReader`] splits raw log lines into named JSON entries and [`parser::ArenaParser`]
+//! turns those into typed [`events::ArenaEvent`]s, applying each GRE state to a
+//! [`state::GameState`] along the way. All of it is pure, so the CLI can feed them from a file,
+//! a tailed log or a stored session alike.
+
+pub mod cards;
+pub mod drafts;
+pub mod events;
+pub mod history;
+pub mod inventory;
+pub mod library;
+pub mod log;
+pub mod opponent;
+pub mod parser;
+pub mod ratings;
+pub mod replay;
+pub mod state;
+pub mod stream;
+
+pub use cards::{ArenaCard, ArenaCardDatabase, ArenaCardMap, ArenaCardResolver};
+pub use drafts::{
+    record_drafts, DraftHistory, DraftPickRecord, DraftRecord, DraftRecorder, PickLog,
+};
+pub use events::*;
+pub use history::{MatchHistory, MatchRecord, MatchRecorder};
+pub use inventory::{parse_inventory_line, InventoryInfo};
+pub use library::{draw_probability, LibraryCard, LibraryContents, LibraryTracker};
+pub use log::{read_entries, LogEntry, LogReader};
+pub use opponent::{OpponentTracker, SeenCard, Sighting};
+pub use parser::{parse_log, ArenaParser};
+pub use ratings::{
+    suggest_picks, CardRating, CardRatings, ColorCommitment, PickAdvice, PickSuggestion,
+};
+pub use replay::{replay_matches, GameReplay, MatchReplay};
+pub use state::{GameObject, GameState, PlayerState, ZoneState};
+pub use stream::{EventStream, StreamEvent, SCHEMA_VERSION};
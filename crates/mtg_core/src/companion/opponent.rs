@@ -0,0 +1,174 @@
+use super::events::{ArenaEvent, SeatId, Zone, ZoneTransfer};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// How an opponent card became visible
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sighting {
+    Cast,
+    Played,
+    Battlefield,
+    Revealed,
+    Graveyard,
+    Exiled,
+}
+
+impl Sighting {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Sighting::Cast => "cast",
+            Sighting::Played => "played",
+            Sighting::Battlefield => "put onto the battlefield",
+            Sighting::Revealed => "revealed",
+            Sighting::Graveyard => "graveyard",
+            Sighting::Exiled => "exiled",
+        }
+    }
+
+    fn of(transfer: &ZoneTransfer) -> Option<Self> {
+        match (transfer.category.as_deref(), transfer.from, transfer.to) {
+            (Some("CastSpell"), _, _) => Some(Sighting::Cast),
+            (Some("PlayLand"), _, _) => Some(Sighting::Played),
+            (_, Zone::Hand | Zone::Library, Zone::Stack) => Some(Sighting::Cast),
+            (_, _, Zone::Revealed) => Some(Sighting::Revealed),
+            (_, _, Zone::Graveyard) => Some(Sighting::Graveyard),
+            (_, _, Zone::Exile) => Some(Sighting::Exiled),
+            (_, _, Zone::Battlefield) => Some(Sighting::Battlefield),
+            _ => None,
+        }
+    }
+}
+
+/// A card the opponent revealed during a match
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeenCard {
+    pub grp_id: u32,
+    /// Filled in by callers that can look up card names
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Most copies cast or played in a single game, at least one
+    pub copies: u32,
+    pub seen: Vec<Sighting>,
+}
+
+/// Collects the cards an opponent reveals over a match.
+///
+/// Cards count as revealed when they are cast, played, put onto the battlefield, revealed or
+/// land in the graveyard or exile; the local seat comes from [`ArenaEvent::GameStarted`].
+#[derive(Debug, Default)]
+pub struct OpponentTracker {
+    local_seat: Option<SeatId>,
+    cards: BTreeMap<u32, SeenCard>,
+    /// Copies cast or played per card in the current game
+    game_copies: HashMap<u32, u32>,
+}
+
+impl OpponentTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed an event, returning the card when it was seen for the first time or in a new way
+    pub fn push(&mut self, event: &ArenaEvent) -> Option<SeenCard> {
+        match event {
+            ArenaEvent::MatchStarted { .. } => {
+                self.local_seat = None;
+                self.cards.clear();
+                self.game_copies.clear();
+            }
+            ArenaEvent::GameStarted { local_seat, .. } => {
+                if local_seat.is_some() {
+                    self.local_seat = *local_seat;
+                }
+                self.game_copies.clear();
+            }
+            ArenaEvent::ZoneTransfer(transfer) => return self.transfer(transfer),
+            _ => {}
+        }
+        None
+    }
+
+    fn transfer(&mut self, transfer: &ZoneTransfer) -> Option<SeenCard> {
+        let local_seat = self.local_seat?;
+        let owner = transfer.card.owner?;
+        let grp_id = transfer.card.grp_id?;
+        if owner == local_seat {
+            return None;
+        }
+        let sighting = Sighting::of(transfer)?;
+
+        let copies = if matches!(sighting, Sighting::Cast | Sighting::Played) {
+            let copies = self.game_copies.entry(grp_id).or_default();
+            *copies += 1;
+            *copies
+        } else {
+            1
+        };
+
+        let card = self.cards.entry(grp_id).or_insert_with(|| SeenCard {
+            grp_id,
+            name: None,
+            copies: 0,
+            seen: Vec::new(),
+        });
+        let first = card.seen.is_empty();
+        card.copies = card.copies.max(copies);
+        if card.seen.contains(&sighting) {
+            return None;
+        }
+        card.seen.push(sighting);
+        card.seen.sort();
+        // A permanent cast from hand always resolves onto the battlefield, not worth a line
+        if !first && sighting == Sighting::Battlefield {
+            return None;
+        }
+        Some(card.clone())
+    }
+
+    /// Cards seen so far in the match, by grpId
+    pub fn cards(&self) -> Vec<SeenCard> {
+        self.cards.values().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::companion::parse_log;
+
+    #[test]
+    fn test_tracks_opponent_cards_only() {
+        let mut tracker = OpponentTracker::new();
+        let new: Vec<SeenCard> = parse_log(include_str!("fixtures/match.log"))
+            .iter()
+            .filter_map(|event| tracker.push(event))
+            .collect();
+
+        // The fixture's opponent only draws a hidden card, all visible cards are ours
+        assert!(new.is_empty());
+        assert!(tracker.cards().is_empty());
+
+        let cast = |instance_id, owner| {
+            ArenaEvent::ZoneTransfer(ZoneTransfer {
+                card: crate::companion::CardRef {
+                    instance_id,
+                    grp_id: Some(90012),
+                    owner: Some(owner),
+                },
+                from: Zone::Hand,
+                to: Zone::Stack,
+                category: Some("CastSpell".to_string()),
+            })
+        };
+        assert!(tracker.push(&cast(300, 2)).is_some());
+        // A second copy is counted but not announced again
+        assert!(tracker.push(&cast(301, 2)).is_none());
+        assert!(tracker.push(&cast(302, 1)).is_none());
+
+        let cards = tracker.cards();
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].copies, 2);
+        assert_eq!(cards[0].seen, vec![Sighting::Cast]);
+    }
+}
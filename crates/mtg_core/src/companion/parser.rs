@@ -0,0 +1,936 @@
+use super::events::*;
+use super::log::{LogEntry, LogReader};
+use super::state::GameState;
+use chrono::NaiveDateTime;
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy)]
+struct ZoneInfo {
+    zone: Zone,
+    owner: Option<SeatId>,
+}
+
+/// Turns Arena log lines into [`ArenaEvent`]s.
+///
+/// The parser applies every GRE state message to a [`GameState`] and turns the diffs into
+/// events. It performs no I/O, so the same stream can be rendered live, stored, or replayed.
+#[derive(Debug, Default)]
+pub struct ArenaParser {
+    reader: LogReader,
+    match_id: Option<String>,
+    local_seat: Option<SeatId>,
+    game_number: u32,
+    game_over: bool,
+    state: GameState,
+    life: HashMap<SeatId, i32>,
+    mulligans: HashMap<SeatId, u32>,
+    turn: u32,
+    active_seat: SeatId,
+    phase: Option<(String, Option<String>)>,
+}
+
+impl ArenaParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seat of the local player in the current match, once the GRE has told us
+    pub fn local_seat(&self) -> Option<SeatId> {
+        self.local_seat
+    }
+
+    /// Id of the current match, once the match room or the GRE has named it
+    pub fn match_id(&self) -> Option<&str> {
+        self.match_id.as_deref()
+    }
+
+    /// The current game as of the last game state message
+    pub fn game_state(&self) -> &GameState {
+        &self.state
+    }
+
+    /// Feed one log line, returning the events it completes
+    pub fn push_line(&mut self, line: &str) -> Vec<ArenaEvent> {
+        match self.reader.push_line(line) {
+            Some(entry) => self.parse_entry(&entry),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn parse_entry(&mut self, entry: &LogEntry) -> Vec<ArenaEvent> {
+        let mut events = Vec::new();
+        let payload = &entry.payload;
+
+        match entry.name.as_str() {
+            "GreToClientEvent" => self.gre_to_client(payload, &mut events),
+            "ClientToMatchServiceMessageType_ClientToGREMessage" => {
+                self.client_to_gre(payload, &mut events)
+            }
+            "MatchGameRoomStateChangedEvent" => {
+                self.match_room(payload, entry.timestamp, &mut events)
+            }
+            "StateChanged" => {
+                if let (Some(from), Some(to)) =
+                    (str_field(payload, "old"), str_field(payload, "new"))
+                {
+                    events.push(ArenaEvent::StateChanged { from, to });
+                }
+            }
+            "Authenticate" | "AuthenticateResponse" => {
+                let data = payload.get("authenticateResponse").unwrap_or(payload);
+                if let (Some(client_id), Some(screen_name)) =
+                    (str_field(data, "clientId"), str_field(data, "screenName"))
+                {
+                    events.push(ArenaEvent::Authenticated {
+                        client_id,
+                        screen_name,
+                    });
+                }
+            }
+            "MatchCreated" | "Event.MatchCreated" | "Event_MatchCreated" => {
+                let data = unwrap_request(payload);
+                if let Some(match_id) = str_field(&data, "matchId") {
+                    let opponent_rank = str_field(&data, "opponentRankingClass")
+                        .filter(|class| !class.is_empty() && class != "None")
+                        .map(|class| match u32_field(&data, "opponentRankingTier") {
+                            Some(tier) if tier > 0 && class != "Mythic" => {
+                                format!("{class} {tier}")
+                            }
+                            _ => class,
+                        });
+                    events.push(ArenaEvent::MatchCreated {
+                        match_id,
+                        event_id: str_field(&data, "eventId"),
+                        opponent_name: str_field(&data, "opponentScreenName"),
+                        opponent_rank,
+                    });
+                }
+            }
+            "LogBusinessEvents" => business_event(&unwrap_request(payload), &mut events),
+            "EventSetDeckV2"
+            | "Event_SetDeckV2"
+            | "DeckUpsertDeckV2"
+            | "Deck_UpsertDeckV2"
+            | "Event_GetCourseDeck"
+            | "DeckSubmission" => {
+                if let Some(deck) = arena_deck(&unwrap_request(payload)) {
+                    events.push(ArenaEvent::DeckSelected(deck));
+                }
+            }
+            "Draft.Notify"
+            | "HumanDraftPack"
+            | "BotDraft_DraftStatus"
+            | "BotDraft_DraftPick"
+            | "Event_PlayerDraftMakePick"
+            | "HumanDraftPick" => {
+                draft_event(&unwrap_request(payload), entry.timestamp, &mut events)
+            }
+            "RankGetCombinedRankInfo" => {
+                let data = unwrap_request(payload);
+                events.push(ArenaEvent::RankUpdated {
+                    constructed: rank(&data, "constructed"),
+                    limited: rank(&data, "limited"),
+                });
+            }
+            "QuestGetQuests" => {
+                let data = unwrap_request(payload);
+                let quests = array(&data, "quests")
+                    .iter()
+                    .map(|quest| Quest {
+                        name: str_field(quest, "locKey")
+                            .map(|key| key.rsplit('/').next().unwrap_or(&key).replace('_', " "))
+                            .unwrap_or_default(),
+                        progress: u32_field(quest, "endingProgress").unwrap_or(0),
+                        goal: u32_field(quest, "goal").unwrap_or(0),
+                    })
+                    .collect();
+                events.push(ArenaEvent::Quests { quests });
+            }
+            _ => {}
+        }
+
+        events
+    }
+
+    fn reset_game(&mut self) {
+        self.game_over = false;
+        self.state = GameState::new();
+        self.life.clear();
+        self.mulligans.clear();
+        self.turn = 0;
+        self.active_seat = 0;
+        self.phase = None;
+    }
+
+    fn match_room(
+        &mut self,
+        payload: &Value,
+        timestamp: Option<NaiveDateTime>,
+        events: &mut Vec<ArenaEvent>,
+    ) {
+        let event = payload
+            .get("matchGameRoomStateChangedEvent")
+            .unwrap_or(payload);
+        let Some(info) = event.get("gameRoomInfo") else {
+            return;
+        };
+        let config = info.get("gameRoomConfig");
+        let match_id = config
+            .and_then(|config| str_field(config, "matchId"))
+            .or_else(|| self.match_id.clone())
+            .unwrap_or_default();
+
+        match info.get("stateType").and_then(Value::as_str) {
+            Some("MatchGameRoomStateType_Playing") => {
+                if self.match_id.as_deref() == Some(match_id.as_str()) {
+                    return;
+                }
+                self.match_id = Some(match_id.clone());
+                self.local_seat = None;
+                self.game_number = 0;
+                self.reset_game();
+
+                let reserved = config.map(|config| array(config, "reservedPlayers"));
+                let reserved = reserved.unwrap_or_default();
+                let players = reserved
+                    .iter()
+                    .map(|player| MatchPlayer {
+                        seat: u32_field(player, "systemSeatId").unwrap_or(0),
+                        team: u32_field(player, "teamId").unwrap_or(0),
+                        name: str_field(player, "playerName").unwrap_or_default(),
+                        user_id: str_field(player, "userId"),
+                    })
+                    .collect();
+                let event_id = reserved
+                    .iter()
+                    .find_map(|player| str_field(player, "eventId"));
+
+                events.push(ArenaEvent::MatchStarted {
+                    match_id,
+                    event_id,
+                    players,
+                    timestamp,
+                });
+            }
+            Some("MatchGameRoomStateType_MatchCompleted") => {
+                let result = info.get("finalMatchResult");
+                let games: Vec<GameResult> = result
+                    .map(|result| array(result, "resultList"))
+                    .unwrap_or_default()
+                    .iter()
+                    .map(game_result)
+                    .collect();
+                let winning_team = games
+                    .iter()
+                    .find(|game| game.scope == "match")
+                    .and_then(|game| game.winning_team);
+                let reason = result
+                    .and_then(|result| str_field(result, "matchCompletedReason"))
+                    .map(|reason| strip_prefix(&reason, "MatchCompletedReasonType_"));
+
+                events.push(ArenaEvent::MatchCompleted {
+                    match_id,
+                    winning_team,
+                    reason,
+                    games: games
+                        .into_iter()
+                        .filter(|game| game.scope == "game")
+                        .collect(),
+                    timestamp,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn gre_to_client(&mut self, payload: &Value, events: &mut Vec<ArenaEvent>) {
+        let event = payload.get("greToClientEvent").unwrap_or(payload);
+
+        for message in array(event, "greToClientMessages") {
+            match message.get("type").and_then(Value::as_str) {
+                Some("GREMessageType_ConnectResp") => {
+                    if let Some(seat) = array(message, "systemSeatIds")
+                        .first()
+                        .and_then(Value::as_u64)
+                    {
+                        self.local_seat = Some(seat as SeatId);
+                    }
+                    if let Some(deck) = message
+                        .get("connectResp")
+                        .and_then(|resp| resp.get("deckMessage"))
+                    {
+                        events.push(ArenaEvent::DeckSubmitted {
+                            seat: self.local_seat,
+                            main: grp_id_entries(array(deck, "deckCards")),
+                            sideboard: grp_id_entries(array(deck, "sideboardCards")),
+                        });
+                    }
+                }
+                Some("GREMessageType_DieRollResultsResp") => {
+                    let rolls = message
+                        .get("dieRollResultsResp")
+                        .map(|resp| array(resp, "playerDieRolls"))
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|roll| DieRoll {
+                            seat: u32_field(roll, "systemSeatId").unwrap_or(0),
+                            value: u32_field(roll, "rollValue").unwrap_or(0),
+                        })
+                        .collect();
+                    events.push(ArenaEvent::DieRoll { rolls });
+                }
+                Some("GREMessageType_GameStateMessage") => {
+                    if let Some(state) = message.get("gameStateMessage") {
+                        self.apply_game_state(state, events);
+                    }
+                }
+                Some("GREMessageType_TimerStateMessage") => {
+                    if let Some(timer) = message.get("timerStateMessage") {
+                        events.push(ArenaEvent::Timer(TimerUpdate {
+                            seat: u32_field(timer, "seatId").unwrap_or(0),
+                            timers: array(timer, "timers").iter().map(timer_state).collect(),
+                        }));
+                    }
+                }
+                Some("GREMessageType_IntermissionReq") => {
+                    if let Some(result) = message
+                        .get("intermissionReq")
+                        .and_then(|req| req.get("result"))
+                    {
+                        self.finish_game(vec![game_result(result)], events);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn client_to_gre(&mut self, payload: &Value, events: &mut Vec<ArenaEvent>) {
+        let message = unwrap_request(payload);
+        let message = message.get("payload").cloned().unwrap_or(message);
+        let message = match message.as_str() {
+            Some(text) => serde_json::from_str(text).unwrap_or(Value::Null),
+            None => message,
+        };
+
+        if message.get("type").and_then(Value::as_str) == Some("ClientMessageType_SubmitDeckResp") {
+            if let Some(deck) = message
+                .get("submitDeckResp")
+                .and_then(|resp| resp.get("deck"))
+            {
+                events.push(ArenaEvent::DeckSubmitted {
+                    seat: self.local_seat,
+                    main: grp_id_entries(array(deck, "deckCards")),
+                    sideboard: grp_id_entries(array(deck, "sideboardCards")),
+                });
+            }
+        }
+    }
+
+    fn finish_game(&mut self, results: Vec<GameResult>, events: &mut Vec<ArenaEvent>) {
+        if self.game_over {
+            return;
+        }
+        self.game_over = true;
+        events.push(ArenaEvent::GameOver {
+            game_number: self.game_number.max(1),
+            results,
+        });
+    }
+
+    fn card_ref(&self, instance_id: u32) -> CardRef {
+        let object = self.state.object(instance_id);
+        CardRef {
+            instance_id,
+            grp_id: object
+                .map(|object| object.grp_id)
+                .filter(|grp_id| *grp_id != 0),
+            owner: object.map(|object| object.owner),
+        }
+    }
+
+    fn zone(&self, zone_id: Option<u32>) -> ZoneInfo {
+        zone_id
+            .and_then(|id| self.state.zones.get(&id))
+            .map(|state| ZoneInfo {
+                zone: state.zone,
+                owner: state.owner,
+            })
+            .unwrap_or(ZoneInfo {
+                zone: Zone::Unknown,
+                owner: None,
+            })
+    }
+
+    fn apply_game_state(&mut self, state: &Value, events: &mut Vec<ArenaEvent>) {
+        let info = state.get("gameInfo");
+
+        if let Some(info) = info {
+            if self.match_id.is_none() {
+                self.match_id = str_field(info, "matchID");
+            }
+            if let Some(game_number) = u32_field(info, "gameNumber") {
+                if game_number != self.game_number {
+                    self.game_number = game_number;
+                    self.reset_game();
+                    events.push(ArenaEvent::GameStarted {
+                        game_number,
+                        local_seat: self.local_seat,
+                    });
+                }
+            }
+        }
+
+        let previous: HashMap<u32, (bool, bool)> = self
+            .state
+            .objects
+            .iter()
+            .map(|(id, object)| (*id, (object.attacking, object.blocking)))
+            .collect();
+        self.state.apply(state);
+
+        let mut combat = Vec::new();
+        for object in array(state, "gameObjects") {
+            let Some(instance_id) = u32_field(object, "instanceId") else {
+                continue;
+            };
+            let Some(current) = self.state.object(instance_id) else {
+                continue;
+            };
+            let (was_attacking, was_blocking) =
+                previous.get(&instance_id).copied().unwrap_or_default();
+            if current.attacking && !was_attacking {
+                combat.push(ArenaEvent::Attacking {
+                    card: self.card_ref(instance_id),
+                });
+            }
+            if current.blocking && !was_blocking {
+                combat.push(ArenaEvent::Blocking {
+                    card: self.card_ref(instance_id),
+                });
+            }
+        }
+
+        if let Some(turn_info) = state.get("turnInfo") {
+            let active_seat = u32_field(turn_info, "activePlayer").unwrap_or(self.active_seat);
+            if let Some(turn) = u32_field(turn_info, "turnNumber") {
+                if turn > self.turn {
+                    self.turn = turn;
+                    self.active_seat = active_seat;
+                    self.phase = None;
+                    events.push(ArenaEvent::TurnStarted { turn, active_seat });
+                }
+            }
+            if let Some(phase) = str_field(turn_info, "phase") {
+                let phase = strip_prefix(&phase, "Phase_");
+                let step = str_field(turn_info, "step").map(|step| strip_prefix(&step, "Step_"));
+                let current = Some((phase.clone(), step.clone()));
+                if self.phase != current && self.turn > 0 {
+                    self.phase = current;
+                    events.push(ArenaEvent::PhaseChanged {
+                        turn: self.turn,
+                        active_seat: self.active_seat,
+                        phase,
+                        step,
+                    });
+                }
+            }
+        }
+
+        for annotation in array(state, "annotations") {
+            self.annotation(annotation, events);
+        }
+
+        for player in array(state, "players") {
+            let Some(seat) = u32_field(player, "systemSeatNumber") else {
+                continue;
+            };
+            if let Some(life) = player.get("lifeTotal").and_then(Value::as_i64) {
+                let life = life as i32;
+                if let Some(previous) = self.life.insert(seat, life) {
+                    if previous != life {
+                        events.push(ArenaEvent::LifeChanged {
+                            seat,
+                            from: previous,
+                            to: life,
+                        });
+                    }
+                }
+            }
+            if let Some(count) = u32_field(player, "mulliganCount") {
+                let previous = self.mulligans.insert(seat, count).unwrap_or(0);
+                if count > previous {
+                    events.push(ArenaEvent::Mulligan { seat, count });
+                }
+            }
+        }
+
+        events.extend(combat);
+
+        if let Some(info) = info {
+            if info.get("stage").and_then(Value::as_str) == Some("GameStage_GameOver") {
+                let results = array(info, "results").iter().map(game_result).collect();
+                self.finish_game(results, events);
+            }
+        }
+    }
+
+    fn annotation(&mut self, annotation: &Value, events: &mut Vec<ArenaEvent>) {
+        let affected: Vec<u32> = array(annotation, "affectedIds")
+            .iter()
+            .filter_map(|id| id.as_u64().map(|id| id as u32))
+            .collect();
+        let affector = u32_field(annotation, "affectorId");
+
+        for kind in array(annotation, "type") {
+            match kind.as_str().unwrap_or_default() {
+                "AnnotationType_ZoneTransfer" => {
+                    let from = self.zone(detail_int(annotation, "zone_src").map(|id| id as u32));
+                    let to = self.zone(detail_int(annotation, "zone_dest").map(|id| id as u32));
+                    let category = detail_str(annotation, "category");
+                    for &id in &affected {
+                        let mut card = self.card_ref(id);
+                        // Hidden cards (an opponent's draw) belong to the owner of their zone
+                        card.owner = card.owner.or(from.owner).or(to.owner);
+                        events.push(ArenaEvent::ZoneTransfer(ZoneTransfer {
+                            card,
+                            from: from.zone,
+                            to: to.zone,
+                            category: category.clone(),
+                        }));
+                    }
+                }
+                "AnnotationType_DamageDealt" => {
+                    let amount = detail_int(annotation, "damage").unwrap_or(0).max(0) as u32;
+                    let Some(source) = affector else { continue };
+                    for &target in &affected {
+                        let target = if self.state.object(target).is_none()
+                            && self.state.players.contains_key(&target)
+                        {
+                            DamageTarget::Player(target)
+                        } else {
+                            DamageTarget::Card(self.card_ref(target))
+                        };
+                        if amount > 0 {
+                            events.push(ArenaEvent::Damage {
+                                source: self.card_ref(source),
+                                target,
+                                amount,
+                            });
+                        }
+                    }
+                }
+                "AnnotationType_TappedUntappedPermanent" => {
+                    let tapped = detail_int(annotation, "tapped").unwrap_or(0) == 1;
+                    for &id in &affected {
+                        events.push(ArenaEvent::Tapped {
+                            card: self.card_ref(id),
+                            tapped,
+                        });
+                    }
+                }
+                kind @ ("AnnotationType_CounterAdded" | "AnnotationType_CounterRemoved") => {
+                    let amount = detail_int(annotation, "transaction_amount").unwrap_or(1) as i32;
+                    let change = if kind == "AnnotationType_CounterAdded" {
+                        amount
+                    } else {
+                        -amount
+                    };
+                    let counter = match detail_int(annotation, "counter_type") {
+                        Some(1) => "+1/+1".to_string(),
+                        Some(2) => "-1/-1".to_string(),
+                        Some(7) => "loyalty".to_string(),
+                        Some(other) => format!("type {other}"),
+                        None => "unknown".to_string(),
+                    };
+                    for &id in &affected {
+                        events.push(ArenaEvent::Counters {
+                            card: self.card_ref(id),
+                            counter: counter.clone(),
+                            change,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Parse a whole log into events
+pub fn parse_log(log: &str) -> Vec<ArenaEvent> {
+    let mut parser = ArenaParser::new();
+    log.lines()
+        .flat_map(|line| parser.push_line(line))
+        .collect()
+}
+
+fn business_event(request: &Value, events: &mut Vec<ArenaEvent>) {
+    if let (Some(from), Some(to)) = (
+        str_field(request, "fromSceneName"),
+        str_field(request, "toSceneName"),
+    ) {
+        events.push(ArenaEvent::SceneChanged { from, to });
+        return;
+    }
+
+    if u32_field(request, "EventType") == Some(4) {
+        if let Some(match_id) = str_field(request, "MatchId") {
+            events.push(ArenaEvent::MatchReport(MatchReport {
+                match_id,
+                event_id: str_field(request, "EventId"),
+                seat: u32_field(request, "SeatId"),
+                team: u32_field(request, "TeamId"),
+                winning_team: u32_field(request, "WinningTeamId"),
+                reason: str_field(request, "WinningReason")
+                    .map(|reason| strip_prefix(&reason, "ResultReason_")),
+                turns: u32_field(request, "TurnCount"),
+                seconds: u32_field(request, "SecondsCount"),
+                starting_team: u32_field(request, "StartingTeamId"),
+                mulligans: array(request, "MulliganedHands").len() as u32,
+            }));
+        }
+    }
+}
+
+fn draft_event(data: &Value, timestamp: Option<NaiveDateTime>, events: &mut Vec<ArenaEvent>) {
+    // Premier and traditional drafts: Draft.Notify
+    if let Some(cards) = str_field(data, "PackCards") {
+        events.push(ArenaEvent::DraftPack {
+            draft_id: str_field(data, "draftId"),
+            pack: u32_field(data, "SelfPack").unwrap_or(0),
+            pick: u32_field(data, "SelfPick").unwrap_or(0),
+            cards: cards
+                .split(',')
+                .filter_map(|id| id.trim().parse().ok())
+                .collect(),
+            timestamp,
+        });
+        return;
+    }
+
+    // Premier and traditional drafts: Event_PlayerDraftMakePick
+    if let Some(draft_id) = str_field(data, "DraftId") {
+        if data.get("GrpIds").is_some() || data.get("GrpId").is_some() {
+            let mut cards = id_list(array(data, "GrpIds"));
+            cards.extend(u32_field(data, "GrpId"));
+            events.push(ArenaEvent::DraftPick {
+                draft_id: Some(draft_id),
+                pack: u32_field(data, "Pack").unwrap_or(0),
+                pick: u32_field(data, "Pick").unwrap_or(0),
+                cards,
+                timestamp,
+            });
+            return;
+        }
+    }
+
+    // Quick drafts: BotDraft_DraftPick requests (0-based pack and pick)
+    if let Some(pick) = data.get("PickInfo") {
+        events.push(ArenaEvent::DraftPick {
+            draft_id: str_field(pick, "EventName").or_else(|| str_field(data, "EventName")),
+            pack: u32_field(pick, "PackNumber").unwrap_or(0) + 1,
+            pick: u32_field(pick, "PickNumber").unwrap_or(0) + 1,
+            cards: id_list(array(pick, "CardIds")),
+            timestamp,
+        });
+        return;
+    }
+
+    // Quick drafts: BotDraft responses with the next pack
+    match str_field(data, "DraftStatus").as_deref() {
+        Some("PickNext") => events.push(ArenaEvent::DraftPack {
+            draft_id: str_field(data, "EventName"),
+            pack: u32_field(data, "PackNumber").unwrap_or(0) + 1,
+            pick: u32_field(data, "PickNumber").unwrap_or(0) + 1,
+            cards: id_list(array(data, "DraftPack")),
+            timestamp,
+        }),
+        Some("Completed") => events.push(ArenaEvent::DraftCompleted {
+            draft_id: str_field(data, "EventName"),
+            timestamp,
+        }),
+        _ => {
+            if data.get("IsPickingCompleted").and_then(Value::as_bool) == Some(true) {
+                events.push(ArenaEvent::DraftCompleted {
+                    draft_id: str_field(data, "DraftId"),
+                    timestamp,
+                });
+            }
+        }
+    }
+}
+
+fn arena_deck(data: &Value) -> Option<ArenaDeck> {
+    let summary = data
+        .get("Summary")
+        .or_else(|| data.get("CourseDeckSummary"));
+    let deck = data
+        .get("Deck")
+        .or_else(|| data.get("CourseDeck"))
+        .unwrap_or(data);
+    let main = deck_entries(deck.get("MainDeck")?);
+    let sideboard = deck.get("Sideboard").map(deck_entries).unwrap_or_default();
+
+    let attribute = |name: &str| {
+        summary
+            .map(|summary| array(summary, "Attributes"))
+            .unwrap_or_default()
+            .iter()
+            .find(|attribute| attribute.get("name").and_then(Value::as_str) == Some(name))
+            .and_then(|attribute| str_field(attribute, "value"))
+    };
+
+    Some(ArenaDeck {
+        id: summary.and_then(|summary| str_field(summary, "DeckId")),
+        name: summary.and_then(|summary| str_field(summary, "Name")),
+        format: attribute("Format"),
+        event: str_field(data, "EventName"),
+        main,
+        sideboard,
+    })
+}
+
+/// Deck lists are either `[{"cardId": .., "quantity": ..}]` or flat `[id, quantity, ..]` arrays
+fn deck_entries(cards: &Value) -> Vec<DeckEntry> {
+    let Some(cards) = cards.as_array() else {
+        return Vec::new();
+    };
+    if cards.iter().all(Value::is_number) {
+        return cards
+            .chunks(2)
+            .filter_map(|pair| match pair {
+                [id, quantity] => Some(DeckEntry {
+                    grp_id: id.as_u64()? as u32,
+                    quantity: quantity.as_u64()? as u32,
+                }),
+                _ => None,
+            })
+            .collect();
+    }
+    cards
+        .iter()
+        .filter_map(|card| {
+            Some(DeckEntry {
+                grp_id: u32_field(card, "cardId")?,
+                quantity: u32_field(card, "quantity").unwrap_or(1),
+            })
+        })
+        .collect()
+}
+
+/// Group a list with one grpId per copy, keeping the first-seen order
+fn grp_id_entries(ids: &[Value]) -> Vec<DeckEntry> {
+    let mut entries: Vec<DeckEntry> = Vec::new();
+    for grp_id in id_list(ids) {
+        match entries.iter_mut().find(|entry| entry.grp_id == grp_id) {
+            Some(entry) => entry.quantity += 1,
+            None => entries.push(DeckEntry {
+                grp_id,
+                quantity: 1,
+            }),
+        }
+    }
+    entries
+}
+
+fn game_result(result: &Value) -> GameResult {
+    GameResult {
+        scope: str_field(result, "scope")
+            .map(|scope| strip_prefix(&scope, "MatchScope_").to_lowercase())
+            .unwrap_or_default(),
+        winning_team: u32_field(result, "winningTeamId"),
+        reason: str_field(result, "reason").map(|reason| strip_prefix(&reason, "ResultReason_")),
+    }
+}
+
+fn timer_state(timer: &Value) -> Timer {
+    let seconds = |key: &str| {
+        timer
+            .get(key)
+            .and_then(Value::as_f64)
+            .map(|value| value as u32)
+    };
+    Timer {
+        kind: str_field(timer, "type")
+            .map(|kind| strip_prefix(&kind, "TimerType_"))
+            .unwrap_or_default(),
+        running: timer
+            .get("running")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        duration_sec: seconds("durationSec").unwrap_or(0),
+        elapsed_sec: seconds("elapsedSec").unwrap_or(0),
+        warning_sec: seconds("warningThresholdSec"),
+    }
+}
+
+fn rank(data: &Value, prefix: &str) -> Option<Rank> {
+    let class = str_field(data, &format!("{prefix}Class"))?;
+    Some(Rank {
+        class,
+        level: u32_field(data, &format!("{prefix}Level")),
+        step: u32_field(data, &format!("{prefix}Step")),
+        won: u32_field(data, &format!("{prefix}MatchesWon")).unwrap_or(0),
+        lost: u32_field(data, &format!("{prefix}MatchesLost")).unwrap_or(0),
+        drawn: u32_field(data, &format!("{prefix}MatchesDrawn")).unwrap_or(0),
+    })
+}
+
+/// Requests wrap their body in a JSON string under `request` (or `Payload` for responses)
+fn unwrap_request(payload: &Value) -> Value {
+    for key in ["request", "Payload", "payload"] {
+        if let Some(text) = payload.get(key).and_then(Value::as_str) {
+            if let Ok(inner) = serde_json::from_str(text) {
+                return inner;
+            }
+        }
+    }
+    payload.clone()
+}
+
+/// Annotation detail values: `{"key": .., "valueInt32": [..]}` or `{"key": .., "valueString": [..]}`
+fn detail<'a>(annotation: &'a Value, key: &str) -> Option<&'a Value> {
+    annotation
+        .get("details")?
+        .as_array()?
+        .iter()
+        .find(|detail| detail.get("key").and_then(Value::as_str) == Some(key))
+}
+
+fn detail_int(annotation: &Value, key: &str) -> Option<i64> {
+    detail(annotation, key)?
+        .get("valueInt32")?
+        .as_array()?
+        .first()?
+        .as_i64()
+}
+
+fn detail_str(annotation: &Value, key: &str) -> Option<String> {
+    detail(annotation, key)?
+        .get("valueString")?
+        .as_array()?
+        .first()?
+        .as_str()
+        .map(str::to_string)
+}
+
+fn array<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn id_list(values: &[Value]) -> Vec<u32> {
+    values
+        .iter()
+        .filter_map(|value| match value {
+            Value::Number(number) => number.as_u64().map(|id| id as u32),
+            Value::String(text) => text.parse().ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+fn str_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key)?.as_str().map(str::to_string)
+}
+
+/// Numbers are sometimes logged as strings
+fn u32_field(value: &Value, key: &str) -> Option<u32> {
+    match value.get(key)? {
+        Value::Number(number) => number.as_u64().map(|number| number as u32),
+        Value::String(text) => text.parse().ok(),
+        _ => None,
+    }
+}
+
+fn strip_prefix(value: &str, prefix: &str) -> String {
+    value.strip_prefix(prefix).unwrap_or(value).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Compare the events of a fixture log with its expected JSON.
+    /// Run with `UPDATE_GOLDEN=1` to rewrite the expected file after an intended change.
+    fn assert_golden(log: &str, expected: &str, golden_file: &str) {
+        let actual = serde_json::to_string_pretty(&parse_log(log)).unwrap() + "\n";
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("src/companion/fixtures")
+                .join(golden_file);
+            std::fs::write(path, &actual).unwrap();
+            return;
+        }
+        assert_eq!(
+            actual, expected,
+            "events differ from fixtures/{golden_file}"
+        );
+    }
+
+    #[test]
+    fn test_match_log_golden() {
+        assert_golden(
+            include_str!("fixtures/match.log"),
+            include_str!("fixtures/match.json"),
+            "match.json",
+        );
+    }
+
+    #[test]
+    fn test_draft_log_golden() {
+        assert_golden(
+            include_str!("fixtures/draft.log"),
+            include_str!("fixtures/draft.json"),
+            "draft.json",
+        );
+    }
+
+    #[test]
+    fn test_line_by_line_matches_whole_log() {
+        let log = include_str!("fixtures/match.log");
+        let mut parser = ArenaParser::new();
+        let mut events = Vec::new();
+        for line in log.lines() {
+            events.extend(parser.push_line(line));
+        }
+        assert_eq!(events, parse_log(log));
+        assert_eq!(parser.local_seat(), Some(1));
+    }
+
+    #[test]
+    fn test_game_over_reported_once() {
+        let events = parse_log(include_str!("fixtures/match.log"));
+        let game_overs = events
+            .iter()
+            .filter(|event| matches!(event, ArenaEvent::GameOver { .. }))
+            .count();
+        assert_eq!(game_overs, 1);
+    }
+
+    #[test]
+    fn test_deck_entries_formats() {
+        let objects =
+            serde_json::json!([{"cardId": 1, "quantity": 4}, {"cardId": 2, "quantity": 1}]);
+        let flat = serde_json::json!([1, 4, 2, 1]);
+        assert_eq!(deck_entries(&objects), deck_entries(&flat));
+        assert_eq!(
+            grp_id_entries(&[2.into(), 1.into(), 2.into()]),
+            vec![
+                DeckEntry {
+                    grp_id: 2,
+                    quantity: 2
+                },
+                DeckEntry {
+                    grp_id: 1,
+                    quantity: 1
+                },
+            ]
+        );
+    }
+}
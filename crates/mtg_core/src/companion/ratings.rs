@@ -0,0 +1,460 @@
+use color_eyre::{eyre::eyre, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+const NAME_COLUMNS: &[&str] = &["name", "card name", "card"];
+const SET_COLUMNS: &[&str] = &["set", "expansion", "set code"];
+const GRP_ID_COLUMNS: &[&str] = &[
+    "grpid", "grp id", "arena id", "arena_id", "mtga id", "mtga_id",
+];
+const COLOR_COLUMNS: &[&str] = &["color", "colors", "colour"];
+const RARITY_COLUMNS: &[&str] = &["rarity"];
+const ALSA_COLUMNS: &[&str] = &["alsa", "avg seen"];
+const ATA_COLUMNS: &[&str] = &["ata", "avg taken"];
+const GIH_WR_COLUMNS: &[&str] = &["gih wr", "gih_wr", "games in hand wr"];
+const GIH_GAMES_COLUMNS: &[&str] = &["# gih", "gih games"];
+const GP_WR_COLUMNS: &[&str] = &["gp wr", "gp_wr", "games played wr"];
+const IWD_COLUMNS: &[&str] = &["iwd", "improvement when drawn"];
+
+/// Score points a card loses at full commitment when none of its colors are in our lane
+const OFF_COLOR_PENALTY: f64 = 4.0;
+/// Score points a card gains at full commitment when it is in our lane
+const ON_COLOR_BONUS: f64 = 1.0;
+/// Picks after which the lane counts fully, before that it is still open
+const COMMITTED_AFTER: f64 = 10.0;
+/// Games in hand below which a win rate is flagged as unreliable
+const SMALL_SAMPLE: u32 = 500;
+
+/// Draft metrics of one card, as exported by 17lands and similar sites
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CardRating {
+    pub name: String,
+    pub set: Option<String>,
+    pub grp_id: Option<u32>,
+    /// Color letters, e.g. `WB`, empty for colorless cards
+    pub colors: String,
+    pub rarity: Option<String>,
+    /// Average pick at which the card was last seen
+    pub alsa: Option<f64>,
+    /// Average pick at which the card was taken
+    pub ata: Option<f64>,
+    /// Win rate of games with the card in hand, in percent
+    pub gih_wr: Option<f64>,
+    pub gih_games: Option<u32>,
+    /// Win rate of games with the card in the deck, in percent
+    pub gp_wr: Option<f64>,
+    /// Improvement when drawn, in percentage points
+    pub iwd: Option<f64>,
+}
+
+impl CardRating {
+    /// The win rate the pick order is based on, games in hand first
+    pub fn win_rate(&self) -> Option<f64> {
+        self.gih_wr.or(self.gp_wr)
+    }
+}
+
+/// Card ratings loaded from one or more files
+#[derive(Debug, Clone, Default)]
+pub struct CardRatings {
+    cards: Vec<CardRating>,
+    by_name: HashMap<String, Vec<usize>>,
+    by_grp_id: HashMap<u32, usize>,
+}
+
+impl CardRatings {
+    /// Parse a ratings CSV.
+    ///
+    /// Columns are found by their header so 17lands card data exports work as they are; only
+    /// the name is required. Rows without a set column get `default_set`, e.g. taken from
+    /// the file name.
+    pub fn parse_csv(content: &str, default_set: Option<&str>) -> Result<Self> {
+        let mut ratings = Self::default();
+        ratings.extend_csv(content, default_set)?;
+        Ok(ratings)
+    }
+
+    /// Add the ratings of another CSV, e.g. of a second set
+    pub fn extend_csv(&mut self, content: &str, default_set: Option<&str>) -> Result<()> {
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(content.as_bytes());
+
+        let headers: Vec<String> = reader
+            .headers()
+            .map_err(|e| eyre!("Failed to read ratings header: {}", e))?
+            .iter()
+            .map(|header| header.to_lowercase())
+            .collect();
+        let column = |names: &[&str]| {
+            names
+                .iter()
+                .find_map(|name| headers.iter().position(|header| header == name))
+        };
+
+        let name_column = column(NAME_COLUMNS)
+            .ok_or_else(|| eyre!("Ratings have no card name column (expected Name)"))?;
+        let set_column = column(SET_COLUMNS);
+        let grp_id_column = column(GRP_ID_COLUMNS);
+        let color_column = column(COLOR_COLUMNS);
+        let rarity_column = column(RARITY_COLUMNS);
+        let alsa_column = column(ALSA_COLUMNS);
+        let ata_column = column(ATA_COLUMNS);
+        let gih_wr_column = column(GIH_WR_COLUMNS);
+        let gih_games_column = column(GIH_GAMES_COLUMNS);
+        let gp_wr_column = column(GP_WR_COLUMNS);
+        let iwd_column = column(IWD_COLUMNS);
+
+        for (index, record) in reader.records().enumerate() {
+            let record =
+                record.map_err(|e| eyre!("Failed to read ratings row {}: {}", index + 2, e))?;
+            let field = |column: Option<usize>| {
+                column
+                    .and_then(|column| record.get(column))
+                    .filter(|value| !value.is_empty())
+            };
+
+            let Some(name) = field(Some(name_column)) else {
+                continue;
+            };
+            self.push(CardRating {
+                name: name.to_string(),
+                set: field(set_column)
+                    .or(default_set)
+                    .map(|set| set.to_uppercase()),
+                grp_id: field(grp_id_column).and_then(|id| id.parse().ok()),
+                colors: field(color_column)
+                    .map(|colors| {
+                        colors
+                            .to_uppercase()
+                            .chars()
+                            .filter(|c| "WUBRG".contains(*c))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                rarity: field(rarity_column).map(str::to_string),
+                alsa: field(alsa_column).and_then(parse_number),
+                ata: field(ata_column).and_then(parse_number),
+                gih_wr: field(gih_wr_column).and_then(parse_percent),
+                gih_games: field(gih_games_column).and_then(|games| games.parse().ok()),
+                gp_wr: field(gp_wr_column).and_then(parse_percent),
+                iwd: field(iwd_column).and_then(parse_number),
+            });
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, rating: CardRating) {
+        let index = self.cards.len();
+        self.by_name
+            .entry(rating.name.to_lowercase())
+            .or_default()
+            .push(index);
+        if let Some(grp_id) = rating.grp_id {
+            self.by_grp_id.insert(grp_id, index);
+        }
+        self.cards.push(rating);
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// Rating of a card by grpId, or else by name, preferring the given set
+    pub fn find(&self, grp_id: u32, name: Option<&str>, set: Option<&str>) -> Option<&CardRating> {
+        if let Some(index) = self.by_grp_id.get(&grp_id) {
+            return Some(&self.cards[*index]);
+        }
+        let candidates = self.by_name.get(&name?.to_lowercase())?;
+        candidates
+            .iter()
+            .map(|index| &self.cards[*index])
+            .find(|rating| {
+                set.is_some_and(|set| rating.set.as_deref() == Some(set.to_uppercase().as_str()))
+            })
+            .or_else(|| candidates.last().map(|index| &self.cards[*index]))
+    }
+
+    /// Average win rate of the rated cards of a set, all cards when the set is unknown
+    pub fn average_win_rate(&self, set: Option<&str>) -> Option<f64> {
+        let set = set.map(str::to_uppercase);
+        let in_set = |rating: &&CardRating| {
+            set.is_none() || rating.set.is_none() || rating.set.as_deref() == set.as_deref()
+        };
+        let rates: Vec<f64> = self
+            .cards
+            .iter()
+            .filter(in_set)
+            .filter_map(CardRating::win_rate)
+            .collect();
+        if rates.is_empty() {
+            return None;
+        }
+        Some(rates.iter().sum::<f64>() / rates.len() as f64)
+    }
+}
+
+/// `58.3%` and `0.583` both read as 58.3
+fn parse_percent(value: &str) -> Option<f64> {
+    let number: f64 = value.trim_end_matches('%').trim().parse().ok()?;
+    if !value.ends_with('%') && number.abs() <= 1.0 {
+        Some(number * 100.0)
+    } else {
+        Some(number)
+    }
+}
+
+/// Numbers with an optional unit, e.g. `4.2pp` or `2.41`
+fn parse_number(value: &str) -> Option<f64> {
+    value
+        .trim_end_matches(|c: char| c.is_alphabetic() || c == '%')
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// How far the picks so far lean towards each color
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ColorCommitment {
+    /// Weight per color letter, better cards count more
+    pub weights: BTreeMap<char, f64>,
+    pub picks: usize,
+}
+
+impl ColorCommitment {
+    /// Weigh the colored cards picked so far by how much better than average they are
+    pub fn from_picks<'a>(picks: impl Iterator<Item = &'a CardRating>, average: f64) -> Self {
+        let mut commitment = Self::default();
+        for rating in picks {
+            commitment.picks += 1;
+            let colors = rating.colors.chars().count();
+            if colors == 0 {
+                continue;
+            }
+            let quality = rating
+                .win_rate()
+                .map(|rate| (rate - average + 3.0).max(0.5))
+                .unwrap_or(1.0);
+            for color in rating.colors.chars() {
+                *commitment.weights.entry(color).or_default() += quality / colors as f64;
+            }
+        }
+        commitment
+    }
+
+    /// The two colors we're leaning towards, strongest first
+    pub fn lane(&self) -> Vec<char> {
+        let mut colors: Vec<(char, f64)> = self
+            .weights
+            .iter()
+            .map(|(color, weight)| (*color, *weight))
+            .collect();
+        colors.sort_by(|a, b| b.1.total_cmp(&a.1));
+        colors.into_iter().take(2).map(|(color, _)| color).collect()
+    }
+
+    /// From 0 (wide open) to 1 (settled): grows with the picks and how much of the weight
+    /// the lane holds
+    pub fn strength(&self) -> f64 {
+        let total: f64 = self.weights.values().sum();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        let lane: f64 = self.lane().iter().map(|color| self.weights[color]).sum();
+        (self.picks as f64 / COMMITTED_AFTER).min(1.0) * lane / total
+    }
+}
+
+/// A card of the pack with its score and why it got it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PickSuggestion {
+    pub grp_id: u32,
+    pub name: Option<String>,
+    /// Win rate adjusted for our colors, `None` for unrated cards
+    pub score: Option<f64>,
+    pub reasons: Vec<String>,
+}
+
+/// Ranked picks for a pack
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PickAdvice {
+    pub commitment: ColorCommitment,
+    /// Best pick first, unrated cards last
+    pub suggestions: Vec<PickSuggestion>,
+}
+
+/// Rank the cards of a pack by their rating, adjusted for the colors of the cards picked so far
+pub fn suggest_picks(
+    pack: &[u32],
+    picked: &[u32],
+    names: &HashMap<u32, String>,
+    ratings: &CardRatings,
+    set: Option<&str>,
+) -> PickAdvice {
+    let rating = |grp_id: &u32| ratings.find(*grp_id, names.get(grp_id).map(String::as_str), set);
+    let average = ratings.average_win_rate(set).unwrap_or(55.0);
+    let commitment = ColorCommitment::from_picks(picked.iter().filter_map(rating), average);
+    let lane = commitment.lane();
+    let strength = commitment.strength();
+    let lane_label: String = lane.iter().collect();
+
+    let mut suggestions: Vec<PickSuggestion> = pack
+        .iter()
+        .map(|grp_id| {
+            let name = names.get(grp_id).cloned();
+            let Some(card) = rating(grp_id) else {
+                return PickSuggestion {
+                    grp_id: *grp_id,
+                    name,
+                    score: None,
+                    reasons: vec!["no rating".to_string()],
+                };
+            };
+
+            let mut reasons = Vec::new();
+            let mut score = match card.win_rate() {
+                Some(rate) => {
+                    let metric = if card.gih_wr.is_some() {
+                        "GIH WR"
+                    } else {
+                        "GP WR"
+                    };
+                    reasons.push(format!(
+                        "{metric} {rate:.1}% ({:+.1} vs average)",
+                        rate - average
+                    ));
+                    rate
+                }
+                None => average,
+            };
+            if card.gih_games.is_some_and(|games| games < SMALL_SAMPLE) {
+                reasons.push("small sample".to_string());
+            }
+            if let Some(alsa) = card.alsa {
+                if alsa <= 3.0 {
+                    reasons.push(format!("ALSA {alsa:.1}, rarely wheels"));
+                } else if alsa >= 7.0 {
+                    reasons.push(format!("ALSA {alsa:.1}, may come back"));
+                }
+            }
+            if let Some(iwd) = card.iwd.filter(|iwd| *iwd >= 3.0) {
+                reasons.push(format!("IWD {iwd:+.1}pp"));
+            }
+
+            if card.colors.is_empty() {
+                reasons.push("colorless, fits any deck".to_string());
+            } else if !lane.is_empty() && strength > 0.0 {
+                let colors = card.colors.chars().count() as f64;
+                let fit = card.colors.chars().filter(|c| lane.contains(c)).count() as f64 / colors;
+                if fit >= 1.0 {
+                    score += ON_COLOR_BONUS * strength;
+                    reasons.push(format!("in your colors ({lane_label})"));
+                } else {
+                    score -= OFF_COLOR_PENALTY * strength * (1.0 - fit);
+                    let off = if fit > 0.0 { "partly off" } else { "off" };
+                    reasons.push(format!("{off} your colors ({lane_label})"));
+                }
+            }
+
+            PickSuggestion {
+                grp_id: *grp_id,
+                name: name.or_else(|| Some(card.name.clone())),
+                score: Some(score),
+                reasons,
+            }
+        })
+        .collect();
+    suggestions.sort_by(|a, b| match (a.score, b.score) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+
+    PickAdvice {
+        commitment,
+        suggestions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATINGS: &str = include_str!("fixtures/ratings.csv");
+
+    fn names(cards: &[(u32, &str)]) -> HashMap<u32, String> {
+        cards
+            .iter()
+            .map(|(grp_id, name)| (*grp_id, name.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_ratings() {
+        let ratings = CardRatings::parse_csv(RATINGS, Some("blb")).unwrap();
+        assert_eq!(ratings.len(), 6);
+        let hero = ratings
+            .find(1, Some("heartfire hero"), Some("BLB"))
+            .unwrap();
+        assert_eq!(hero.colors, "R");
+        assert_eq!(hero.set.as_deref(), Some("BLB"));
+        assert_eq!(hero.gih_wr, Some(59.4));
+        assert_eq!(hero.iwd, Some(2.6));
+        assert_eq!(hero.alsa, Some(2.41));
+        assert_eq!(ratings.find(1, Some("Short Bow"), None).unwrap().colors, "");
+        assert!(ratings.find(1, Some("Unknown Card"), None).is_none());
+        assert_eq!(parse_percent("0.583"), Some(58.3));
+    }
+
+    #[test]
+    fn test_suggestions_follow_colors() {
+        let ratings = CardRatings::parse_csv(RATINGS, Some("BLB")).unwrap();
+        let names = names(&[
+            (1, "Heartfire Hero"),
+            (2, "Salvation Swan"),
+            (3, "Fell"),
+            (4, "Thought Shucker"),
+            (5, "Seasoned Warrenguard"),
+            (6, "Short Bow"),
+        ]);
+        let pack = [4, 2, 5, 99];
+
+        // Wide open, the best card wins
+        let open = suggest_picks(&pack, &[], &names, &ratings, Some("BLB"));
+        assert_eq!(open.suggestions[0].name.as_deref(), Some("Salvation Swan"));
+        assert_eq!(open.suggestions[3].grp_id, 99);
+        assert_eq!(open.suggestions[3].score, None);
+
+        // Deep in black and red, the white rare loses points for its color
+        let picked = [1, 3, 1, 3, 1, 3, 1, 3, 1, 3, 6];
+        let advice = suggest_picks(&pack, &picked, &names, &ratings, Some("BLB"));
+        let mut lane = advice.commitment.lane();
+        lane.sort();
+        assert_eq!(lane, vec!['B', 'R']);
+        assert!(advice.commitment.strength() > 0.9);
+        let swan = advice
+            .suggestions
+            .iter()
+            .find(|suggestion| suggestion.grp_id == 2)
+            .unwrap();
+        assert!(swan.score.unwrap() < 60.0);
+        assert!(swan
+            .reasons
+            .iter()
+            .any(|reason| reason.starts_with("off your colors")));
+
+        let fell_pack = [3, 2];
+        let advice = suggest_picks(&fell_pack, &picked, &names, &ratings, Some("BLB"));
+        assert_eq!(advice.suggestions[0].name.as_deref(), Some("Fell"));
+        assert!(advice.suggestions[0]
+            .reasons
+            .iter()
+            .any(|reason| reason.starts_with("in your colors")));
+    }
+}
@@ -0,0 +1,193 @@
+use super::events::{ArenaEvent, MatchPlayer, SeatId};
+use super::log::{LogEntry, LogReader};
+use super::parser::ArenaParser;
+use super::state::GameState;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// One game of a match as the sequence of states the GRE sent, one per `gameStateId`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GameReplay {
+    pub game_number: u32,
+    pub snapshots: Vec<GameState>,
+}
+
+impl GameReplay {
+    /// The state at the end of each turn, starting with the opening hands as turn 0
+    pub fn turns(&self) -> Vec<&GameState> {
+        let mut turns: Vec<&GameState> = Vec::new();
+        for snapshot in &self.snapshots {
+            match turns.last_mut() {
+                Some(last) if last.turn == snapshot.turn => *last = snapshot,
+                _ => turns.push(snapshot),
+            }
+        }
+        turns
+    }
+
+    /// The state as of a `gameStateId`, or the last one before it
+    pub fn at(&self, state_id: u32) -> Option<&GameState> {
+        self.snapshots
+            .iter()
+            .take_while(|snapshot| snapshot.state_id <= state_id)
+            .last()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MatchReplay {
+    /// Empty when the log starts in the middle of a match
+    pub match_id: String,
+    pub event_id: Option<String>,
+    pub players: Vec<MatchPlayer>,
+    pub local_seat: Option<SeatId>,
+    pub games: Vec<GameReplay>,
+}
+
+impl MatchReplay {
+    pub fn player_name(&self, seat: SeatId) -> Option<&str> {
+        self.players
+            .iter()
+            .find(|player| player.seat == seat)
+            .map(|player| player.name.as_str())
+    }
+}
+
+/// Rebuild every match of a log as game state snapshots
+pub fn replay_matches(log: &str) -> Vec<MatchReplay> {
+    let mut reader = LogReader::new();
+    let mut parser = ArenaParser::new();
+    let mut matches: Vec<MatchReplay> = Vec::new();
+
+    for line in log.lines() {
+        let Some(entry) = reader.push_line(line) else {
+            continue;
+        };
+        for entry in split_gre_messages(entry) {
+            let events = parser.parse_entry(&entry);
+            for event in &events {
+                match event {
+                    ArenaEvent::MatchStarted {
+                        match_id,
+                        event_id,
+                        players,
+                        ..
+                    } => {
+                        if matches.last().map(|replay| &replay.match_id) != Some(match_id) {
+                            matches.push(MatchReplay {
+                                match_id: match_id.clone(),
+                                ..Default::default()
+                            });
+                        }
+                        let replay = matches.last_mut().expect("match was just pushed");
+                        replay.event_id = event_id.clone();
+                        replay.players = players.clone();
+                    }
+                    ArenaEvent::GameStarted { game_number, .. } => {
+                        if matches.is_empty() {
+                            matches.push(MatchReplay {
+                                match_id: parser.match_id().unwrap_or_default().to_string(),
+                                ..Default::default()
+                            });
+                        }
+                        let replay = matches.last_mut().expect("a match exists");
+                        replay.games.push(GameReplay {
+                            game_number: *game_number,
+                            snapshots: Vec::new(),
+                        });
+                    }
+                    _ => {}
+                }
+            }
+
+            let state = parser.game_state();
+            let Some(replay) = matches.last_mut() else {
+                continue;
+            };
+            if replay.local_seat.is_none() {
+                replay.local_seat = parser.local_seat();
+            }
+            let Some(game) = replay.games.last_mut() else {
+                continue;
+            };
+            match game.snapshots.last_mut() {
+                _ if state.state_id == 0 || state.game_number != game.game_number => {}
+                Some(last) if last.state_id == state.state_id => *last = state.clone(),
+                _ => game.snapshots.push(state.clone()),
+            }
+        }
+    }
+
+    matches.retain(|replay| !replay.games.is_empty());
+    matches
+}
+
+/// A `GreToClientEvent` can carry several game state messages; hand them to the parser one at
+/// a time so that every `gameStateId` gets its own snapshot
+fn split_gre_messages(entry: LogEntry) -> Vec<LogEntry> {
+    if entry.name != "GreToClientEvent" {
+        return vec![entry];
+    }
+    let event = entry
+        .payload
+        .get("greToClientEvent")
+        .unwrap_or(&entry.payload);
+    let messages = event
+        .get("greToClientMessages")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    if messages.len() <= 1 {
+        return vec![entry];
+    }
+    messages
+        .into_iter()
+        .map(|message| LogEntry {
+            name: entry.name.clone(),
+            timestamp: entry.timestamp,
+            payload: json!({ "greToClientEvent": { "greToClientMessages": [message] } }),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::companion::events::Zone;
+
+    #[test]
+    fn test_replay_fixture_match() {
+        let matches = replay_matches(include_str!("fixtures/match.log"));
+        assert_eq!(matches.len(), 1);
+        let replay = &matches[0];
+        assert!(!replay.match_id.is_empty());
+        assert!(replay.local_seat.is_some());
+        assert_eq!(replay.games.len(), 1);
+
+        let game = &replay.games[0];
+        let ids: Vec<u32> = game.snapshots.iter().map(|s| s.state_id).collect();
+        let mut sorted = ids.clone();
+        sorted.dedup();
+        assert_eq!(ids, sorted, "one snapshot per state id");
+
+        let turns = game.turns();
+        assert!(turns.len() > 1);
+        assert!(turns.windows(2).all(|pair| pair[0].turn < pair[1].turn));
+
+        // Every visible object sits in a zone that lists it
+        for snapshot in &game.snapshots {
+            for zone in snapshot.zones.values() {
+                for id in &zone.object_ids {
+                    if let Some(object) = snapshot.objects.get(id) {
+                        assert_eq!(object.zone_id, zone.zone_id);
+                    }
+                }
+            }
+        }
+
+        let last = game.snapshots.last().unwrap();
+        let local = replay.local_seat.unwrap();
+        assert!(!last.objects_in(Zone::Battlefield, local).is_empty());
+        assert_eq!(game.at(last.state_id), Some(last));
+    }
+}
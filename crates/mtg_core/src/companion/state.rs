@@ -0,0 +1,354 @@
+use super::events::{SeatId, Zone};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// A game object (card, token, ability on the stack) as the GRE last described it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameObject {
+    pub instance_id: u32,
+    pub grp_id: u32,
+    /// GRE object type without its prefix, e.g. `Card`, `Token` or `Ability`
+    pub kind: String,
+    pub zone_id: u32,
+    pub owner: SeatId,
+    pub controller: SeatId,
+    /// Card types without their prefix, e.g. `Creature` or `Land`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub card_types: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toughness: Option<i32>,
+    #[serde(default)]
+    pub tapped: bool,
+    #[serde(default)]
+    pub attacking: bool,
+    #[serde(default)]
+    pub blocking: bool,
+}
+
+impl GameObject {
+    pub fn is_land(&self) -> bool {
+        self.card_types.iter().any(|kind| kind == "Land")
+    }
+
+    pub fn is_creature(&self) -> bool {
+        self.card_types.iter().any(|kind| kind == "Creature")
+    }
+}
+
+/// A zone and the instance ids it holds, visible or not
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ZoneState {
+    pub zone_id: u32,
+    pub zone: Zone,
+    /// Shared zones (battlefield, stack, exile) have no owner
+    pub owner: Option<SeatId>,
+    pub object_ids: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlayerState {
+    pub seat: SeatId,
+    pub life: i32,
+    #[serde(default)]
+    pub mulligans: u32,
+}
+
+/// The game as of one game state message, built by applying the GRE's full states and diffs.
+///
+/// Diffs only carry what changed: zones are sent whole, objects and players are replaced
+/// one by one, and `diffDeletedInstanceIds` lists the objects that left the game (or changed
+/// id). [`GameState::apply`] keeps a consistent snapshot that can be cloned per state id.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameState {
+    /// `gameStateId` of the last applied message
+    pub state_id: u32,
+    pub game_number: u32,
+    pub turn: u32,
+    pub active_seat: SeatId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phase: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step: Option<String>,
+    pub players: BTreeMap<SeatId, PlayerState>,
+    pub zones: BTreeMap<u32, ZoneState>,
+    pub objects: BTreeMap<u32, GameObject>,
+    /// Objects deleted by the last message; its annotations may still refer to them
+    #[serde(skip)]
+    deleted: HashMap<u32, GameObject>,
+}
+
+impl GameState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply a `gameStateMessage`, full or diff
+    pub fn apply(&mut self, message: &Value) {
+        self.deleted.clear();
+
+        if message.get("type").and_then(Value::as_str) == Some("GameStateType_Full") {
+            self.zones.clear();
+            self.objects.clear();
+        }
+        if let Some(id) = u32_field(message, "gameStateId") {
+            self.state_id = id;
+        }
+        if let Some(number) = message
+            .get("gameInfo")
+            .and_then(|info| u32_field(info, "gameNumber"))
+        {
+            self.game_number = number;
+        }
+
+        let mut sent_zones = Vec::new();
+        for zone in array(message, "zones") {
+            let Some(zone_id) = u32_field(zone, "zoneId") else {
+                continue;
+            };
+            sent_zones.push(zone_id);
+            self.zones.insert(
+                zone_id,
+                ZoneState {
+                    zone_id,
+                    zone: Zone::from_gre(zone.get("type").and_then(Value::as_str).unwrap_or("")),
+                    owner: u32_field(zone, "ownerSeatId"),
+                    object_ids: array(zone, "objectInstanceIds")
+                        .iter()
+                        .filter_map(|id| id.as_u64().map(|id| id as u32))
+                        .collect(),
+                },
+            );
+        }
+
+        for object in array(message, "gameObjects") {
+            let Some(instance_id) = u32_field(object, "instanceId") else {
+                continue;
+            };
+            let previous = self.objects.get(&instance_id);
+            let owner = u32_field(object, "ownerSeatId")
+                .or(previous.map(|previous| previous.owner))
+                .unwrap_or(0);
+            let stat = |key: &str| {
+                object
+                    .get(key)
+                    .and_then(|stat| stat.get("value"))
+                    .and_then(Value::as_i64)
+                    .map(|value| value as i32)
+            };
+            let object = GameObject {
+                instance_id,
+                grp_id: u32_field(object, "grpId").unwrap_or(0),
+                kind: str_field(object, "type")
+                    .map(|kind| strip(&kind, "GameObjectType_"))
+                    .unwrap_or_default(),
+                zone_id: u32_field(object, "zoneId").unwrap_or(0),
+                owner,
+                controller: u32_field(object, "controllerSeatId").unwrap_or(owner),
+                card_types: array(object, "cardTypes")
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(|kind| strip(kind, "CardType_"))
+                    .collect(),
+                power: stat("power"),
+                toughness: stat("toughness"),
+                tapped: object.get("isTapped").and_then(Value::as_bool) == Some(true),
+                attacking: str_field(object, "attackState").as_deref()
+                    == Some("AttackState_Attacking"),
+                blocking: str_field(object, "blockState").as_deref() == Some("BlockState_Blocking"),
+            };
+            // Zones the message left out still have to list the objects that moved in or out
+            if !sent_zones.contains(&object.zone_id) {
+                for zone in self.zones.values_mut() {
+                    if sent_zones.contains(&zone.zone_id) {
+                        continue;
+                    }
+                    if zone.zone_id == object.zone_id {
+                        if !zone.object_ids.contains(&instance_id) {
+                            zone.object_ids.push(instance_id);
+                        }
+                    } else {
+                        zone.object_ids.retain(|id| *id != instance_id);
+                    }
+                }
+            }
+            self.objects.insert(instance_id, object);
+        }
+
+        for player in array(message, "players") {
+            let Some(seat) = u32_field(player, "systemSeatNumber") else {
+                continue;
+            };
+            let state = self.players.entry(seat).or_insert(PlayerState {
+                seat,
+                ..Default::default()
+            });
+            if let Some(life) = player.get("lifeTotal").and_then(Value::as_i64) {
+                state.life = life as i32;
+            }
+            if let Some(mulligans) = u32_field(player, "mulliganCount") {
+                state.mulligans = mulligans;
+            }
+        }
+
+        if let Some(turn_info) = message.get("turnInfo") {
+            if let Some(turn) = u32_field(turn_info, "turnNumber") {
+                self.turn = turn;
+            }
+            if let Some(seat) = u32_field(turn_info, "activePlayer") {
+                self.active_seat = seat;
+            }
+            if let Some(phase) = str_field(turn_info, "phase") {
+                self.phase = Some(strip(&phase, "Phase_"));
+                self.step = str_field(turn_info, "step").map(|step| strip(&step, "Step_"));
+            }
+        }
+
+        for id in array(message, "diffDeletedInstanceIds") {
+            let Some(id) = id.as_u64().map(|id| id as u32) else {
+                continue;
+            };
+            if let Some(object) = self.objects.remove(&id) {
+                self.deleted.insert(id, object);
+            }
+            for zone in self.zones.values_mut() {
+                zone.object_ids.retain(|object_id| *object_id != id);
+            }
+        }
+    }
+
+    /// An object by instance id, including the ones the last message deleted
+    pub fn object(&self, instance_id: u32) -> Option<&GameObject> {
+        self.objects
+            .get(&instance_id)
+            .or_else(|| self.deleted.get(&instance_id))
+    }
+
+    /// The zone of a kind owned by a seat (library, hand, graveyard, ...)
+    pub fn zone(&self, zone: Zone, owner: SeatId) -> Option<&ZoneState> {
+        self.zones
+            .values()
+            .find(|state| state.zone == zone && state.owner == Some(owner))
+    }
+
+    /// Visible objects of a seat's zone, in zone order. For the battlefield (which has no
+    /// owner) these are the permanents the seat controls.
+    pub fn objects_in(&self, zone: Zone, seat: SeatId) -> Vec<&GameObject> {
+        let shared = self
+            .zones
+            .values()
+            .find(|state| state.zone == zone && state.owner.is_none());
+        let (state, controlled) = match (self.zone(zone, seat), shared) {
+            (Some(state), _) => (state, false),
+            (None, Some(state)) => (state, true),
+            (None, None) => return Vec::new(),
+        };
+        state
+            .object_ids
+            .iter()
+            .filter_map(|id| self.objects.get(id))
+            .filter(|object| !controlled || object.controller == seat)
+            .collect()
+    }
+
+    /// Number of cards in a seat's zone, hidden ones included
+    pub fn zone_size(&self, zone: Zone, seat: SeatId) -> usize {
+        self.zone(zone, seat)
+            .map(|state| state.object_ids.len())
+            .unwrap_or(0)
+    }
+}
+
+fn array<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn str_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key)?.as_str().map(str::to_string)
+}
+
+fn u32_field(value: &Value, key: &str) -> Option<u32> {
+    value.get(key)?.as_u64().map(|number| number as u32)
+}
+
+fn strip(value: &str, prefix: &str) -> String {
+    value.strip_prefix(prefix).unwrap_or(value).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn full_state() -> Value {
+        json!({
+            "type": "GameStateType_Full",
+            "gameStateId": 1,
+            "gameInfo": {"gameNumber": 1},
+            "players": [
+                {"systemSeatNumber": 1, "lifeTotal": 20},
+                {"systemSeatNumber": 2, "lifeTotal": 20}
+            ],
+            "turnInfo": {"turnNumber": 1, "activePlayer": 1, "phase": "Phase_Main1"},
+            "zones": [
+                {"zoneId": 28, "type": "ZoneType_Battlefield", "objectInstanceIds": []},
+                {"zoneId": 31, "type": "ZoneType_Hand", "ownerSeatId": 1, "objectInstanceIds": [160, 161]},
+                {"zoneId": 35, "type": "ZoneType_Hand", "ownerSeatId": 2, "objectInstanceIds": [170, 171, 172]}
+            ],
+            "gameObjects": [
+                {"instanceId": 160, "grpId": 87521, "type": "GameObjectType_Card", "zoneId": 31, "ownerSeatId": 1, "cardTypes": ["CardType_Land"]},
+                {"instanceId": 161, "grpId": 90012, "type": "GameObjectType_Card", "zoneId": 31, "ownerSeatId": 1, "cardTypes": ["CardType_Creature"], "power": {"value": 2}, "toughness": {"value": 1}}
+            ]
+        })
+    }
+
+    #[test]
+    fn test_full_state() {
+        let mut state = GameState::new();
+        state.apply(&full_state());
+        assert_eq!(state.state_id, 1);
+        assert_eq!(state.players[&2].life, 20);
+        assert_eq!(state.objects_in(Zone::Hand, 1).len(), 2);
+        // The opponent's hand is hidden but its size is known
+        assert!(state.objects_in(Zone::Hand, 2).is_empty());
+        assert_eq!(state.zone_size(Zone::Hand, 2), 3);
+        assert_eq!(state.phase.as_deref(), Some("Main1"));
+    }
+
+    #[test]
+    fn test_diff_moves_and_deletes_objects() {
+        let mut state = GameState::new();
+        state.apply(&full_state());
+        state.apply(&json!({
+            "type": "GameStateType_Diff",
+            "gameStateId": 2,
+            "zones": [
+                {"zoneId": 28, "type": "ZoneType_Battlefield", "objectInstanceIds": [180]},
+                {"zoneId": 31, "type": "ZoneType_Hand", "ownerSeatId": 1, "objectInstanceIds": [161]}
+            ],
+            "gameObjects": [
+                {"instanceId": 180, "grpId": 87521, "type": "GameObjectType_Card", "zoneId": 28, "ownerSeatId": 1, "controllerSeatId": 1, "cardTypes": ["CardType_Land"], "isTapped": true}
+            ],
+            "players": [{"systemSeatNumber": 2, "lifeTotal": 17}],
+            "diffDeletedInstanceIds": [160]
+        }));
+
+        assert_eq!(state.state_id, 2);
+        let battlefield = state.objects_in(Zone::Battlefield, 1);
+        assert_eq!(battlefield.len(), 1);
+        assert!(battlefield[0].tapped && battlefield[0].is_land());
+        assert!(state.objects_in(Zone::Battlefield, 2).is_empty());
+        assert_eq!(state.objects_in(Zone::Hand, 1).len(), 1);
+        assert_eq!(state.players[&2].life, 17);
+        assert_eq!(state.players[&1].life, 20);
+        // Deleted objects stay reachable for the annotations of the same message
+        assert!(!state.objects.contains_key(&160));
+        assert_eq!(state.object(160).map(|object| object.grp_id), Some(87521));
+    }
+}
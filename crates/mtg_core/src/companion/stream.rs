@@ -0,0 +1,118 @@
+//! Versioned event stream for tools that follow a live game.
+//!
+//! Each event goes out as one JSON object per line (NDJSON):
+//!
+//! ```json
+//! {"schema":1,"seq":27,"category":"life","match_id":"match-0001","event":{"type":"life_changed","seat":2,"from":20,"to":17}}
+//! ```
+//!
+//! - `schema` is [`SCHEMA_VERSION`]. It only changes when a field is removed, renamed or
+//!   changes meaning; new event types and new optional fields keep the version, so consumers
+//!   should ignore what they don't know.
+//! - `seq` counts the events of one stream from 1, a gap means events were dropped.
+//! - `category` is the filter name of the event (`match`, `game`, `life`, `draft`, ...).
+//! - `match_id` is the match the event happened in, absent outside of matches.
+//! - `event` is the [`ArenaEvent`], its `type` tells which one.
+
+use super::events::ArenaEvent;
+use serde::{Deserialize, Serialize};
+
+/// Version of the stream format, see the module documentation for when it changes
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// One line of the event stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamEvent {
+    pub schema: u32,
+    pub seq: u64,
+    pub category: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_id: Option<String>,
+    pub event: ArenaEvent,
+}
+
+impl StreamEvent {
+    /// The event as a single NDJSON line, without the trailing newline
+    pub fn to_line(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+}
+
+/// Numbers the events of a stream
+#[derive(Debug, Default)]
+pub struct EventStream {
+    seq: u64,
+}
+
+impl EventStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn next(&mut self, event: &ArenaEvent, match_id: Option<&str>) -> StreamEvent {
+        self.seq += 1;
+        StreamEvent {
+            schema: SCHEMA_VERSION,
+            seq: self.seq,
+            category: event.category().to_string(),
+            match_id: match_id.map(str::to_string),
+            event: event.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::companion::ArenaParser;
+
+    fn stream_of(log: &str) -> String {
+        let mut parser = ArenaParser::new();
+        let mut stream = EventStream::new();
+        let mut lines = String::new();
+        for line in log.lines() {
+            for event in parser.push_line(line) {
+                let line = stream.next(&event, parser.match_id()).to_line().unwrap();
+                lines.push_str(&line);
+                lines.push('\n');
+            }
+        }
+        lines
+    }
+
+    /// The stream is a public format: any change to this fixture needs a look at
+    /// [`SCHEMA_VERSION`]. Run with `UPDATE_GOLDEN=1` to rewrite it after an intended change.
+    #[test]
+    fn test_match_stream_golden() {
+        let actual = stream_of(include_str!("fixtures/match.log"));
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("src/companion/fixtures/match.ndjson");
+            std::fs::write(path, &actual).unwrap();
+            return;
+        }
+        assert_eq!(
+            actual,
+            include_str!("fixtures/match.ndjson"),
+            "stream differs from fixtures/match.ndjson"
+        );
+    }
+
+    #[test]
+    fn test_stream_round_trip() {
+        let lines = stream_of(include_str!("fixtures/match.log"));
+        let events: Vec<StreamEvent> = lines
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert!(!events.is_empty());
+        for (index, event) in events.iter().enumerate() {
+            assert_eq!(event.schema, SCHEMA_VERSION);
+            assert_eq!(event.seq, index as u64 + 1);
+            assert_eq!(event.category, event.event.category());
+        }
+        assert!(events
+            .iter()
+            .any(|event| event.match_id.as_deref() == Some("match-0001")));
+    }
+}
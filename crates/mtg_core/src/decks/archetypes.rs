@@ -0,0 +1,703 @@
+use crate::decks::meta::deck_colors;
+use crate::decks::ParsedDeck;
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// A named archetype recognized by its signature cards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchetypeDefinition {
+    pub name: String,
+    /// Only match decks of this format
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Deck colors in WUBRG order, e.g. `UR`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub colors: Option<String>,
+    /// Cards that identify the archetype
+    pub signature_cards: Vec<String>,
+}
+
+/// User-editable list of archetype definitions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArchetypeDefinitions {
+    pub archetypes: Vec<ArchetypeDefinition>,
+}
+
+impl ArchetypeDefinitions {
+    /// Load definitions from a JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| eyre!("Failed to read '{}': {}", path.display(), e))?;
+        serde_json::from_str(&content).map_err(|e| {
+            eyre!(
+                "Invalid archetype definitions in '{}': {}",
+                path.display(),
+                e
+            )
+        })
+    }
+
+    /// Load definitions from a file, or no definitions if the file doesn't exist
+    pub fn load_or_default(path: &Path) -> Result<Self> {
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Example definitions written by `mtg decks archetypes --init`
+    pub fn example() -> Self {
+        let definition = |name: &str, colors: &str, cards: &[&str]| ArchetypeDefinition {
+            name: name.to_string(),
+            format: None,
+            colors: Some(colors.to_string()),
+            signature_cards: cards.iter().map(|c| c.to_string()).collect(),
+        };
+
+        Self {
+            archetypes: vec![
+                definition(
+                    "Mono-Red Aggro",
+                    "R",
+                    &["Monastery Swiftspear", "Lightning Bolt", "Play with Fire"],
+                ),
+                definition(
+                    "Izzet Prowess",
+                    "UR",
+                    &["Monastery Swiftspear", "Slickshot Show-Off", "Opt"],
+                ),
+                definition(
+                    "Azorius Control",
+                    "WU",
+                    &["Supreme Verdict", "Memory Deluge", "No More Lies"],
+                ),
+            ],
+        }
+    }
+
+    /// Best matching definition for a deck with its confidence
+    pub fn label(&self, deck: &ParsedDeck) -> Option<(&ArchetypeDefinition, f64)> {
+        let colors = deck_colors(deck, &HashMap::new());
+
+        self.archetypes
+            .iter()
+            .filter(|definition| match (&definition.format, &deck.format) {
+                (Some(wanted), Some(format)) => format.eq_ignore_ascii_case(wanted),
+                _ => true,
+            })
+            .map(|definition| (definition, definition_score(definition, deck, &colors)))
+            .filter(|(_, score)| *score >= MIN_DEFINITION_SCORE)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+}
+
+/// Default location of the archetype definitions file
+pub fn default_archetypes_path() -> PathBuf {
+    dirs::config_dir()
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("mtg")
+        .join("archetypes.json")
+}
+
+/// Share of signature cards a deck needs to be labeled with a definition
+const MIN_DEFINITION_SCORE: f64 = 0.5;
+
+/// Score of a definition for a deck between 0 and 1.
+///
+/// The share of signature cards in the main deck, reduced when the deck's
+/// colors (from its basic lands) are known and don't match.
+fn definition_score(definition: &ArchetypeDefinition, deck: &ParsedDeck, colors: &str) -> f64 {
+    if definition.signature_cards.is_empty() {
+        return 0.0;
+    }
+
+    let found = definition
+        .signature_cards
+        .iter()
+        .filter(|card| {
+            deck.main_deck
+                .iter()
+                .any(|deck_card| deck_card.name.eq_ignore_ascii_case(card))
+        })
+        .count();
+    let mut score = found as f64 / definition.signature_cards.len() as f64;
+
+    if let Some(wanted) = &definition.colors {
+        if colors != "C" && !wanted.eq_ignore_ascii_case(colors) {
+            score *= 0.75;
+        }
+    }
+
+    score
+}
+
+/// Similarity measure between two main decks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Similarity {
+    /// Cosine similarity over card quantities
+    Cosine,
+    /// Jaccard similarity over card names
+    Jaccard,
+}
+
+impl FromStr for Similarity {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "cosine" => Ok(Similarity::Cosine),
+            "jaccard" => Ok(Similarity::Jaccard),
+            other => Err(eyre!(
+                "Unknown similarity '{}'. Use cosine or jaccard",
+                other
+            )),
+        }
+    }
+}
+
+/// Main deck as card name to quantity
+type CardVector = BTreeMap<String, f64>;
+
+fn card_vector(deck: &ParsedDeck) -> CardVector {
+    let mut vector = CardVector::new();
+    for card in &deck.main_deck {
+        *vector.entry(card.name.clone()).or_default() += card.quantity as f64;
+    }
+    vector
+}
+
+impl Similarity {
+    fn between(&self, a: &CardVector, b: &CardVector) -> f64 {
+        match self {
+            Similarity::Cosine => {
+                let dot: f64 = a
+                    .iter()
+                    .filter_map(|(name, x)| b.get(name).map(|y| x * y))
+                    .sum();
+                let norm_a = a.values().map(|x| x * x).sum::<f64>().sqrt();
+                let norm_b = b.values().map(|x| x * x).sum::<f64>().sqrt();
+                if norm_a == 0.0 || norm_b == 0.0 {
+                    0.0
+                } else {
+                    dot / (norm_a * norm_b)
+                }
+            }
+            Similarity::Jaccard => {
+                let shared = a.keys().filter(|name| b.contains_key(*name)).count();
+                let union = a.len() + b.len() - shared;
+                if union == 0 {
+                    0.0
+                } else {
+                    shared as f64 / union as f64
+                }
+            }
+        }
+    }
+}
+
+/// Options for grouping decks into archetypes
+#[derive(Debug, Clone)]
+pub struct ClusterConfig {
+    pub similarity: Similarity,
+    /// Minimum average similarity for two clusters to be merged
+    pub threshold: f64,
+}
+
+impl Default for ClusterConfig {
+    fn default() -> Self {
+        Self {
+            similarity: Similarity::Cosine,
+            threshold: 0.5,
+        }
+    }
+}
+
+/// Archetype assigned to a deck
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeckArchetype {
+    pub deck_id: String,
+    pub archetype: String,
+    /// Definition score, or similarity to the rest of the cluster
+    pub confidence: f64,
+    /// Whether the label comes from a definition
+    pub from_definition: bool,
+    pub cluster: usize,
+}
+
+/// A group of similar decks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchetypeCluster {
+    pub name: String,
+    pub colors: String,
+    pub decks: Vec<String>,
+    pub share: f64,
+    /// Cards in the main deck of at least 80% of the decks
+    pub core_cards: Vec<String>,
+}
+
+/// Decks grouped into archetypes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArchetypeReport {
+    /// Clusters sorted by number of decks
+    pub clusters: Vec<ArchetypeCluster>,
+    /// Archetype of every deck, in the order of the input
+    pub decks: Vec<DeckArchetype>,
+}
+
+impl ArchetypeReport {
+    /// Archetype of a deck by ID
+    pub fn deck(&self, deck_id: &str) -> Option<&DeckArchetype> {
+        self.decks.iter().find(|deck| deck.deck_id == deck_id)
+    }
+}
+
+/// Share of cluster decks a card needs to be a core card
+const CORE_CARD_SHARE: f64 = 0.8;
+
+/// Group decks with average-linkage clustering and label every group.
+///
+/// Clusters are named after the definition most of their decks match, or
+/// after their colors and most played cards when no definition matches.
+/// Decks matching a definition keep that label even in a mixed cluster.
+pub fn cluster_archetypes(
+    decks: &[ParsedDeck],
+    definitions: &ArchetypeDefinitions,
+    config: &ClusterConfig,
+) -> ArchetypeReport {
+    if decks.is_empty() {
+        return ArchetypeReport::default();
+    }
+
+    let vectors: Vec<CardVector> = decks.iter().map(card_vector).collect();
+    let n = decks.len();
+    let mut similarities = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in i..n {
+            let similarity = config.similarity.between(&vectors[i], &vectors[j]);
+            similarities[i][j] = similarity;
+            similarities[j][i] = similarity;
+        }
+    }
+
+    let groups = average_linkage(&similarities, config.threshold);
+    let labels: Vec<Option<(&ArchetypeDefinition, f64)>> =
+        decks.iter().map(|deck| definitions.label(deck)).collect();
+
+    let mut clusters = Vec::new();
+    let mut deck_archetypes: Vec<Option<DeckArchetype>> = vec![None; n];
+
+    for (cluster_index, members) in groups.iter().enumerate() {
+        let member_decks: Vec<&ParsedDeck> = members.iter().map(|&i| &decks[i]).collect();
+        let core_cards = core_cards(&member_decks);
+        let colors = majority(
+            member_decks
+                .iter()
+                .map(|deck| deck_colors(deck, &HashMap::new())),
+        )
+        .unwrap_or_else(|| "C".to_string());
+
+        let name = majority(
+            members
+                .iter()
+                .filter_map(|&i| labels[i].map(|(definition, _)| definition.name.clone())),
+        )
+        .unwrap_or_else(|| generated_name(&colors, &core_cards, &member_decks));
+
+        for &i in members {
+            // A deck alone in its cluster matched nothing, its closest deck tells how far off it is
+            let cohesion = if members.len() == 1 {
+                (0..n)
+                    .filter(|&j| j != i)
+                    .map(|j| similarities[i][j])
+                    .fold(0.0, f64::max)
+            } else {
+                members
+                    .iter()
+                    .filter(|&&j| j != i)
+                    .map(|&j| similarities[i][j])
+                    .sum::<f64>()
+                    / (members.len() - 1) as f64
+            };
+
+            deck_archetypes[i] = Some(match labels[i] {
+                Some((definition, score)) => DeckArchetype {
+                    deck_id: decks[i].id.clone(),
+                    archetype: definition.name.clone(),
+                    confidence: score,
+                    from_definition: true,
+                    cluster: cluster_index,
+                },
+                None => DeckArchetype {
+                    deck_id: decks[i].id.clone(),
+                    archetype: name.clone(),
+                    confidence: cohesion,
+                    from_definition: false,
+                    cluster: cluster_index,
+                },
+            });
+        }
+
+        clusters.push(ArchetypeCluster {
+            name,
+            colors,
+            decks: member_decks.iter().map(|deck| deck.id.clone()).collect(),
+            share: members.len() as f64 / n as f64,
+            core_cards,
+        });
+    }
+
+    ArchetypeReport {
+        clusters,
+        decks: deck_archetypes.into_iter().flatten().collect(),
+    }
+}
+
+/// Merge the two most similar clusters until no pair reaches the threshold.
+///
+/// Returns the member indexes of each cluster, largest first.
+fn average_linkage(similarities: &[Vec<f64>], threshold: f64) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = (0..similarities.len()).map(|i| vec![i]).collect();
+
+    loop {
+        let mut best: Option<(usize, usize, f64)> = None;
+        for a in 0..groups.len() {
+            for b in (a + 1)..groups.len() {
+                let total: f64 = groups[a]
+                    .iter()
+                    .flat_map(|&i| groups[b].iter().map(move |&j| similarities[i][j]))
+                    .sum();
+                let average = total / (groups[a].len() * groups[b].len()) as f64;
+                if average >= threshold && best.is_none_or(|(_, _, best)| average > best) {
+                    best = Some((a, b, average));
+                }
+            }
+        }
+
+        let Some((a, b, _)) = best else {
+            break;
+        };
+        let merged = groups.remove(b);
+        groups[a].extend(merged);
+    }
+
+    for group in &mut groups {
+        group.sort();
+    }
+    groups.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a[0].cmp(&b[0])));
+    groups
+}
+
+fn core_cards(decks: &[&ParsedDeck]) -> Vec<String> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for deck in decks {
+        let mut names: Vec<&str> = deck.main_deck.iter().map(|c| c.name.as_str()).collect();
+        names.sort();
+        names.dedup();
+        for name in names {
+            *counts.entry(name).or_default() += 1;
+        }
+    }
+
+    let needed = (decks.len() as f64 * CORE_CARD_SHARE).ceil() as usize;
+    let mut cards: Vec<String> = counts
+        .into_iter()
+        .filter(|(_, count)| *count >= needed)
+        .map(|(name, _)| name.to_string())
+        .collect();
+    cards.sort();
+    cards
+}
+
+/// A card the decks of an archetype play that hasn't been seen yet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LikelyCard {
+    pub name: String,
+    /// Share of the archetype's decks with the card in their main deck
+    pub share: f64,
+}
+
+/// An archetype that fits a partial view of a deck
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchetypeGuess {
+    pub archetype: String,
+    /// Share of the seen cards found in the best matching deck of the archetype
+    pub score: f64,
+    /// Number of decks labeled with the archetype
+    pub decks: usize,
+    /// Most played cards of the archetype that weren't seen, most common first
+    pub likely_unseen: Vec<LikelyCard>,
+}
+
+/// Cards listed in [`ArchetypeGuess::likely_unseen`]
+const LIKELY_UNSEEN_CARDS: usize = 8;
+
+/// Guess which archetypes a deck belongs to from some of its cards, e.g. what an
+/// opponent has shown so far.
+///
+/// Every labeled deck is scored by the share of seen cards (basic lands aside) in its main
+/// deck; an archetype gets the score of its best deck. Best guesses come first.
+pub fn guess_archetype(
+    seen: &[String],
+    decks: &[ParsedDeck],
+    report: &ArchetypeReport,
+) -> Vec<ArchetypeGuess> {
+    let seen: Vec<&String> = seen.iter().filter(|name| !is_basic_land(name)).collect();
+    if seen.is_empty() {
+        return Vec::new();
+    }
+
+    let mut archetypes: BTreeMap<&str, Vec<(&ParsedDeck, f64)>> = BTreeMap::new();
+    for deck in decks {
+        let Some(label) = report.deck(&deck.id) else {
+            continue;
+        };
+        let found = seen
+            .iter()
+            .filter(|name| {
+                deck.main_deck
+                    .iter()
+                    .any(|card| card.name.eq_ignore_ascii_case(name))
+            })
+            .count();
+        archetypes
+            .entry(label.archetype.as_str())
+            .or_default()
+            .push((deck, found as f64 / seen.len() as f64));
+    }
+
+    let mut guesses: Vec<ArchetypeGuess> = archetypes
+        .into_iter()
+        .filter_map(|(archetype, members)| {
+            let score = members.iter().map(|(_, score)| *score).fold(0.0, f64::max);
+            if score == 0.0 {
+                return None;
+            }
+
+            let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+            for (deck, _) in &members {
+                let mut names: Vec<&str> = deck
+                    .main_deck
+                    .iter()
+                    .map(|card| card.name.as_str())
+                    .collect();
+                names.sort_unstable();
+                names.dedup();
+                for name in names {
+                    *counts.entry(name).or_default() += 1;
+                }
+            }
+            let mut likely_unseen: Vec<LikelyCard> = counts
+                .into_iter()
+                .filter(|(name, _)| {
+                    !is_basic_land(name) && !seen.iter().any(|seen| seen.eq_ignore_ascii_case(name))
+                })
+                .map(|(name, count)| LikelyCard {
+                    name: name.to_string(),
+                    share: count as f64 / members.len() as f64,
+                })
+                .collect();
+            likely_unseen.sort_by(|a, b| b.share.total_cmp(&a.share));
+            likely_unseen.truncate(LIKELY_UNSEEN_CARDS);
+
+            Some(ArchetypeGuess {
+                archetype: archetype.to_string(),
+                score,
+                decks: members.len(),
+                likely_unseen,
+            })
+        })
+        .collect();
+    guesses.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| b.decks.cmp(&a.decks))
+    });
+    guesses
+}
+
+/// Name a cluster without a definition after its colors and top nonland cards
+fn generated_name(colors: &str, core_cards: &[String], decks: &[&ParsedDeck]) -> String {
+    let mut copies: HashMap<&str, u32> = HashMap::new();
+    for deck in decks {
+        for card in &deck.main_deck {
+            if core_cards.contains(&card.name) && !is_basic_land(&card.name) {
+                *copies.entry(card.name.as_str()).or_default() += card.quantity;
+            }
+        }
+    }
+
+    let mut top: Vec<(&str, u32)> = copies.into_iter().collect();
+    top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    let top: Vec<&str> = top.iter().take(2).map(|(name, _)| *name).collect();
+
+    if top.is_empty() {
+        colors.to_string()
+    } else {
+        format!("{} {}", colors, top.join(" / "))
+    }
+}
+
+fn is_basic_land(name: &str) -> bool {
+    crate::decks::mana::basic_land_color(name).is_some() || name == "Wastes"
+}
+
+/// Most common value, ties broken alphabetically
+fn majority(values: impl Iterator<Item = String>) -> Option<String> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for value in values {
+        *counts.entry(value).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+        .map(|(value, _)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decks::DeckCard;
+
+    fn deck(id: &str, cards: &[(u32, &str)]) -> ParsedDeck {
+        ParsedDeck {
+            id: id.to_string(),
+            title: None,
+            subtitle: None,
+            event_date: None,
+            event_name: None,
+            format: None,
+            main_deck: cards
+                .iter()
+                .map(|(quantity, name)| DeckCard {
+                    quantity: *quantity,
+                    name: name.to_string(),
+                    set_code: None,
+                    collector_number: None,
+                    card_details: None,
+                })
+                .collect(),
+            sideboard: vec![],
+        }
+    }
+
+    fn decks() -> Vec<ParsedDeck> {
+        vec![
+            deck(
+                "red-1",
+                &[
+                    (4, "Monastery Swiftspear"),
+                    (4, "Lightning Bolt"),
+                    (20, "Mountain"),
+                ],
+            ),
+            deck(
+                "red-2",
+                &[
+                    (4, "Monastery Swiftspear"),
+                    (3, "Lightning Bolt"),
+                    (21, "Mountain"),
+                ],
+            ),
+            deck(
+                "blue-1",
+                &[(4, "Counterspell"), (4, "Brainstorm"), (20, "Island")],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_similarity() {
+        let decks = decks();
+        let red_1 = card_vector(&decks[0]);
+        let red_2 = card_vector(&decks[1]);
+        let blue = card_vector(&decks[2]);
+
+        assert!(Similarity::Cosine.between(&red_1, &red_2) > 0.9);
+        assert_eq!(Similarity::Cosine.between(&red_1, &blue), 0.0);
+        assert_eq!(Similarity::Jaccard.between(&red_1, &red_2), 1.0);
+        assert_eq!(Similarity::Jaccard.between(&red_1, &blue), 0.0);
+    }
+
+    #[test]
+    fn test_cluster_archetypes_without_definitions() {
+        let report = cluster_archetypes(
+            &decks(),
+            &ArchetypeDefinitions::default(),
+            &ClusterConfig::default(),
+        );
+
+        assert_eq!(report.clusters.len(), 2);
+        assert_eq!(report.clusters[0].decks, vec!["red-1", "red-2"]);
+        assert_eq!(report.clusters[0].colors, "R");
+        assert_eq!(
+            report.clusters[0].name,
+            "R Monastery Swiftspear / Lightning Bolt"
+        );
+        assert!(!report.deck("red-1").unwrap().from_definition);
+        assert_eq!(report.deck("blue-1").unwrap().cluster, 1);
+        // Nothing else looks like the blue deck
+        assert_eq!(report.deck("blue-1").unwrap().confidence, 0.0);
+        assert!(report.deck("red-1").unwrap().confidence > 0.9);
+
+        let single = cluster_archetypes(
+            &decks()[2..],
+            &ArchetypeDefinitions::default(),
+            &ClusterConfig::default(),
+        );
+        assert_eq!(single.decks[0].confidence, 0.0);
+    }
+
+    #[test]
+    fn test_cluster_archetypes_with_definitions() {
+        let definitions = ArchetypeDefinitions {
+            archetypes: vec![ArchetypeDefinition {
+                name: "Mono-Red Aggro".to_string(),
+                format: None,
+                colors: Some("R".to_string()),
+                signature_cards: vec![
+                    "Monastery Swiftspear".to_string(),
+                    "Lightning Bolt".to_string(),
+                ],
+            }],
+        };
+
+        let report = cluster_archetypes(&decks(), &definitions, &ClusterConfig::default());
+        assert_eq!(report.clusters[0].name, "Mono-Red Aggro");
+
+        let red = report.deck("red-2").unwrap();
+        assert_eq!(red.archetype, "Mono-Red Aggro");
+        assert_eq!(red.confidence, 1.0);
+        assert!(red.from_definition);
+        assert!(definitions.label(&decks()[2]).is_none());
+    }
+
+    #[test]
+    fn test_guess_archetype_from_seen_cards() {
+        let decks = decks();
+        let report = cluster_archetypes(
+            &decks,
+            &ArchetypeDefinitions::default(),
+            &ClusterConfig::default(),
+        );
+
+        let guesses = guess_archetype(
+            &["Lightning Bolt".to_string(), "Mountain".to_string()],
+            &decks,
+            &report,
+        );
+        assert_eq!(guesses.len(), 1);
+        assert_eq!(guesses[0].archetype, report.clusters[0].name);
+        assert_eq!(guesses[0].score, 1.0);
+        assert_eq!(guesses[0].decks, 2);
+        assert_eq!(guesses[0].likely_unseen[0].name, "Monastery Swiftspear");
+        assert_eq!(guesses[0].likely_unseen.len(), 1);
+
+        assert!(guess_archetype(&["Mountain".to_string()], &decks, &report).is_empty());
+    }
+}
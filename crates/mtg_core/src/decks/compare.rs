@@ -1,9 +1,11 @@
 use crate::cache::{CacheStore, DiskCache};
-use crate::decks::{ranked::RankedDecksClient, ParsedDeck};
-use color_eyre::Result;
-use std::collections::HashMap;
+use crate::decks::library::{diff_deck_lists, CardChange, DeckSection};
+use crate::decks::{ranked::RankedDecksClient, DeckCard, DeckList, ParsedDeck};
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CardEntry {
     pub main_count: u32,
     pub side_count: u32,
@@ -244,6 +246,590 @@ pub fn compare_decks(deck1: &ParsedDeck, deck2: &ParsedDeck) -> DeckComparison {
     }
 }
 
+/// How widely a card is played across the compared decks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CardRole {
+    /// In the main deck of every deck
+    Core,
+    /// In the main deck of some decks
+    Flex,
+    /// Only ever in sideboards
+    Sideboard,
+}
+
+/// One card across all compared decks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardRow {
+    pub name: String,
+    /// Copies in each deck, in the order of `MultiDeckComparison::decks`
+    pub counts: Vec<CardEntry>,
+    pub role: CardRole,
+    /// Copies in the consensus main deck and sideboard
+    pub consensus: CardEntry,
+}
+
+/// Differences between one deck and the consensus list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeckDeviation {
+    pub deck: String,
+    /// Changes from the consensus (`before`) to this deck (`after`)
+    pub changes: Vec<CardChange>,
+    /// Cards in the main deck that differ from the consensus
+    pub main_swing: u32,
+    /// Cards in the sideboard that differ from the consensus
+    pub sideboard_swing: u32,
+}
+
+/// Comparison of any number of decks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiDeckComparison {
+    pub decks: Vec<String>,
+    /// Card by deck matrix sorted by role, then by how many decks play the card
+    pub cards: Vec<CardRow>,
+    pub deviations: Vec<DeckDeviation>,
+}
+
+impl MultiDeckComparison {
+    /// Cards with the given role
+    pub fn cards_with_role(&self, role: CardRole) -> impl Iterator<Item = &CardRow> {
+        self.cards.iter().filter(move |card| card.role == role)
+    }
+
+    /// The consensus list as `(name, main, side)` tuples
+    pub fn consensus_list(&self) -> Vec<(&str, u32, u32)> {
+        self.cards
+            .iter()
+            .filter(|card| card.consensus.total() > 0)
+            .map(|card| {
+                (
+                    card.name.as_str(),
+                    card.consensus.main_count,
+                    card.consensus.side_count,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Name each deck by its title, falling back to its ID, and number duplicates
+fn deck_names(decks: &[ParsedDeck]) -> Vec<String> {
+    let base: Vec<String> = decks
+        .iter()
+        .map(|deck| deck.title.clone().unwrap_or_else(|| deck.id.clone()))
+        .collect();
+
+    base.iter()
+        .enumerate()
+        .map(|(i, name)| {
+            if base.iter().filter(|other| *other == name).count() > 1 {
+                let nth = base[..=i].iter().filter(|other| *other == name).count();
+                format!("{name} #{nth}")
+            } else {
+                name.clone()
+            }
+        })
+        .collect()
+}
+
+/// Median of the non-zero counts, rounded down
+fn median_count(counts: impl Iterator<Item = u32>) -> u32 {
+    let mut counts: Vec<u32> = counts.filter(|count| *count > 0).collect();
+    if counts.is_empty() {
+        return 0;
+    }
+    counts.sort();
+    counts[(counts.len() - 1) / 2]
+}
+
+/// Compare any number of decks.
+///
+/// A card is in the consensus main deck (or sideboard) when at least half of
+/// the decks play it there, with the median number of copies among those
+/// decks. Deviations compare every deck against that consensus.
+pub fn compare_many_decks(decks: &[ParsedDeck]) -> MultiDeckComparison {
+    let names = deck_names(decks);
+    let mut matrix: BTreeMap<String, Vec<CardEntry>> = BTreeMap::new();
+
+    for (index, deck) in decks.iter().enumerate() {
+        for card in &deck.main_deck {
+            matrix
+                .entry(card.name.clone())
+                .or_insert_with(|| vec![CardEntry::default(); decks.len()])[index]
+                .main_count += card.quantity;
+        }
+        for card in &deck.sideboard {
+            matrix
+                .entry(card.name.clone())
+                .or_insert_with(|| vec![CardEntry::default(); decks.len()])[index]
+                .side_count += card.quantity;
+        }
+    }
+
+    let half = decks.len().div_ceil(2);
+    let mut cards: Vec<CardRow> = matrix
+        .into_iter()
+        .map(|(name, counts)| {
+            let main_decks = counts.iter().filter(|c| c.main_count > 0).count();
+            let side_decks = counts.iter().filter(|c| c.side_count > 0).count();
+
+            let role = if main_decks == decks.len() {
+                CardRole::Core
+            } else if main_decks > 0 {
+                CardRole::Flex
+            } else {
+                CardRole::Sideboard
+            };
+
+            let consensus = CardEntry {
+                main_count: if main_decks >= half {
+                    median_count(counts.iter().map(|c| c.main_count))
+                } else {
+                    0
+                },
+                side_count: if side_decks >= half {
+                    median_count(counts.iter().map(|c| c.side_count))
+                } else {
+                    0
+                },
+            };
+
+            CardRow {
+                name,
+                counts,
+                role,
+                consensus,
+            }
+        })
+        .collect();
+
+    let role_order = |role: CardRole| match role {
+        CardRole::Core => 0,
+        CardRole::Flex => 1,
+        CardRole::Sideboard => 2,
+    };
+    let played_in = |card: &CardRow| card.counts.iter().filter(|c| c.total() > 0).count();
+    cards.sort_by(|a, b| {
+        role_order(a.role)
+            .cmp(&role_order(b.role))
+            .then_with(|| played_in(b).cmp(&played_in(a)))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let deviations = names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let mut changes = Vec::new();
+            for card in &cards {
+                let count = &card.counts[index];
+                for (section, before, after) in [
+                    (
+                        DeckSection::Main,
+                        card.consensus.main_count,
+                        count.main_count,
+                    ),
+                    (
+                        DeckSection::Sideboard,
+                        card.consensus.side_count,
+                        count.side_count,
+                    ),
+                ] {
+                    if before != after {
+                        changes.push(CardChange {
+                            name: card.name.clone(),
+                            section,
+                            before,
+                            after,
+                        });
+                    }
+                }
+            }
+            changes.sort_by(|a, b| a.section.cmp(&b.section).then_with(|| a.name.cmp(&b.name)));
+
+            let swing = |section: DeckSection| {
+                changes
+                    .iter()
+                    .filter(|change| change.section == section)
+                    .map(|change| change.delta().unsigned_abs() as u32)
+                    .sum()
+            };
+
+            DeckDeviation {
+                deck: name.clone(),
+                main_swing: swing(DeckSection::Main),
+                sideboard_swing: swing(DeckSection::Sideboard),
+                changes,
+            }
+        })
+        .collect();
+
+    MultiDeckComparison {
+        decks: names,
+        cards,
+        deviations,
+    }
+}
+
+/// One line of a deck diff
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum DiffLine {
+    /// Copies added (positive) to or removed (negative) from a section
+    Change {
+        section: DeckSection,
+        name: String,
+        delta: i64,
+    },
+    /// Copies moved between the main deck and the sideboard
+    Move {
+        name: String,
+        quantity: u32,
+        from: DeckSection,
+        to: DeckSection,
+    },
+}
+
+/// Patch-style difference between two versions of a deck list
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeckDiff {
+    pub lines: Vec<DiffLine>,
+}
+
+impl DeckDiff {
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Cards added and removed across both sections, moves excluded
+    pub fn totals(&self) -> (u32, u32) {
+        let mut added = 0;
+        let mut removed = 0;
+        for line in &self.lines {
+            if let DiffLine::Change { delta, .. } = line {
+                if *delta > 0 {
+                    added += *delta as u32;
+                } else {
+                    removed += delta.unsigned_abs() as u32;
+                }
+            }
+        }
+        (added, removed)
+    }
+}
+
+fn section_name(section: DeckSection) -> &'static str {
+    match section {
+        DeckSection::Main => "main",
+        DeckSection::Sideboard => "sideboard",
+    }
+}
+
+fn parse_section(name: &str) -> Option<DeckSection> {
+    match name.trim().to_lowercase().as_str() {
+        "main" | "deck" | "maindeck" | "main deck" => Some(DeckSection::Main),
+        "side" | "sideboard" => Some(DeckSection::Sideboard),
+        _ => None,
+    }
+}
+
+/// Diff two versions of a deck list.
+///
+/// Copies that left one section and appeared in the other are reported as
+/// moves, everything else as additions and removals per section.
+pub fn diff_decks(old: &DeckList, new: &DeckList) -> DeckDiff {
+    let changes = diff_deck_lists(old, new);
+
+    let mut deltas: BTreeMap<String, [i64; 2]> = BTreeMap::new();
+    for change in &changes {
+        let index = match change.section {
+            DeckSection::Main => 0,
+            DeckSection::Sideboard => 1,
+        };
+        deltas.entry(change.name.clone()).or_default()[index] += change.delta();
+    }
+
+    let mut moves = Vec::new();
+    for (name, [main, side]) in deltas.iter_mut() {
+        let (from, to, quantity) = if *main < 0 && *side > 0 {
+            (
+                DeckSection::Main,
+                DeckSection::Sideboard,
+                (-*main).min(*side),
+            )
+        } else if *side < 0 && *main > 0 {
+            (
+                DeckSection::Sideboard,
+                DeckSection::Main,
+                (-*side).min(*main),
+            )
+        } else {
+            continue;
+        };
+
+        let (main_shift, side_shift) = match from {
+            DeckSection::Main => (quantity, -quantity),
+            DeckSection::Sideboard => (-quantity, quantity),
+        };
+        *main += main_shift;
+        *side += side_shift;
+        moves.push(DiffLine::Move {
+            name: name.clone(),
+            quantity: quantity as u32,
+            from,
+            to,
+        });
+    }
+
+    let mut lines = Vec::new();
+    for (section, index) in [(DeckSection::Main, 0), (DeckSection::Sideboard, 1)] {
+        for change in changes.iter().filter(|change| change.section == section) {
+            let Some(delta) = deltas.get_mut(&change.name).map(|deltas| deltas[index]) else {
+                continue;
+            };
+            if delta != 0 {
+                lines.push(DiffLine::Change {
+                    section,
+                    name: change.name.clone(),
+                    delta,
+                });
+            }
+        }
+    }
+    lines.extend(moves);
+
+    DeckDiff { lines }
+}
+
+/// Format a diff as text, one section header per group of lines:
+///
+/// ```text
+/// @@ main @@
+/// +2 Fable of the Mirror-Breaker
+/// -1 Lightning Bolt
+/// @@ sideboard @@
+/// +1 Duress
+/// @@ moved @@
+/// ~1 Negate main -> sideboard
+/// ```
+pub fn format_deck_diff(diff: &DeckDiff) -> String {
+    let mut output = String::new();
+    let mut header = None;
+
+    for line in &diff.lines {
+        let (group, text) = match line {
+            DiffLine::Change {
+                section,
+                name,
+                delta,
+            } => (
+                section_name(*section),
+                format!(
+                    "{}{} {}",
+                    if *delta > 0 { "+" } else { "-" },
+                    delta.abs(),
+                    name
+                ),
+            ),
+            DiffLine::Move {
+                name,
+                quantity,
+                from,
+                to,
+            } => (
+                "moved",
+                format!(
+                    "~{} {} {} -> {}",
+                    quantity,
+                    name,
+                    section_name(*from),
+                    section_name(*to)
+                ),
+            ),
+        };
+
+        if header != Some(group) {
+            output.push_str(&format!("@@ {group} @@\n"));
+            header = Some(group);
+        }
+        output.push_str(&text);
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Parse a diff written by [`format_deck_diff`].
+///
+/// Blank lines, `#` comments and `---`/`+++` file headers are skipped. Changes
+/// before any section header apply to the main deck.
+pub fn parse_deck_diff(text: &str) -> Result<DeckDiff> {
+    let mut section = DeckSection::Main;
+    let mut lines = Vec::new();
+
+    for (number, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with("---")
+            || line.starts_with("+++")
+        {
+            continue;
+        }
+
+        let error = || eyre!("Invalid diff line {}: '{}'", number + 1, line);
+
+        if let Some(header) = line.strip_prefix("@@").and_then(|l| l.strip_suffix("@@")) {
+            if header.trim().eq_ignore_ascii_case("moved") {
+                continue;
+            }
+            section = parse_section(header).ok_or_else(error)?;
+            continue;
+        }
+
+        let sign = line.chars().next().ok_or_else(error)?;
+        let rest = &line[1..];
+        let (quantity, text) = rest.split_once(' ').ok_or_else(error)?;
+        let quantity: u32 = quantity.parse().map_err(|_| error())?;
+        let text = text.trim();
+
+        match sign {
+            '+' | '-' => lines.push(DiffLine::Change {
+                section,
+                name: text.to_string(),
+                delta: if sign == '+' {
+                    quantity as i64
+                } else {
+                    -(quantity as i64)
+                },
+            }),
+            '~' => {
+                let (rest, to) = text.rsplit_once("->").ok_or_else(error)?;
+                let (name, from) = rest.trim().rsplit_once(' ').ok_or_else(error)?;
+                lines.push(DiffLine::Move {
+                    name: name.trim().to_string(),
+                    quantity,
+                    from: parse_section(from).ok_or_else(error)?,
+                    to: parse_section(to).ok_or_else(error)?,
+                });
+            }
+            _ => return Err(error()),
+        }
+    }
+
+    Ok(DeckDiff { lines })
+}
+
+/// Apply a diff to a deck list.
+///
+/// Card names match case-insensitively. Removing or moving more copies than
+/// the deck has is an error, so a diff only applies to the list it came from.
+pub fn apply_deck_diff(deck: &DeckList, diff: &DeckDiff) -> Result<DeckList> {
+    let mut result = deck.clone();
+
+    fn cards_of(deck: &mut DeckList, section: DeckSection) -> &mut Vec<DeckCard> {
+        match section {
+            DeckSection::Main => &mut deck.main_deck,
+            DeckSection::Sideboard => &mut deck.sideboard,
+        }
+    }
+
+    fn remove(
+        cards: &mut Vec<DeckCard>,
+        name: &str,
+        quantity: u32,
+        section: DeckSection,
+    ) -> Result<()> {
+        let available: u32 = cards
+            .iter()
+            .filter(|card| card.name.eq_ignore_ascii_case(name))
+            .map(|card| card.quantity)
+            .sum();
+        if available < quantity {
+            return Err(eyre!(
+                "Cannot remove {} {} from the {}: the deck has {}",
+                quantity,
+                name,
+                section_name(section),
+                available
+            ));
+        }
+
+        let mut left = quantity;
+        for card in cards
+            .iter_mut()
+            .filter(|card| card.name.eq_ignore_ascii_case(name))
+        {
+            let taken = left.min(card.quantity);
+            card.quantity -= taken;
+            left -= taken;
+        }
+        cards.retain(|card| card.quantity > 0);
+        Ok(())
+    }
+
+    fn add(cards: &mut Vec<DeckCard>, name: &str, quantity: u32) {
+        match cards
+            .iter_mut()
+            .find(|card| card.name.eq_ignore_ascii_case(name))
+        {
+            Some(card) => card.quantity += quantity,
+            None => cards.push(DeckCard {
+                quantity,
+                name: name.to_string(),
+                set_code: None,
+                collector_number: None,
+                card_details: None,
+            }),
+        }
+    }
+
+    for line in &diff.lines {
+        match line {
+            DiffLine::Change {
+                section,
+                name,
+                delta,
+            } => {
+                let cards = cards_of(&mut result, *section);
+                if *delta > 0 {
+                    add(cards, name, *delta as u32);
+                } else {
+                    remove(cards, name, delta.unsigned_abs() as u32, *section)?;
+                }
+            }
+            DiffLine::Move {
+                name,
+                quantity,
+                from,
+                to,
+            } => {
+                // Keep the printing of the moved card
+                let printing = cards_of(&mut result, *from)
+                    .iter()
+                    .find(|card| card.name.eq_ignore_ascii_case(name))
+                    .cloned();
+                remove(cards_of(&mut result, *from), name, *quantity, *from)?;
+                let cards = cards_of(&mut result, *to);
+                match (
+                    cards
+                        .iter()
+                        .any(|card| card.name.eq_ignore_ascii_case(name)),
+                    printing,
+                ) {
+                    (false, Some(printing)) => cards.push(DeckCard {
+                        quantity: *quantity,
+                        ..printing
+                    }),
+                    _ => add(cards, name, *quantity),
+                }
+            }
+        }
+    }
+
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -336,4 +922,102 @@ mod tests {
         assert_eq!(lightning_bolt.0.main_count, 4);
         assert_eq!(lightning_bolt.1.main_count, 3);
     }
+
+    #[test]
+    fn test_compare_many_decks() {
+        let decks = vec![
+            create_test_deck(
+                "Red",
+                vec![("Lightning Bolt", 4), ("Shock", 4)],
+                vec![("Abrade", 2)],
+            ),
+            create_test_deck(
+                "Red",
+                vec![("Lightning Bolt", 4), ("Shock", 2), ("Play with Fire", 2)],
+                vec![("Abrade", 3)],
+            ),
+            create_test_deck(
+                "Other Red",
+                vec![("Lightning Bolt", 3), ("Play with Fire", 4)],
+                vec![],
+            ),
+        ];
+
+        let comparison = compare_many_decks(&decks);
+        assert_eq!(comparison.decks, vec!["Red #1", "Red #2", "Other Red"]);
+
+        let bolt = &comparison.cards[0];
+        assert_eq!(bolt.name, "Lightning Bolt");
+        assert_eq!(bolt.role, CardRole::Core);
+        assert_eq!(bolt.consensus.main_count, 4);
+
+        assert_eq!(comparison.cards_with_role(CardRole::Flex).count(), 2);
+        assert_eq!(
+            comparison.consensus_list(),
+            vec![
+                ("Lightning Bolt", 4, 0),
+                ("Play with Fire", 2, 0),
+                ("Shock", 2, 0),
+                ("Abrade", 0, 2),
+            ]
+        );
+
+        let first = &comparison.deviations[0];
+        assert_eq!(first.main_swing, 4);
+        assert_eq!(first.sideboard_swing, 0);
+
+        let third = &comparison.deviations[2];
+        assert_eq!(third.main_swing, 5);
+        assert_eq!(third.sideboard_swing, 2);
+    }
+
+    #[test]
+    fn test_deck_diff_round_trip() {
+        let old = create_test_deck(
+            "Old",
+            vec![("Lightning Bolt", 4), ("Negate", 2), ("Mountain", 18)],
+            vec![("Duress", 2)],
+        );
+        let new = create_test_deck(
+            "New",
+            vec![
+                ("Lightning Bolt", 3),
+                ("Play with Fire", 2),
+                ("Mountain", 18),
+            ],
+            vec![("Duress", 2), ("Negate", 2)],
+        );
+        let old = DeckList {
+            main_deck: old.main_deck,
+            sideboard: old.sideboard,
+        };
+        let new = DeckList {
+            main_deck: new.main_deck,
+            sideboard: new.sideboard,
+        };
+
+        let diff = diff_decks(&old, &new);
+        assert_eq!(diff.totals(), (2, 1));
+        assert_eq!(
+            diff.lines.last(),
+            Some(&DiffLine::Move {
+                name: "Negate".to_string(),
+                quantity: 2,
+                from: DeckSection::Main,
+                to: DeckSection::Sideboard,
+            })
+        );
+
+        let text = format_deck_diff(&diff);
+        assert_eq!(
+            text,
+            "@@ main @@\n-1 Lightning Bolt\n+2 Play with Fire\n@@ moved @@\n~2 Negate main -> sideboard\n"
+        );
+        assert_eq!(parse_deck_diff(&text).unwrap(), diff);
+
+        let patched = apply_deck_diff(&old, &diff).unwrap();
+        assert!(diff_decks(&patched, &new).is_empty());
+        assert!(apply_deck_diff(&new, &diff).is_err());
+        assert!(parse_deck_diff("*1 Shock").is_err());
+    }
 }
@@ -0,0 +1,448 @@
+use crate::decks::sideboard::SideboardPlan;
+use crate::decks::{DeckCard, DeckList};
+use crate::paths;
+use chrono::{DateTime, Utc};
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Section of a deck a card change applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeckSection {
+    Main,
+    Sideboard,
+}
+
+/// Change in the number of copies of a card between two revisions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CardChange {
+    pub name: String,
+    pub section: DeckSection,
+    pub before: u32,
+    pub after: u32,
+}
+
+impl CardChange {
+    /// Difference in copies, positive when cards were added
+    pub fn delta(&self) -> i64 {
+        self.after as i64 - self.before as i64
+    }
+}
+
+/// A saved version of a deck
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeckRevision {
+    pub number: u32,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub deck_list: DeckList,
+    /// Changes from the previous revision
+    #[serde(default)]
+    pub changes: Vec<CardChange>,
+}
+
+/// A named deck in the library with its full history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryDeck {
+    pub name: String,
+    pub slug: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Where the deck came from (file, deck ID, URL)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Sideboard plans per opposing archetype
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sideboard_plans: Vec<SideboardPlan>,
+    pub revisions: Vec<DeckRevision>,
+}
+
+impl LibraryDeck {
+    /// The most recent revision
+    pub fn latest(&self) -> &DeckRevision {
+        self.revisions
+            .last()
+            .expect("library decks always have at least one revision")
+    }
+
+    /// A revision by number
+    pub fn revision(&self, number: u32) -> Option<&DeckRevision> {
+        self.revisions.iter().find(|r| r.number == number)
+    }
+}
+
+/// Result of saving a deck to the library
+#[derive(Debug, Clone)]
+pub enum SaveOutcome {
+    /// A new deck was added
+    Created(LibraryDeck),
+    /// A new revision was added to an existing deck
+    Updated(LibraryDeck),
+    /// The deck list didn't change, no revision was added
+    Unchanged(LibraryDeck),
+}
+
+impl SaveOutcome {
+    pub fn deck(&self) -> &LibraryDeck {
+        match self {
+            SaveOutcome::Created(deck)
+            | SaveOutcome::Updated(deck)
+            | SaveOutcome::Unchanged(deck) => deck,
+        }
+    }
+}
+
+/// Default location of the deck library, next to (but outside of) the cache.
+///
+/// Can be overridden with the `MTG_LIBRARY_DIR` environment variable.
+pub fn default_library_path() -> PathBuf {
+    if let Some(dir) = std::env::var_os("MTG_LIBRARY_DIR") {
+        return PathBuf::from(dir);
+    }
+
+    paths::data_dir().join("library")
+}
+
+/// Turn a deck name into the file name used to store it
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.trim().to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.ends_with('-') && !slug.is_empty() {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Persistent store of named decks, one JSON file per deck
+#[derive(Debug, Clone)]
+pub struct DeckLibrary {
+    root: PathBuf,
+}
+
+impl DeckLibrary {
+    /// Open a library at the given directory
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Open the library at its default location
+    pub fn open_default() -> Self {
+        Self::new(default_library_path())
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn deck_path(&self, slug: &str) -> PathBuf {
+        self.root.join(format!("{slug}.json"))
+    }
+
+    fn slug_for(name: &str) -> Result<String> {
+        let slug = slugify(name);
+        if slug.is_empty() {
+            return Err(eyre!(
+                "Deck name '{}' must contain letters or numbers",
+                name
+            ));
+        }
+        Ok(slug)
+    }
+
+    /// Check whether a deck exists
+    pub fn contains(&self, name: &str) -> bool {
+        Self::slug_for(name).is_ok_and(|slug| self.deck_path(&slug).exists())
+    }
+
+    /// Load a deck by name or slug
+    pub fn get(&self, name: &str) -> Result<LibraryDeck> {
+        let slug = Self::slug_for(name)?;
+        let path = self.deck_path(&slug);
+        let content = std::fs::read_to_string(&path)
+            .map_err(|_| eyre!("Deck '{}' not found in the library", name))?;
+        serde_json::from_str(&content)
+            .map_err(|e| eyre!("Failed to read deck '{}': {}", path.display(), e))
+    }
+
+    fn write(&self, deck: &LibraryDeck) -> Result<()> {
+        paths::write_json_atomic(&self.deck_path(&deck.slug), deck)
+    }
+
+    /// Save a deck list, adding a revision when it changed.
+    ///
+    /// Names only differing in case refer to the same deck. A different name that maps to
+    /// the same file, such as "mono-red" for "Mono Red", is rejected instead of silently
+    /// adding a revision to the other deck.
+    pub fn save(
+        &self,
+        name: &str,
+        deck_list: &DeckList,
+        message: Option<String>,
+        source: Option<String>,
+    ) -> Result<SaveOutcome> {
+        let slug = Self::slug_for(name)?;
+        let deck_list = strip_details(deck_list);
+        let now = Utc::now();
+
+        if !self.deck_path(&slug).exists() {
+            let deck = LibraryDeck {
+                name: name.trim().to_string(),
+                slug,
+                created_at: now,
+                updated_at: now,
+                tags: Vec::new(),
+                notes: None,
+                source,
+                sideboard_plans: Vec::new(),
+                revisions: vec![DeckRevision {
+                    number: 1,
+                    created_at: now,
+                    message,
+                    deck_list,
+                    changes: Vec::new(),
+                }],
+            };
+            self.write(&deck)?;
+            return Ok(SaveOutcome::Created(deck));
+        }
+
+        let mut deck = self.get(&slug)?;
+        if !deck.name.eq_ignore_ascii_case(name.trim()) {
+            return Err(eyre!(
+                "Deck name '{}' conflicts with the saved deck '{}', save it under that name to update it or choose another name",
+                name.trim(),
+                deck.name
+            ));
+        }
+        let changes = diff_deck_lists(&deck.latest().deck_list, &deck_list);
+        if changes.is_empty() {
+            return Ok(SaveOutcome::Unchanged(deck));
+        }
+
+        let number = deck.latest().number + 1;
+        deck.revisions.push(DeckRevision {
+            number,
+            created_at: now,
+            message,
+            deck_list,
+            changes,
+        });
+        deck.updated_at = now;
+        if source.is_some() {
+            deck.source = source;
+        }
+        self.write(&deck)?;
+
+        Ok(SaveOutcome::Updated(deck))
+    }
+
+    /// All decks sorted by name
+    pub fn list(&self) -> Result<Vec<LibraryDeck>> {
+        if !self.root.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut decks = Vec::new();
+        for entry in std::fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let content = std::fs::read_to_string(&path)?;
+            match serde_json::from_str::<LibraryDeck>(&content) {
+                Ok(deck) => decks.push(deck),
+                Err(e) => {
+                    return Err(eyre!("Failed to read deck '{}': {}", path.display(), e));
+                }
+            }
+        }
+
+        decks.sort_by_key(|deck| deck.name.to_lowercase());
+        Ok(decks)
+    }
+
+    /// Delete a deck and its history
+    pub fn remove(&self, name: &str) -> Result<LibraryDeck> {
+        let deck = self.get(name)?;
+        std::fs::remove_file(self.deck_path(&deck.slug))?;
+        Ok(deck)
+    }
+
+    /// Add and remove tags on a deck
+    pub fn tag(&self, name: &str, add: &[String], remove: &[String]) -> Result<LibraryDeck> {
+        let mut deck = self.get(name)?;
+
+        for tag in add {
+            let tag = tag.trim().to_lowercase();
+            if !tag.is_empty() && !deck.tags.contains(&tag) {
+                deck.tags.push(tag);
+            }
+        }
+        deck.tags
+            .retain(|tag| !remove.iter().any(|r| r.trim().eq_ignore_ascii_case(tag)));
+        deck.tags.sort();
+
+        deck.updated_at = Utc::now();
+        self.write(&deck)?;
+        Ok(deck)
+    }
+
+    /// Replace the notes of a deck, clearing them with `None`
+    pub fn set_notes(&self, name: &str, notes: Option<String>) -> Result<LibraryDeck> {
+        let mut deck = self.get(name)?;
+        deck.notes = notes.filter(|notes| !notes.trim().is_empty());
+        deck.updated_at = Utc::now();
+        self.write(&deck)?;
+        Ok(deck)
+    }
+
+    /// Replace the sideboard plans of a deck
+    pub fn set_sideboard_plans(
+        &self,
+        name: &str,
+        plans: Vec<SideboardPlan>,
+    ) -> Result<LibraryDeck> {
+        let mut deck = self.get(name)?;
+        deck.sideboard_plans = plans;
+        deck.updated_at = Utc::now();
+        self.write(&deck)?;
+        Ok(deck)
+    }
+}
+
+/// Copy of a deck list without card details, which are large and go stale
+fn strip_details(deck_list: &DeckList) -> DeckList {
+    let strip = |cards: &[DeckCard]| {
+        cards
+            .iter()
+            .map(|card| DeckCard {
+                card_details: None,
+                ..card.clone()
+            })
+            .collect()
+    };
+
+    DeckList {
+        main_deck: strip(&deck_list.main_deck),
+        sideboard: strip(&deck_list.sideboard),
+    }
+}
+
+/// Card count changes between two deck lists, main deck first then sideboard
+pub fn diff_deck_lists(old: &DeckList, new: &DeckList) -> Vec<CardChange> {
+    let mut counts: BTreeMap<(DeckSection, String), (u32, u32)> = BTreeMap::new();
+
+    for (section, cards) in [
+        (DeckSection::Main, &old.main_deck),
+        (DeckSection::Sideboard, &old.sideboard),
+    ] {
+        for card in cards {
+            counts.entry((section, card.name.clone())).or_default().0 += card.quantity;
+        }
+    }
+    for (section, cards) in [
+        (DeckSection::Main, &new.main_deck),
+        (DeckSection::Sideboard, &new.sideboard),
+    ] {
+        for card in cards {
+            counts.entry((section, card.name.clone())).or_default().1 += card.quantity;
+        }
+    }
+
+    counts
+        .into_iter()
+        .filter(|(_, (before, after))| before != after)
+        .map(|((section, name), (before, after))| CardChange {
+            name,
+            section,
+            before,
+            after,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decks::parse_deck_list;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Mono-Red Aggro"), "mono-red-aggro");
+        assert_eq!(slugify("  Izzet  Phoenix (v2) "), "izzet-phoenix-v2");
+        assert_eq!(slugify("!!!"), "");
+    }
+
+    #[test]
+    fn test_diff_deck_lists() {
+        let old = parse_deck_list("4 Lightning Bolt\n20 Mountain\n\nSideboard\n2 Abrade").unwrap();
+        let new = parse_deck_list("3 Lightning Bolt\n1 Shock\n20 Mountain\n\nSideboard\n2 Abrade")
+            .unwrap();
+
+        let changes = diff_deck_lists(&old, &new);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].name, "Lightning Bolt");
+        assert_eq!(changes[0].delta(), -1);
+        assert_eq!(changes[1].name, "Shock");
+        assert_eq!(changes[1].delta(), 1);
+    }
+
+    #[test]
+    fn test_library_revisions_and_tags() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = DeckLibrary::new(dir.path());
+
+        let deck = parse_deck_list("4 Lightning Bolt\n20 Mountain").unwrap();
+        let outcome = library
+            .save("Mono Red", &deck, None, Some("deck.txt".to_string()))
+            .unwrap();
+        assert!(matches!(outcome, SaveOutcome::Created(_)));
+
+        let outcome = library.save("mono red", &deck, None, None).unwrap();
+        assert!(matches!(outcome, SaveOutcome::Unchanged(_)));
+
+        let edited = parse_deck_list("4 Lightning Bolt\n4 Shock\n16 Mountain").unwrap();
+        let outcome = library
+            .save("Mono Red", &edited, Some("Add Shock".to_string()), None)
+            .unwrap();
+        let saved = outcome.deck();
+        assert_eq!(saved.revisions.len(), 2);
+        assert_eq!(saved.latest().number, 2);
+        assert_eq!(saved.latest().changes.len(), 2);
+        assert_eq!(saved.source.as_deref(), Some("deck.txt"));
+
+        let tagged = library
+            .tag(
+                "mono-red",
+                &["Modern".to_string(), "aggro".to_string()],
+                &[],
+            )
+            .unwrap();
+        assert_eq!(tagged.tags, vec!["aggro", "modern"]);
+        let tagged = library
+            .tag("mono-red", &[], &["MODERN".to_string()])
+            .unwrap();
+        assert_eq!(tagged.tags, vec!["aggro"]);
+
+        let conflict = library.save("mono-red", &edited, None, None).unwrap_err();
+        assert!(conflict
+            .to_string()
+            .contains("conflicts with the saved deck 'Mono Red'"));
+        assert_eq!(library.get("Mono Red").unwrap().revisions.len(), 2);
+
+        assert_eq!(library.list().unwrap().len(), 1);
+        library.remove("Mono Red").unwrap();
+        assert!(library.list().unwrap().is_empty());
+        assert!(library.get("Mono Red").is_err());
+    }
+}
@@ -0,0 +1,652 @@
+use crate::decks::mana::{
+    basic_land_color, is_land, parse_mana_cost, produced_colors, ManaCost, COLORS,
+};
+use crate::decks::roles::{card_roles, Role, RoleOverrides};
+use crate::decks::stats::{calculate_deck_stats, DeckStats};
+use crate::decks::{DeckCard, DeckList};
+use crate::scryfall::Card;
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// Settings for building limited decks from a pool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LimitedConfig {
+    /// Nonland cards in each build
+    pub spells: u32,
+    /// Total cards in each build
+    pub deck_size: u32,
+    /// Consider splashing a third color for strong cards
+    pub splash: bool,
+    /// Most cards of the splash color
+    pub max_splash_cards: usize,
+    /// Number of builds to suggest
+    pub builds: usize,
+}
+
+impl Default for LimitedConfig {
+    fn default() -> Self {
+        Self {
+            spells: 23,
+            deck_size: 40,
+            splash: true,
+            max_splash_cards: 2,
+            builds: 3,
+        }
+    }
+}
+
+/// A suggested 40-card build from a pool
+#[derive(Debug, Clone, Serialize)]
+pub struct LimitedBuild {
+    /// Main colors, in WUBRG order
+    pub colors: Vec<String>,
+    pub splash: Option<String>,
+    pub score: f64,
+    /// Nonland cards in the pool castable with the main colors
+    pub playables: u32,
+    /// Basic lands added, by land name
+    pub basics: BTreeMap<String, u32>,
+    /// The build in the main deck, the rest of the pool in the sideboard
+    pub deck: DeckList,
+    pub stats: DeckStats,
+}
+
+impl LimitedBuild {
+    /// Colors like `WU` or `WU+r`
+    pub fn label(&self) -> String {
+        let mut label = self.colors.concat();
+        if let Some(splash) = &self.splash {
+            label.push('+');
+            label.push_str(&splash.to_lowercase());
+        }
+        label
+    }
+}
+
+/// One copy of a card in the pool
+#[derive(Debug, Clone)]
+struct PoolCard {
+    card: Card,
+    cost: ManaCost,
+    colors: Vec<String>,
+    quality: f64,
+}
+
+impl PoolCard {
+    fn new(card: Card, overrides: &RoleOverrides) -> Self {
+        let cost = parse_mana_cost(card.mana_cost.as_deref().unwrap_or(""));
+        let colors = required_colors(&card, &cost);
+        let quality = card_quality(&card, &card_roles(&card, overrides));
+        Self {
+            card,
+            cost,
+            colors,
+            quality,
+        }
+    }
+
+    fn castable_with(&self, colors: &[&str]) -> bool {
+        self.colors
+            .iter()
+            .all(|color| colors.contains(&color.as_str()))
+            && self.cost.pips.iter().all(|pip| {
+                pip.colors
+                    .iter()
+                    .any(|color| colors.contains(&color.as_str()))
+            })
+    }
+}
+
+/// Colors a card needs, from its single-color pips or its color indicator
+fn required_colors(card: &Card, cost: &ManaCost) -> Vec<String> {
+    let mut colors: Vec<String> = COLORS
+        .iter()
+        .filter(|color| cost.pips_of(color) > 0)
+        .map(|color| color.to_string())
+        .collect();
+
+    if cost.pips.is_empty() && card.mana_cost.as_deref().unwrap_or("").is_empty() {
+        colors = card.colors.clone().unwrap_or_default();
+    }
+
+    colors
+}
+
+/// How much a card is worth in limited: rarity as a proxy for power, plus
+/// bonuses for the roles that decide limited games
+fn card_quality(card: &Card, roles: &[Role]) -> f64 {
+    let mut quality = match card.rarity.as_str() {
+        "mythic" => 3.0,
+        "rare" => 2.5,
+        "uncommon" => 1.5,
+        _ => 1.0,
+    };
+
+    for role in roles {
+        quality += match role {
+            Role::Removal => 2.0,
+            Role::Wipe => 1.5,
+            Role::Threat => 1.0,
+            Role::Draw => 0.75,
+            Role::Counterspell => 0.5,
+            Role::Ramp | Role::Tutor => 0.25,
+            Role::Land => 0.0,
+        };
+    }
+
+    if card.cmc >= 7.0 {
+        quality -= 1.0;
+    }
+
+    quality
+}
+
+/// Points lost for a curve that is too slow or too top-heavy
+fn curve_penalty(cards: &[&PoolCard]) -> f64 {
+    let cheap = cards.iter().filter(|card| card.card.cmc <= 2.0).count() as f64;
+    let expensive = cards.iter().filter(|card| card.card.cmc >= 5.0).count() as f64;
+    (5.0 - cheap).max(0.0) + (expensive - 5.0).max(0.0)
+}
+
+fn basic_land_name(color: &str) -> &'static str {
+    match color {
+        "W" => "Plains",
+        "U" => "Island",
+        "B" => "Swamp",
+        "R" => "Mountain",
+        _ => "Forest",
+    }
+}
+
+/// Card details for a basic land, built on another card of the pool so the
+/// deck stats count it as a land and a source of its color
+fn basic_land_card(name: &str, template: &Card) -> Card {
+    let color = basic_land_color(name).unwrap_or("G");
+    Card {
+        id: format!("basic-{}", name.to_lowercase()),
+        oracle_id: None,
+        arena_id: None,
+        name: name.to_string(),
+        mana_cost: None,
+        cmc: 0.0,
+        type_line: format!("Basic Land — {name}"),
+        oracle_text: Some(format!("({{T}}: Add {{{color}}}.)")),
+        power: None,
+        toughness: None,
+        loyalty: None,
+        colors: Some(Vec::new()),
+        color_identity: vec![color.to_string()],
+        keywords: None,
+        produced_mana: Some(vec![color.to_string()]),
+        rarity: "common".to_string(),
+        ..template.clone()
+    }
+}
+
+/// Split basic lands between colors by their share of colored pips, using
+/// largest remainders, with at least two sources for a splash color
+fn split_basics(
+    count: u32,
+    pips: &BTreeMap<String, u32>,
+    splash: Option<&str>,
+    splash_sources: u32,
+) -> BTreeMap<String, u32> {
+    let total: u32 = pips.values().sum();
+    let mut basics: BTreeMap<String, u32> = BTreeMap::new();
+    if count == 0 || total == 0 {
+        return basics;
+    }
+
+    let mut remainders = Vec::new();
+    let mut assigned = 0;
+    for (color, color_pips) in pips {
+        let share = count as f64 * *color_pips as f64 / total as f64;
+        basics.insert(color.clone(), share.floor() as u32);
+        assigned += share.floor() as u32;
+        remainders.push((share - share.floor(), color.clone()));
+    }
+    remainders.sort_by(|a, b| b.0.total_cmp(&a.0));
+    for (_, color) in remainders.iter().take((count - assigned) as usize) {
+        *basics.entry(color.clone()).or_insert(0) += 1;
+    }
+
+    if let Some(splash) = splash {
+        let needed = 2u32.saturating_sub(splash_sources);
+        while basics.get(splash).copied().unwrap_or(0) < needed {
+            let Some(largest) = basics
+                .iter()
+                .filter(|(color, _)| color.as_str() != splash)
+                .max_by_key(|(_, count)| **count)
+                .map(|(color, _)| color.clone())
+            else {
+                break;
+            };
+            *basics.get_mut(&largest).unwrap() -= 1;
+            *basics.entry(splash.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    basics
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(color, count)| (basic_land_name(&color).to_string(), count))
+        .collect()
+}
+
+/// Group single copies back into deck entries, keeping pool order
+fn to_deck_cards(cards: &[&PoolCard]) -> Vec<DeckCard> {
+    let mut entries: Vec<DeckCard> = Vec::new();
+    for pool_card in cards {
+        match entries
+            .iter_mut()
+            .find(|entry| entry.name == pool_card.card.name)
+        {
+            Some(entry) => entry.quantity += 1,
+            None => entries.push(DeckCard {
+                quantity: 1,
+                name: pool_card.card.name.clone(),
+                set_code: Some(pool_card.card.set.clone()),
+                collector_number: Some(pool_card.card.collector_number.clone()),
+                card_details: Some(pool_card.card.clone()),
+            }),
+        }
+    }
+    entries
+}
+
+/// Suggest two-color (plus splash) builds from a sealed or draft pool.
+///
+/// Every color pair is ranked by the quality of its best nonland cards, with
+/// a penalty for a poor curve. Strong cards with a single pip of a third color
+/// are splashed when they beat the weakest cards of the pair. Nonbasic lands
+/// for the deck's colors are played and basics are split by pip ratio.
+/// Cards in the pool's main deck and sideboard all count, and cards need
+/// details to be considered.
+pub fn build_limited(
+    pool: &DeckList,
+    overrides: &RoleOverrides,
+    config: &LimitedConfig,
+) -> Result<Vec<LimitedBuild>> {
+    let mut spells = Vec::new();
+    let mut lands = Vec::new();
+    for entry in pool.main_deck.iter().chain(&pool.sideboard) {
+        let Some(card) = &entry.card_details else {
+            continue;
+        };
+        if card.type_line.contains("Basic") {
+            continue;
+        }
+        for _ in 0..entry.quantity {
+            let pool_card = PoolCard::new(card.clone(), overrides);
+            if is_land(card) {
+                lands.push(pool_card);
+            } else {
+                spells.push(pool_card);
+            }
+        }
+    }
+
+    if spells.is_empty() {
+        return Err(eyre!(
+            "The pool has no nonland cards with card details to build from"
+        ));
+    }
+
+    spells.sort_by(|a, b| {
+        b.quality
+            .total_cmp(&a.quality)
+            .then(a.card.cmc.total_cmp(&b.card.cmc))
+    });
+
+    let mut builds = Vec::new();
+    for (index, first) in COLORS.iter().enumerate() {
+        for second in &COLORS[index + 1..] {
+            let pair = [*first, *second];
+            builds.push(build_pair(&pair, &spells, &lands, pool, config)?);
+        }
+    }
+
+    builds.sort_by(|a, b| b.score.total_cmp(&a.score));
+    builds.truncate(config.builds);
+    Ok(builds)
+}
+
+fn build_pair(
+    pair: &[&str; 2],
+    spells: &[PoolCard],
+    lands: &[PoolCard],
+    pool: &DeckList,
+    config: &LimitedConfig,
+) -> Result<LimitedBuild> {
+    let on_color: Vec<&PoolCard> = spells
+        .iter()
+        .filter(|card| card.castable_with(pair))
+        .collect();
+    let mut picks: Vec<&PoolCard> = on_color
+        .iter()
+        .take(config.spells as usize)
+        .copied()
+        .collect();
+
+    let mut splash = None;
+    if config.splash {
+        let mut best_gain = 0.0;
+        let mut best = None;
+        for color in COLORS.iter().filter(|color| !pair.contains(color)) {
+            let colors = [pair[0], pair[1], *color];
+            let candidates: Vec<&PoolCard> = spells
+                .iter()
+                .filter(|card| {
+                    card.colors.iter().any(|c| c == color)
+                        && card.cost.pips_of(color) == 1
+                        && card.castable_with(&colors)
+                        && card.quality >= 3.0
+                })
+                .take(config.max_splash_cards)
+                .collect();
+
+            let mut trial = picks.clone();
+            let mut gain = 0.0;
+            for candidate in &candidates {
+                if (trial.len() as u32) < config.spells {
+                    gain += candidate.quality;
+                    trial.push(candidate);
+                } else if let Some(weakest) = trial.last() {
+                    // The splash costs consistency, so it has to be a clear upgrade
+                    if candidate.quality > weakest.quality + 1.0 {
+                        gain += candidate.quality - weakest.quality - 1.0;
+                        trial.pop();
+                        trial.insert(0, candidate);
+                    }
+                }
+            }
+
+            if gain > best_gain {
+                best_gain = gain;
+                best = Some((color.to_string(), trial));
+            }
+        }
+
+        if let Some((color, trial)) = best {
+            splash = Some(color);
+            picks = trial;
+        }
+    }
+
+    picks.sort_by(|a, b| {
+        a.card
+            .cmc
+            .total_cmp(&b.card.cmc)
+            .then(a.card.name.cmp(&b.card.name))
+    });
+
+    let deck_colors: Vec<&str> = pair.iter().copied().chain(splash.as_deref()).collect();
+
+    // Nonbasic lands that tap for at least two of the deck's colors
+    let land_slots = config.deck_size.saturating_sub(picks.len() as u32);
+    let nonbasics: Vec<&PoolCard> = lands
+        .iter()
+        .filter(|land| {
+            produced_colors(&land.card)
+                .iter()
+                .filter(|color| deck_colors.contains(&color.as_str()))
+                .count()
+                >= 2
+        })
+        .take(land_slots.min(3) as usize)
+        .collect();
+    let splash_sources = nonbasics
+        .iter()
+        .filter(|land| {
+            splash
+                .as_ref()
+                .is_some_and(|splash| produced_colors(&land.card).contains(splash))
+        })
+        .count() as u32;
+
+    let mut pips: BTreeMap<String, u32> = BTreeMap::new();
+    for color in &deck_colors {
+        let count: u32 = picks.iter().map(|card| card.cost.pips_of(color)).sum();
+        pips.insert(color.to_string(), count.max(1));
+    }
+    let basics = split_basics(
+        land_slots - nonbasics.len() as u32,
+        &pips,
+        splash.as_deref(),
+        splash_sources,
+    );
+
+    let mut main_deck = to_deck_cards(&picks);
+    main_deck.extend(to_deck_cards(&nonbasics));
+    main_deck.extend(basics.iter().map(|(name, quantity)| DeckCard {
+        quantity: *quantity,
+        name: name.clone(),
+        set_code: None,
+        collector_number: None,
+        card_details: picks.first().map(|card| basic_land_card(name, &card.card)),
+    }));
+
+    // Everything else in the pool goes to the sideboard
+    let mut used: HashMap<&str, u32> = HashMap::new();
+    for card in picks.iter().chain(&nonbasics) {
+        *used.entry(card.card.name.as_str()).or_insert(0) += 1;
+    }
+    let mut sideboard = Vec::new();
+    for entry in pool.main_deck.iter().chain(&pool.sideboard) {
+        let in_deck = used.entry(entry.name.as_str()).or_insert(0);
+        let taken = entry.quantity.min(*in_deck);
+        *in_deck -= taken;
+        if entry.quantity > taken {
+            sideboard.push(DeckCard {
+                quantity: entry.quantity - taken,
+                ..entry.clone()
+            });
+        }
+    }
+
+    let deck = DeckList {
+        main_deck,
+        sideboard,
+    };
+    let stats = calculate_deck_stats(&deck)?;
+
+    let quality: f64 = picks.iter().map(|card| card.quality).sum();
+    let depth = on_color.len().saturating_sub(config.spells as usize) as f64;
+    let score = quality - curve_penalty(&picks) + depth * 0.1;
+
+    Ok(LimitedBuild {
+        colors: pair.iter().map(|color| color.to_string()).collect(),
+        splash,
+        score: (score * 10.0).round() / 10.0,
+        playables: on_color.len() as u32,
+        basics,
+        deck,
+        stats,
+    })
+}
+
+/// Find the cards picked in the most recent MTG Arena draft.
+///
+/// Human drafts log `Event_PlayerDraftMakePick` requests with `GrpIds`, bot
+/// drafts log `BotDraft_DraftPick` with `CardIds`. The request payload is a
+/// JSON string inside the logged JSON, so it is parsed again when needed.
+/// Picks are returned as Arena card ids in pick order.
+pub fn parse_arena_draft_picks(log: &str) -> Vec<u32> {
+    let mut draft_id: Option<String> = None;
+    let mut picks = Vec::new();
+
+    for line in log.lines() {
+        if !line.contains("DraftMakePick") && !line.contains("BotDraft_DraftPick") {
+            continue;
+        }
+        let Some(start) = line.find('{') else {
+            continue;
+        };
+        let Ok(value) = serde_json::from_str::<Value>(&line[start..]) else {
+            continue;
+        };
+        let value = match value.get("request").and_then(Value::as_str) {
+            Some(request) => serde_json::from_str(request).unwrap_or(value),
+            None => value,
+        };
+
+        let id = find_key(&value, "DraftId")
+            .or_else(|| find_key(&value, "EventName"))
+            .and_then(|id| id.as_str().map(String::from));
+        if id.is_some() && id != draft_id {
+            draft_id = id;
+            picks.clear();
+        }
+
+        for key in ["GrpIds", "CardIds", "GrpId", "CardId"] {
+            if let Some(found) = find_key(&value, key) {
+                let ids = match found {
+                    Value::Array(ids) => ids.iter().filter_map(card_id).collect(),
+                    other => card_id(other).into_iter().collect::<Vec<_>>(),
+                };
+                if !ids.is_empty() {
+                    picks.extend(ids);
+                    break;
+                }
+            }
+        }
+    }
+
+    picks
+}
+
+fn find_key<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    let object = value.as_object()?;
+    object
+        .get(key)
+        .or_else(|| object.values().find_map(|value| find_key(value, key)))
+}
+
+fn card_id(value: &Value) -> Option<u32> {
+    value
+        .as_u64()
+        .or_else(|| value.as_str().and_then(|id| id.parse().ok()))
+        .map(|id| id as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decks::test_utils::{card, deck_card, land};
+
+    fn creature(name: &str, cost: &str, cmc: f64, rarity: &str, colors: &[&str]) -> Card {
+        let mut card = card(name, Some(cost), cmc, "Creature — Bear");
+        card.rarity = rarity.to_string();
+        card.power = Some("2".to_string());
+        card.colors = Some(colors.iter().map(|c| c.to_string()).collect());
+        card
+    }
+
+    fn removal(name: &str, cost: &str, colors: &[&str]) -> Card {
+        let mut card = card(name, Some(cost), 2.0, "Instant");
+        card.oracle_text = Some("Destroy target creature.".to_string());
+        card.rarity = "uncommon".to_string();
+        card.colors = Some(colors.iter().map(|c| c.to_string()).collect());
+        card
+    }
+
+    fn pool() -> DeckList {
+        let mut main_deck = Vec::new();
+        for index in 0..12 {
+            main_deck.push(deck_card(
+                1,
+                creature(&format!("White {index}"), "{1}{W}", 2.0, "common", &["W"]),
+            ));
+            main_deck.push(deck_card(
+                1,
+                creature(&format!("Black {index}"), "{2}{B}", 3.0, "common", &["B"]),
+            ));
+        }
+        for index in 0..4 {
+            main_deck.push(deck_card(
+                1,
+                creature(&format!("Green {index}"), "{G}", 1.0, "common", &["G"]),
+            ));
+        }
+        main_deck.push(deck_card(2, removal("Murder", "{1}{B}{B}", &["B"])));
+        main_deck.push(deck_card(1, removal("Lightning Strike", "{1}{R}", &["R"])));
+        main_deck.push(deck_card(1, land("Scoured Barrens", &["W", "B"])));
+
+        DeckList {
+            main_deck,
+            sideboard: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_build_limited() {
+        let builds = build_limited(
+            &pool(),
+            &RoleOverrides::default(),
+            &LimitedConfig::default(),
+        )
+        .unwrap();
+        let best = &builds[0];
+
+        assert_eq!(best.colors, vec!["W".to_string(), "B".to_string()]);
+        assert_eq!(best.splash.as_deref(), Some("R"));
+        assert_eq!(best.label(), "WB+r");
+        assert_eq!(builds.len(), 3);
+
+        let main: u32 = best.deck.main_deck.iter().map(|card| card.quantity).sum();
+        assert_eq!(main, 40);
+        assert!(best
+            .deck
+            .main_deck
+            .iter()
+            .any(|card| card.name == "Scoured Barrens"));
+        assert!(best.basics.get("Mountain").is_some_and(|count| *count >= 2));
+        assert!(best
+            .deck
+            .sideboard
+            .iter()
+            .all(|card| card.name.starts_with("Green") || card.name.starts_with("Black")));
+
+        let total: u32 = best
+            .deck
+            .main_deck
+            .iter()
+            .chain(&best.deck.sideboard)
+            .filter(|card| card.card_details.is_some())
+            .map(|card| card.quantity)
+            .sum();
+        assert_eq!(total, 32 + best.basics.values().sum::<u32>());
+        assert_eq!(best.stats.mana_base.land_count, 17);
+        let mountains = best.basics["Mountain"];
+        assert_eq!(best.stats.mana_base.sources[2].color, "R");
+        assert_eq!(best.stats.mana_base.sources[2].total, mountains);
+
+        // Without the splash and the double-pip removal the basics support both colors
+        let mut two_color = pool();
+        two_color
+            .main_deck
+            .retain(|card| !matches!(card.name.as_str(), "Murder" | "Lightning Strike"));
+        let builds = build_limited(
+            &two_color,
+            &RoleOverrides::default(),
+            &LimitedConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(builds[0].label(), "WB");
+        assert_eq!(builds[0].stats.mana_base.land_count, 17);
+        assert!(builds[0].stats.mana_base.under_supported_colors.is_empty());
+    }
+
+    #[test]
+    fn test_parse_arena_draft_picks() {
+        let log = "[UnityCrossThreadLogger]==> Event_PlayerDraftMakePick {\"id\":\"1\",\"request\":\"{\\\"DraftId\\\":\\\"a\\\",\\\"GrpIds\\\":[100],\\\"Pack\\\":1,\\\"Pick\\\":1}\"}\n\
+            [UnityCrossThreadLogger]==> Event_PlayerDraftMakePick {\"id\":\"2\",\"request\":\"{\\\"DraftId\\\":\\\"b\\\",\\\"GrpIds\\\":[200],\\\"Pack\\\":1,\\\"Pick\\\":1}\"}\n\
+            [UnityCrossThreadLogger]==> Event_PlayerDraftMakePick {\"id\":\"3\",\"request\":\"{\\\"DraftId\\\":\\\"b\\\",\\\"GrpIds\\\":[201],\\\"Pack\\\":1,\\\"Pick\\\":2}\"}\n\
+            [UnityCrossThreadLogger]==> BotDraft_DraftPick {\"id\":\"4\",\"request\":\"{\\\"EventName\\\":\\\"b\\\",\\\"PickInfo\\\":{\\\"CardIds\\\":[\\\"202\\\"]}}\"}\n";
+
+        assert_eq!(parse_arena_draft_picks(log), vec![200, 201, 202]);
+        assert!(parse_arena_draft_picks("nothing").is_empty());
+    }
+}
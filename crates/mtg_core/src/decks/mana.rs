@@ -0,0 +1,256 @@
+use crate::scryfall::Card;
+
+/// The five colors of mana in WUBRG order
+pub const COLORS: [&str; 5] = ["W", "U", "B", "R", "G"];
+
+/// A single colored mana symbol from a mana cost.
+///
+/// Hybrid symbols like `{W/U}` list every color that can pay for them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManaPip {
+    pub colors: Vec<String>,
+}
+
+/// A parsed mana cost split into generic mana and colored pips
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManaCost {
+    pub generic: u32,
+    pub pips: Vec<ManaPip>,
+    pub has_x: bool,
+}
+
+impl ManaCost {
+    /// Total amount of mana needed to pay this cost (X counted as zero)
+    pub fn total(&self) -> u32 {
+        self.generic + self.pips.len() as u32
+    }
+
+    /// Number of pips that can only be paid with the given color
+    pub fn pips_of(&self, color: &str) -> u32 {
+        self.pips
+            .iter()
+            .filter(|pip| pip.colors.len() == 1 && pip.colors[0] == color)
+            .count() as u32
+    }
+}
+
+/// Parse a Scryfall mana cost like `{2}{U}{U}` or `{W/U}{B/P}`.
+///
+/// Phyrexian symbols are treated as payable with life, snow and colorless
+/// symbols count as generic mana. Split cards (`{1}{R} // {2}{U}`) only use the
+/// first face.
+pub fn parse_mana_cost(mana_cost: &str) -> ManaCost {
+    let mut cost = ManaCost::default();
+    let first_face = mana_cost.split("//").next().unwrap_or("");
+
+    for symbol in first_face
+        .split('{')
+        .filter_map(|part| part.split('}').next())
+        .filter(|symbol| !symbol.is_empty())
+    {
+        let symbol = symbol.trim().to_uppercase();
+
+        if let Ok(amount) = symbol.parse::<u32>() {
+            cost.generic += amount;
+            continue;
+        }
+
+        match symbol.as_str() {
+            "X" | "Y" | "Z" => cost.has_x = true,
+            "C" | "S" => cost.generic += 1,
+            color if COLORS.contains(&color) => cost.pips.push(ManaPip {
+                colors: vec![color.to_string()],
+            }),
+            hybrid if hybrid.contains('/') => {
+                // Phyrexian mana can always be paid with life
+                if hybrid.ends_with("/P") {
+                    continue;
+                }
+
+                let colors: Vec<String> = hybrid
+                    .split('/')
+                    .filter(|part| COLORS.contains(part))
+                    .map(String::from)
+                    .collect();
+
+                if colors.is_empty() || hybrid.split('/').any(|part| part.parse::<u32>().is_ok()) {
+                    // Two-brid symbols like {2/W} can always be paid with generic mana
+                    cost.generic += 1;
+                } else {
+                    cost.pips.push(ManaPip { colors });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    cost
+}
+
+/// Check whether a card is a land
+pub fn is_land(card: &Card) -> bool {
+    card.type_line
+        .split("//")
+        .next()
+        .unwrap_or("")
+        .contains("Land")
+}
+
+/// Colors of mana a land or mana source can produce.
+///
+/// Uses Scryfall's `produced_mana` when present, falling back to basic land
+/// types in the type line and `Add {X}` clauses in the oracle text.
+pub fn produced_colors(card: &Card) -> Vec<String> {
+    if let Some(produced) = &card.produced_mana {
+        return COLORS
+            .iter()
+            .filter(|color| produced.iter().any(|p| p == *color))
+            .map(|color| color.to_string())
+            .collect();
+    }
+
+    let mut colors = Vec::new();
+    let basic_types = [
+        ("Plains", "W"),
+        ("Island", "U"),
+        ("Swamp", "B"),
+        ("Mountain", "R"),
+        ("Forest", "G"),
+    ];
+    for (land_type, color) in basic_types {
+        if card.type_line.contains(land_type) {
+            colors.push(color.to_string());
+        }
+    }
+
+    if let Some(oracle_text) = &card.oracle_text {
+        if oracle_text.contains("mana of any color") {
+            return COLORS.iter().map(|c| c.to_string()).collect();
+        }
+
+        for line in oracle_text.lines() {
+            if let Some(add_pos) = line.find("Add ") {
+                for color in COLORS {
+                    if line[add_pos..].contains(&format!("{{{color}}}"))
+                        && !colors.iter().any(|c| c == color)
+                    {
+                        colors.push(color.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    COLORS
+        .iter()
+        .filter(|color| colors.iter().any(|c| c == *color))
+        .map(|color| color.to_string())
+        .collect()
+}
+
+/// Colors produced by a basic land, used when a deck has no card details
+pub fn basic_land_color(name: &str) -> Option<&'static str> {
+    match name {
+        "Plains" | "Snow-Covered Plains" => Some("W"),
+        "Island" | "Snow-Covered Island" => Some("U"),
+        "Swamp" | "Snow-Covered Swamp" => Some("B"),
+        "Mountain" | "Snow-Covered Mountain" => Some("R"),
+        "Forest" | "Snow-Covered Forest" => Some("G"),
+        _ => None,
+    }
+}
+
+/// Check whether a set of mana sources can pay a mana cost.
+///
+/// Each source is the list of colors it can produce and provides one mana.
+/// Colored pips are matched to sources with augmenting paths so that dual
+/// lands are assigned where they are needed most.
+pub fn can_pay(cost: &ManaCost, sources: &[Vec<String>]) -> bool {
+    if (sources.len() as u32) < cost.total() {
+        return false;
+    }
+
+    // source index -> pip index currently assigned to it
+    let mut assigned: Vec<Option<usize>> = vec![None; sources.len()];
+
+    for pip_index in 0..cost.pips.len() {
+        let mut visited = vec![false; sources.len()];
+        if !assign_pip(pip_index, cost, sources, &mut assigned, &mut visited) {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn assign_pip(
+    pip_index: usize,
+    cost: &ManaCost,
+    sources: &[Vec<String>],
+    assigned: &mut [Option<usize>],
+    visited: &mut [bool],
+) -> bool {
+    let pip = &cost.pips[pip_index];
+
+    for (source_index, source) in sources.iter().enumerate() {
+        if visited[source_index] || !pip.colors.iter().any(|c| source.contains(c)) {
+            continue;
+        }
+        visited[source_index] = true;
+
+        let can_take = match assigned[source_index] {
+            None => true,
+            Some(other_pip) => assign_pip(other_pip, cost, sources, assigned, visited),
+        };
+
+        if can_take {
+            assigned[source_index] = Some(pip_index);
+            return true;
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sources(lands: &[&str]) -> Vec<Vec<String>> {
+        lands
+            .iter()
+            .map(|colors| colors.chars().map(|c| c.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_mana_cost() {
+        let cost = parse_mana_cost("{2}{U}{U}");
+        assert_eq!(cost.generic, 2);
+        assert_eq!(cost.pips_of("U"), 2);
+        assert_eq!(cost.total(), 4);
+
+        let cost = parse_mana_cost("{X}{R}");
+        assert!(cost.has_x);
+        assert_eq!(cost.total(), 1);
+
+        let cost = parse_mana_cost("{W/U}{B/P}{2/G}");
+        assert_eq!(cost.pips.len(), 1);
+        assert_eq!(cost.pips[0].colors, vec!["W", "U"]);
+        assert_eq!(cost.generic, 1);
+    }
+
+    #[test]
+    fn test_can_pay_with_dual_lands() {
+        let cost = parse_mana_cost("{1}{W}{U}");
+        assert!(can_pay(&cost, &sources(&["WU", "W", "R"])));
+        assert!(!can_pay(&cost, &sources(&["W", "W", "R"])));
+        assert!(!can_pay(&cost, &sources(&["WU", "U"])));
+    }
+
+    #[test]
+    fn test_basic_land_color() {
+        assert_eq!(basic_land_color("Island"), Some("U"));
+        assert_eq!(basic_land_color("Lightning Bolt"), None);
+    }
+}
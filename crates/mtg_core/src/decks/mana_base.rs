@@ -0,0 +1,295 @@
+use crate::decks::mana::{is_land, parse_mana_cost, produced_colors, COLORS};
+use crate::decks::DeckList;
+use crate::scryfall::Card;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Colored sources recommended for a 60-card deck to cast a spell on curve
+/// about 90% of the time, indexed by `[pips - 1][turn - 1]` (Frank Karsten, 2022).
+const SOURCES_60: [[u32; 7]; 4] = [
+    [14, 13, 12, 10, 9, 9, 8],
+    [21, 21, 18, 16, 15, 14, 13],
+    [23, 23, 23, 21, 19, 18, 16],
+    [24, 24, 24, 24, 22, 21, 19],
+];
+
+/// The same table for 40-card limited decks
+const SOURCES_40: [[u32; 7]; 4] = [
+    [9, 9, 8, 7, 6, 6, 5],
+    [14, 14, 12, 11, 10, 9, 9],
+    [16, 16, 16, 14, 13, 12, 11],
+    [17, 17, 17, 17, 16, 14, 13],
+];
+
+/// Number of sources of one color in the main deck
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ColorSources {
+    pub color: String,
+    /// Lands that tap for the color
+    pub lands: u32,
+    /// Lands among those that produce more than one color
+    pub multicolor_lands: u32,
+    /// Fetch lands that can find a land of the color
+    pub fetches: u32,
+    /// Mana rocks and creatures that produce the color
+    pub rocks: u32,
+    pub total: u32,
+}
+
+/// The heaviest colored requirement for a color on a given turn
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipRequirement {
+    pub color: String,
+    pub turn: u32,
+    pub pips: u32,
+    /// The card with this requirement
+    pub card: String,
+    pub recommended_sources: u32,
+    pub available_sources: u32,
+    pub supported: bool,
+}
+
+/// Mana base analysis comparing colored sources with pip requirements
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ManaBaseReport {
+    pub deck_size: u32,
+    pub land_count: u32,
+    pub sources: Vec<ColorSources>,
+    pub requirements: Vec<PipRequirement>,
+    /// Colors with at least one requirement the sources can't support
+    pub under_supported_colors: Vec<String>,
+}
+
+/// Analyze the main deck's colored sources against its spells' pip requirements
+pub fn analyze_mana_base(deck_list: &DeckList) -> ManaBaseReport {
+    let deck_size: u32 = deck_list.main_deck.iter().map(|card| card.quantity).sum();
+    let mut sources: BTreeMap<usize, ColorSources> = BTreeMap::new();
+    let mut land_count = 0;
+
+    let basic_colors: Vec<String> = deck_list
+        .main_deck
+        .iter()
+        .filter_map(|card| card.card_details.as_ref())
+        .filter(|details| details.type_line.contains("Basic"))
+        .flat_map(produced_colors)
+        .collect();
+
+    for deck_card in &deck_list.main_deck {
+        let Some(details) = &deck_card.card_details else {
+            continue;
+        };
+
+        if is_land(details) {
+            land_count += deck_card.quantity;
+
+            if let Some(fetch_colors) = fetchable_colors(details, &basic_colors) {
+                for color in fetch_colors {
+                    let entry = color_entry(&mut sources, &color);
+                    entry.fetches += deck_card.quantity;
+                    entry.total += deck_card.quantity;
+                }
+                continue;
+            }
+
+            let colors = produced_colors(details);
+            for color in &colors {
+                let entry = color_entry(&mut sources, color);
+                entry.lands += deck_card.quantity;
+                entry.total += deck_card.quantity;
+                if colors.len() > 1 {
+                    entry.multicolor_lands += deck_card.quantity;
+                }
+            }
+        } else if is_mana_permanent(details) {
+            for color in produced_colors(details) {
+                let entry = color_entry(&mut sources, &color);
+                entry.rocks += deck_card.quantity;
+                entry.total += deck_card.quantity;
+            }
+        }
+    }
+
+    // Keep the heaviest pip count per color and turn
+    let mut heaviest: BTreeMap<(usize, u32), (u32, String)> = BTreeMap::new();
+    for deck_card in &deck_list.main_deck {
+        let Some(details) = &deck_card.card_details else {
+            continue;
+        };
+        if is_land(details) {
+            continue;
+        }
+
+        let cost = parse_mana_cost(details.mana_cost.as_deref().unwrap_or(""));
+        for (color_index, color) in COLORS.iter().enumerate() {
+            let pips = cost.pips_of(color);
+            if pips == 0 {
+                continue;
+            }
+
+            let turn = (details.cmc as u32).max(pips).clamp(1, 7);
+            let entry = heaviest
+                .entry((color_index, turn))
+                .or_insert((0, String::new()));
+            if pips > entry.0 {
+                *entry = (pips, deck_card.name.clone());
+            }
+        }
+    }
+
+    let mut requirements = Vec::new();
+    let mut under_supported_colors: Vec<String> = Vec::new();
+    for ((color_index, turn), (pips, card)) in heaviest {
+        let color = COLORS[color_index].to_string();
+        let recommended_sources = recommended_sources(pips, turn, deck_size);
+        let available_sources = sources.get(&color_index).map(|s| s.total).unwrap_or(0);
+        let supported = available_sources >= recommended_sources;
+
+        if !supported && !under_supported_colors.contains(&color) {
+            under_supported_colors.push(color.clone());
+        }
+
+        requirements.push(PipRequirement {
+            color,
+            turn,
+            pips,
+            card,
+            recommended_sources,
+            available_sources,
+            supported,
+        });
+    }
+
+    ManaBaseReport {
+        deck_size,
+        land_count,
+        sources: sources.into_values().collect(),
+        requirements,
+        under_supported_colors,
+    }
+}
+
+/// Recommended number of sources to cast a spell with `pips` colored pips on `turn`.
+///
+/// Uses the 40 and 60 card tables directly and scales the 60 card table for
+/// other deck sizes.
+pub fn recommended_sources(pips: u32, turn: u32, deck_size: u32) -> u32 {
+    let pip_index = pips.clamp(1, 4) as usize - 1;
+    let turn_index = turn.clamp(1, 7) as usize - 1;
+
+    match deck_size {
+        40 => SOURCES_40[pip_index][turn_index],
+        60 | 0 => SOURCES_60[pip_index][turn_index],
+        size => {
+            let scaled = SOURCES_60[pip_index][turn_index] as f64 * size as f64 / 60.0;
+            (scaled.ceil() as u32).min(size)
+        }
+    }
+}
+
+fn color_entry<'a>(
+    sources: &'a mut BTreeMap<usize, ColorSources>,
+    color: &str,
+) -> &'a mut ColorSources {
+    let index = COLORS.iter().position(|c| *c == color).unwrap_or(0);
+    sources.entry(index).or_insert_with(|| ColorSources {
+        color: color.to_string(),
+        ..Default::default()
+    })
+}
+
+/// Colors a fetch land can find, or `None` if the land is not a fetch land
+fn fetchable_colors(card: &Card, basic_colors: &[String]) -> Option<Vec<String>> {
+    let oracle_text = card.oracle_text.as_deref()?;
+    if !oracle_text.contains("Search your library for") || card.produced_mana.is_some() {
+        return None;
+    }
+
+    let land_types = [
+        ("Plains", "W"),
+        ("Island", "U"),
+        ("Swamp", "B"),
+        ("Mountain", "R"),
+        ("Forest", "G"),
+    ];
+    let mut colors: Vec<String> = land_types
+        .iter()
+        .filter(|(land_type, _)| oracle_text.contains(land_type))
+        .map(|(_, color)| color.to_string())
+        .collect();
+
+    if colors.is_empty() && oracle_text.contains("basic land card") {
+        colors = COLORS
+            .iter()
+            .filter(|color| basic_colors.iter().any(|c| c == *color))
+            .map(|color| color.to_string())
+            .collect();
+    }
+
+    Some(colors)
+}
+
+/// Nonland permanents that tap for colored mana (mana rocks and dorks)
+fn is_mana_permanent(card: &Card) -> bool {
+    let is_permanent = ["Artifact", "Creature", "Enchantment"]
+        .iter()
+        .any(|card_type| card.type_line.contains(card_type));
+    let taps_for_mana = card
+        .oracle_text
+        .as_deref()
+        .is_some_and(|text| text.contains("{T}: Add"));
+
+    is_permanent && taps_for_mana && card.produced_mana.is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decks::test_utils::{card, deck_card, land, spell};
+
+    #[test]
+    fn test_recommended_sources() {
+        assert_eq!(recommended_sources(1, 1, 60), 14);
+        assert_eq!(recommended_sources(2, 2, 60), 21);
+        assert_eq!(recommended_sources(1, 3, 40), 8);
+        assert_eq!(recommended_sources(1, 1, 80), 19);
+    }
+
+    #[test]
+    fn test_counts_sources_and_flags_colors() {
+        let mut fetch = card("Flooded Strand", None, 0.0, "Land");
+        fetch.oracle_text = Some(
+            "{T}, Pay 1 life, Sacrifice Flooded Strand: Search your library for a Plains or Island card, put it onto the battlefield. Then shuffle.".to_string(),
+        );
+
+        let deck = DeckList {
+            main_deck: vec![
+                deck_card(14, land("Island", &["U"])),
+                deck_card(4, land("Hallowed Fountain", &["W", "U"])),
+                deck_card(4, fetch),
+                deck_card(4, spell("Counterspell", "{U}{U}", 2.0)),
+                deck_card(4, spell("Wrath of God", "{2}{W}{W}", 4.0)),
+                deck_card(30, spell("Opt", "{U}", 1.0)),
+            ],
+            sideboard: vec![],
+        };
+
+        let report = analyze_mana_base(&deck);
+        assert_eq!(report.land_count, 22);
+
+        let blue = report.sources.iter().find(|s| s.color == "U").unwrap();
+        assert_eq!(blue.lands, 18);
+        assert_eq!(blue.multicolor_lands, 4);
+        assert_eq!(blue.fetches, 4);
+        assert_eq!(blue.total, 22);
+
+        let white = report.sources.iter().find(|s| s.color == "W").unwrap();
+        assert_eq!(white.total, 8);
+
+        let wrath = report.requirements.iter().find(|r| r.color == "W").unwrap();
+        assert_eq!(wrath.pips, 2);
+        assert_eq!(wrath.turn, 4);
+        assert!(!wrath.supported);
+
+        assert_eq!(report.under_supported_colors, vec!["W".to_string()]);
+    }
+}
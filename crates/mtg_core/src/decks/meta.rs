@@ -0,0 +1,278 @@
+use crate::decks::mana::{basic_land_color, COLORS};
+use crate::decks::ParsedDeck;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How often a card shows up across the decks of a metagame
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetaCard {
+    pub name: String,
+    /// Decks playing the card in the main deck or sideboard
+    pub decks: u32,
+    /// Share of all decks playing the card
+    pub inclusion_rate: f64,
+    /// Share of all decks playing the card in the main deck
+    pub main_deck_rate: f64,
+    /// Share of all decks playing the card in the sideboard
+    pub sideboard_rate: f64,
+    /// Average copies in the main deck among decks that main deck it
+    pub average_main_copies: f64,
+    /// Average copies in the sideboard among decks that sideboard it
+    pub average_sideboard_copies: f64,
+    /// Average total copies among decks playing the card
+    pub average_copies: f64,
+}
+
+/// Number of decks per color combination
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColorPairShare {
+    /// Colors in WUBRG order, `C` for colorless decks
+    pub colors: String,
+    pub decks: u32,
+    pub share: f64,
+}
+
+/// Aggregated metagame across a set of decks
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetaReport {
+    pub format: Option<String>,
+    pub articles: u32,
+    pub decks: u32,
+    /// Cards sorted by inclusion rate
+    pub cards: Vec<MetaCard>,
+    /// Color combinations sorted by number of decks
+    pub color_pairs: Vec<ColorPairShare>,
+}
+
+#[derive(Default)]
+struct CardTally {
+    decks: u32,
+    main_decks: u32,
+    sideboard_decks: u32,
+    main_copies: u32,
+    sideboard_copies: u32,
+}
+
+/// Aggregate card and color statistics across tournament decks.
+///
+/// `card_colors` maps card names to their colors and is used to work out
+/// each deck's colors. Cards missing from it fall back to their card details,
+/// and basic lands are always counted.
+pub fn aggregate_meta(
+    decks: &[ParsedDeck],
+    card_colors: &HashMap<String, Vec<String>>,
+) -> MetaReport {
+    let mut tallies: HashMap<&str, CardTally> = HashMap::new();
+    let mut color_counts: HashMap<String, u32> = HashMap::new();
+
+    for deck in decks {
+        let mut deck_tallies: HashMap<&str, (u32, u32)> = HashMap::new();
+        for card in &deck.main_deck {
+            deck_tallies.entry(card.name.as_str()).or_default().0 += card.quantity;
+        }
+        for card in &deck.sideboard {
+            deck_tallies.entry(card.name.as_str()).or_default().1 += card.quantity;
+        }
+
+        for (name, (main_copies, sideboard_copies)) in deck_tallies {
+            let tally = tallies.entry(name).or_default();
+            tally.decks += 1;
+            if main_copies > 0 {
+                tally.main_decks += 1;
+                tally.main_copies += main_copies;
+            }
+            if sideboard_copies > 0 {
+                tally.sideboard_decks += 1;
+                tally.sideboard_copies += sideboard_copies;
+            }
+        }
+
+        *color_counts
+            .entry(deck_colors(deck, card_colors))
+            .or_default() += 1;
+    }
+
+    let deck_count = decks.len() as u32;
+    let rate = |count: u32| {
+        if deck_count == 0 {
+            0.0
+        } else {
+            count as f64 / deck_count as f64
+        }
+    };
+    let average = |copies: u32, decks: u32| {
+        if decks == 0 {
+            0.0
+        } else {
+            copies as f64 / decks as f64
+        }
+    };
+
+    let mut cards: Vec<MetaCard> = tallies
+        .into_iter()
+        .map(|(name, tally)| MetaCard {
+            name: name.to_string(),
+            decks: tally.decks,
+            inclusion_rate: rate(tally.decks),
+            main_deck_rate: rate(tally.main_decks),
+            sideboard_rate: rate(tally.sideboard_decks),
+            average_main_copies: average(tally.main_copies, tally.main_decks),
+            average_sideboard_copies: average(tally.sideboard_copies, tally.sideboard_decks),
+            average_copies: average(tally.main_copies + tally.sideboard_copies, tally.decks),
+        })
+        .collect();
+    cards.sort_by(|a, b| {
+        b.decks
+            .cmp(&a.decks)
+            .then_with(|| b.average_copies.total_cmp(&a.average_copies))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let mut color_pairs: Vec<ColorPairShare> = color_counts
+        .into_iter()
+        .map(|(colors, decks)| ColorPairShare {
+            colors,
+            decks,
+            share: rate(decks),
+        })
+        .collect();
+    color_pairs.sort_by(|a, b| b.decks.cmp(&a.decks).then_with(|| a.colors.cmp(&b.colors)));
+
+    MetaReport {
+        format: None,
+        articles: 0,
+        decks: deck_count,
+        cards,
+        color_pairs,
+    }
+}
+
+/// Colors of a deck's main deck in WUBRG order
+pub(crate) fn deck_colors(deck: &ParsedDeck, card_colors: &HashMap<String, Vec<String>>) -> String {
+    let mut colors: Vec<&str> = Vec::new();
+
+    for card in &deck.main_deck {
+        let card_colors: Vec<String> = if let Some(color) = basic_land_color(&card.name) {
+            vec![color.to_string()]
+        } else if let Some(known) = card_colors.get(&card.name) {
+            known.clone()
+        } else if let Some(details) = &card.card_details {
+            details.colors.clone().unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        for color in card_colors {
+            if let Some(known) = COLORS.iter().find(|c| **c == color) {
+                if !colors.contains(known) {
+                    colors.push(known);
+                }
+            }
+        }
+    }
+
+    if colors.is_empty() {
+        return "C".to_string();
+    }
+
+    COLORS
+        .iter()
+        .filter(|color| colors.contains(color))
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decks::DeckCard;
+
+    fn entry(quantity: u32, name: &str) -> DeckCard {
+        DeckCard {
+            quantity,
+            name: name.to_string(),
+            set_code: None,
+            collector_number: None,
+            card_details: None,
+        }
+    }
+
+    fn deck(main_deck: Vec<DeckCard>, sideboard: Vec<DeckCard>) -> ParsedDeck {
+        ParsedDeck {
+            id: String::new(),
+            title: None,
+            subtitle: None,
+            event_date: None,
+            event_name: None,
+            format: None,
+            main_deck,
+            sideboard,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_meta() {
+        let decks = vec![
+            deck(
+                vec![entry(4, "Lightning Bolt"), entry(20, "Mountain")],
+                vec![entry(2, "Counterspell")],
+            ),
+            deck(
+                vec![
+                    entry(2, "Lightning Bolt"),
+                    entry(4, "Counterspell"),
+                    entry(10, "Island"),
+                    entry(10, "Mountain"),
+                ],
+                vec![entry(1, "Lightning Bolt")],
+            ),
+        ];
+
+        let report = aggregate_meta(&decks, &HashMap::new());
+        assert_eq!(report.decks, 2);
+
+        let bolt = report
+            .cards
+            .iter()
+            .find(|c| c.name == "Lightning Bolt")
+            .unwrap();
+        assert_eq!(bolt.decks, 2);
+        assert_eq!(bolt.inclusion_rate, 1.0);
+        assert_eq!(bolt.main_deck_rate, 1.0);
+        assert_eq!(bolt.sideboard_rate, 0.5);
+        assert_eq!(bolt.average_main_copies, 3.0);
+        assert_eq!(bolt.average_copies, 3.5);
+
+        let counterspell = report
+            .cards
+            .iter()
+            .find(|c| c.name == "Counterspell")
+            .unwrap();
+        assert_eq!(counterspell.main_deck_rate, 0.5);
+        assert_eq!(counterspell.sideboard_rate, 0.5);
+
+        let colors: Vec<&str> = report
+            .color_pairs
+            .iter()
+            .map(|p| p.colors.as_str())
+            .collect();
+        assert_eq!(colors, vec!["R", "UR"]);
+    }
+
+    #[test]
+    fn test_deck_colors_uses_color_map() {
+        let mut card_colors = HashMap::new();
+        card_colors.insert("Counterspell".to_string(), vec!["U".to_string()]);
+        card_colors.insert(
+            "Lightning Helix".to_string(),
+            vec!["R".to_string(), "W".to_string()],
+        );
+
+        let deck = deck(
+            vec![entry(4, "Counterspell"), entry(4, "Lightning Helix")],
+            vec![],
+        );
+        assert_eq!(deck_colors(&deck, &card_colors), "WUR");
+        assert_eq!(deck_colors(&self::deck(vec![], vec![]), &card_colors), "C");
+    }
+}
@@ -1,13 +1,46 @@
 use serde::{Deserialize, Serialize};
 
+pub mod archetypes;
 pub mod compare;
+pub mod library;
+pub mod limited;
+pub mod mana;
+pub mod mana_base;
+pub mod meta;
 pub mod parser;
+pub mod pricing;
 pub mod ranked;
+pub mod roles;
+pub mod sideboard;
+pub mod simulate;
 pub mod stats;
 pub mod utils;
 
-pub use compare::{compare_decks, load_deck_from_id_or_url, CardEntry, DeckComparison};
+#[cfg(test)]
+pub(crate) mod test_utils;
+
+pub use archetypes::{
+    cluster_archetypes, guess_archetype, ArchetypeDefinitions, ArchetypeGuess, ArchetypeReport,
+    ClusterConfig, LikelyCard, Similarity,
+};
+pub use compare::{
+    apply_deck_diff, compare_decks, compare_many_decks, diff_decks, format_deck_diff,
+    load_deck_from_id_or_url, parse_deck_diff, CardEntry, CardRole, DeckComparison, DeckDiff,
+    DiffLine, MultiDeckComparison,
+};
+pub use library::{DeckLibrary, LibraryDeck, SaveOutcome};
+pub use limited::{build_limited, LimitedBuild, LimitedConfig};
+pub use mana_base::{analyze_mana_base, ManaBaseReport};
+pub use meta::{aggregate_meta, MetaReport};
 pub use parser::parse_deck_list;
+pub use pricing::{
+    calculate_deck_pricing, find_budget_alternatives, BudgetReport, Currency, DeckPricing,
+};
+pub use roles::{analyze_roles, Role, RoleBreakdown, RoleOverrides};
+pub use sideboard::{
+    parse_sideboard_plans, validate_plan, PlanIssue, PlanValidation, SideboardPlan,
+};
+pub use simulate::{simulate_deck, KeepHeuristic, SimulationConfig, SimulationReport};
 pub use stats::{calculate_deck_stats, DeckStats};
 pub use utils::generate_short_hash;
 
@@ -0,0 +1,400 @@
+use crate::decks::{DeckCard, DeckList};
+use crate::scryfall::{Card, ScryfallClient, SearchParams};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Currencies Scryfall reports prices in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Currency {
+    Usd,
+    Eur,
+    Tix,
+}
+
+impl Currency {
+    pub const ALL: [Currency; 3] = [Currency::Usd, Currency::Eur, Currency::Tix];
+
+    /// Key of the currency in Scryfall's `prices` object
+    pub fn key(&self) -> &'static str {
+        match self {
+            Currency::Usd => "usd",
+            Currency::Eur => "eur",
+            Currency::Tix => "tix",
+        }
+    }
+
+    /// Format an amount in this currency
+    pub fn format(&self, amount: f64) -> String {
+        match self {
+            Currency::Usd => format!("${amount:.2}"),
+            Currency::Eur => format!("€{amount:.2}"),
+            Currency::Tix => format!("{amount:.2} tix"),
+        }
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key().to_uppercase())
+    }
+}
+
+impl FromStr for Currency {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "usd" | "$" => Ok(Currency::Usd),
+            "eur" | "€" => Ok(Currency::Eur),
+            "tix" => Ok(Currency::Tix),
+            other => Err(color_eyre::eyre::eyre!(
+                "Unknown currency '{}'. Use usd, eur or tix",
+                other
+            )),
+        }
+    }
+}
+
+/// Price of a single card in a currency.
+///
+/// Falls back to the foil price when a printing has no nonfoil price.
+pub fn card_price(card: &Card, currency: Currency) -> Option<f64> {
+    let prices = card.prices.as_ref()?;
+    let key = currency.key();
+
+    [
+        key.to_string(),
+        format!("{key}_foil"),
+        format!("{key}_etched"),
+    ]
+    .iter()
+    .filter_map(|key| prices.get(key))
+    .filter_map(|value| value.as_str())
+    .filter_map(|value| value.parse::<f64>().ok())
+    .next()
+}
+
+/// Main deck and sideboard cost in one currency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrencyTotal {
+    pub currency: Currency,
+    pub main_deck: f64,
+    pub sideboard: f64,
+    pub total: f64,
+}
+
+/// A card with its price in the report currency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PricedCard {
+    pub name: String,
+    pub quantity: u32,
+    pub set_code: Option<String>,
+    pub unit_price: f64,
+    pub total_price: f64,
+}
+
+/// Deck cost per currency and its most expensive cards
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeckPricing {
+    pub totals: Vec<CurrencyTotal>,
+    /// Most expensive cards by total price in the first currency with prices
+    pub most_expensive: Vec<PricedCard>,
+    /// Cards without card details or without a price
+    pub cards_without_prices: Vec<String>,
+}
+
+impl DeckPricing {
+    /// Total for a currency
+    pub fn total(&self, currency: Currency) -> Option<&CurrencyTotal> {
+        self.totals.iter().find(|total| total.currency == currency)
+    }
+}
+
+/// Number of cards listed in `DeckPricing::most_expensive`
+const MOST_EXPENSIVE_LIMIT: usize = 10;
+
+/// Calculate the deck cost in every currency using the printings in the deck
+pub fn calculate_deck_pricing(deck_list: &DeckList) -> DeckPricing {
+    let all_cards = || deck_list.main_deck.iter().chain(deck_list.sideboard.iter());
+
+    let totals: Vec<CurrencyTotal> = Currency::ALL
+        .iter()
+        .filter(|currency| all_cards().any(|card| price_of(card, **currency).is_some()))
+        .map(|currency| {
+            let main_deck = section_total(&deck_list.main_deck, *currency);
+            let sideboard = section_total(&deck_list.sideboard, *currency);
+            CurrencyTotal {
+                currency: *currency,
+                main_deck,
+                sideboard,
+                total: main_deck + sideboard,
+            }
+        })
+        .collect();
+
+    let mut most_expensive = Vec::new();
+    if let Some(currency) = totals.first().map(|total| total.currency) {
+        // Merge main deck and sideboard copies of the same card
+        let mut by_name: HashMap<&str, PricedCard> = HashMap::new();
+        for deck_card in all_cards() {
+            let Some(unit_price) = price_of(deck_card, currency) else {
+                continue;
+            };
+            let entry = by_name
+                .entry(deck_card.name.as_str())
+                .or_insert_with(|| PricedCard {
+                    name: deck_card.name.clone(),
+                    quantity: 0,
+                    set_code: deck_card.set_code.clone(),
+                    unit_price,
+                    total_price: 0.0,
+                });
+            entry.quantity += deck_card.quantity;
+            entry.total_price += unit_price * deck_card.quantity as f64;
+        }
+
+        most_expensive = by_name.into_values().collect();
+        most_expensive.sort_by(|a, b| {
+            b.total_price
+                .total_cmp(&a.total_price)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        most_expensive.truncate(MOST_EXPENSIVE_LIMIT);
+    }
+
+    let mut cards_without_prices: Vec<String> = all_cards()
+        .filter(|card| Currency::ALL.iter().all(|c| price_of(card, *c).is_none()))
+        .map(|card| card.name.clone())
+        .collect();
+    cards_without_prices.sort();
+    cards_without_prices.dedup();
+
+    DeckPricing {
+        totals,
+        most_expensive,
+        cards_without_prices,
+    }
+}
+
+fn price_of(deck_card: &DeckCard, currency: Currency) -> Option<f64> {
+    deck_card
+        .card_details
+        .as_ref()
+        .and_then(|card| card_price(card, currency))
+}
+
+fn section_total(cards: &[DeckCard], currency: Currency) -> f64 {
+    cards
+        .iter()
+        .filter_map(|card| price_of(card, currency).map(|price| price * card.quantity as f64))
+        .sum()
+}
+
+/// Cheaper printing suggested for a card in the deck
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetSuggestion {
+    pub name: String,
+    pub quantity: u32,
+    pub current_set: Option<String>,
+    pub current_price: Option<f64>,
+    pub cheapest_set: String,
+    pub cheapest_set_name: String,
+    pub cheapest_collector_number: String,
+    pub cheapest_price: f64,
+    /// Savings for all copies, zero when the current price is unknown
+    pub savings: f64,
+}
+
+/// Deck cost when every card uses its cheapest printing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetReport {
+    pub currency: Currency,
+    pub current_total: f64,
+    pub budget_total: f64,
+    pub savings: f64,
+    pub suggestions: Vec<BudgetSuggestion>,
+    /// Cards without a price in the deck, left out of both totals
+    #[serde(default)]
+    pub unpriced: Vec<String>,
+}
+
+/// Pick the cheapest printing of a card in a currency
+pub fn cheapest_printing(prints: &[Card], currency: Currency) -> Option<(&Card, f64)> {
+    prints
+        .iter()
+        .filter(|card| !card.digital || currency == Currency::Tix)
+        .filter_map(|card| card_price(card, currency).map(|price| (card, price)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Look up every printing of each card and suggest the cheapest ones.
+///
+/// Cards are merged across main deck and sideboard. Cards whose printing in
+/// the deck is already the cheapest are left out of the suggestions.
+pub async fn find_budget_alternatives(
+    deck_list: &DeckList,
+    currency: Currency,
+    client: &ScryfallClient,
+) -> Result<BudgetReport> {
+    let mut cards: Vec<(&DeckCard, u32)> = Vec::new();
+    for deck_card in deck_list.main_deck.iter().chain(deck_list.sideboard.iter()) {
+        match cards
+            .iter_mut()
+            .find(|(card, _)| card.name == deck_card.name)
+        {
+            Some((_, quantity)) => *quantity += deck_card.quantity,
+            None => cards.push((deck_card, deck_card.quantity)),
+        }
+    }
+
+    let mut current_total = 0.0;
+    let mut budget_total = 0.0;
+    let mut suggestions = Vec::new();
+    let mut unpriced = Vec::new();
+
+    for (deck_card, quantity) in cards {
+        let current_price = price_of(deck_card, currency);
+
+        let params = SearchParams {
+            q: format!("!\"{}\"", deck_card.name),
+            unique: Some("prints".to_string()),
+            order: Some(currency.key().to_string()),
+            dir: Some("asc".to_string()),
+            ..Default::default()
+        };
+        let prints = match client.search_cards(params).await {
+            Ok(response) => response.data,
+            Err(e) => {
+                if client.is_verbose() {
+                    eprintln!("Failed to search printings of '{}': {}", deck_card.name, e);
+                }
+                Vec::new()
+            }
+        };
+
+        let cheapest = cheapest_printing(&prints, currency);
+        match budget_prices(current_price, cheapest.map(|(_, price)| price)) {
+            Some((current, budget)) => {
+                current_total += current * quantity as f64;
+                budget_total += budget * quantity as f64;
+            }
+            None => unpriced.push(deck_card.name.clone()),
+        }
+
+        let Some((card, cheapest_price)) = cheapest else {
+            continue;
+        };
+        if current_price.is_some_and(|price| price <= cheapest_price) {
+            continue;
+        }
+
+        suggestions.push(BudgetSuggestion {
+            name: deck_card.name.clone(),
+            quantity,
+            current_set: deck_card.set_code.clone(),
+            current_price,
+            cheapest_set: card.set.clone(),
+            cheapest_set_name: card.set_name.clone(),
+            cheapest_collector_number: card.collector_number.clone(),
+            cheapest_price,
+            savings: current_price
+                .map(|price| (price - cheapest_price) * quantity as f64)
+                .unwrap_or(0.0),
+        });
+    }
+
+    suggestions.sort_by(|a, b| b.savings.total_cmp(&a.savings));
+
+    Ok(BudgetReport {
+        currency,
+        current_total,
+        budget_total,
+        savings: current_total - budget_total,
+        suggestions,
+        unpriced,
+    })
+}
+
+/// Current and budget unit price of a card for the totals.
+///
+/// Cards without a price in the deck are left out of both totals, otherwise the
+/// cheapest printing would count against nothing and inflate the budget total.
+fn budget_prices(current: Option<f64>, cheapest: Option<f64>) -> Option<(f64, f64)> {
+    let current = current?;
+    Some((
+        current,
+        cheapest.map_or(current, |cheapest| cheapest.min(current)),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decks::test_utils::{deck_card, spell};
+    use serde_json::json;
+
+    fn priced(name: &str, set: &str, usd: Option<&str>, eur: Option<&str>) -> Card {
+        let mut card = spell(name, "{R}", 1.0);
+        card.set = set.to_string();
+        card.prices = Some(json!({ "usd": usd, "usd_foil": null, "eur": eur, "tix": null }));
+        card
+    }
+
+    #[test]
+    fn test_card_price_falls_back_to_foil() {
+        let mut card = priced("Lightning Bolt", "2xm", None, Some("1.50"));
+        card.prices = Some(json!({ "usd": null, "usd_foil": "4.00", "eur": "1.50" }));
+        assert_eq!(card_price(&card, Currency::Usd), Some(4.0));
+        assert_eq!(card_price(&card, Currency::Eur), Some(1.5));
+        assert_eq!(card_price(&card, Currency::Tix), None);
+    }
+
+    #[test]
+    fn test_calculate_deck_pricing() {
+        let deck = DeckList {
+            main_deck: vec![
+                deck_card(4, priced("Ragavan", "mh2", Some("50.00"), Some("45.00"))),
+                deck_card(4, priced("Lightning Bolt", "2xm", Some("1.00"), None)),
+            ],
+            sideboard: vec![deck_card(2, priced("Ragavan", "mh2", Some("50.00"), None))],
+        };
+
+        let pricing = calculate_deck_pricing(&deck);
+        let usd = pricing.total(Currency::Usd).unwrap();
+        assert_eq!(usd.main_deck, 204.0);
+        assert_eq!(usd.sideboard, 100.0);
+        assert_eq!(usd.total, 304.0);
+        assert_eq!(pricing.total(Currency::Eur).unwrap().total, 180.0);
+        assert!(pricing.total(Currency::Tix).is_none());
+
+        assert_eq!(pricing.most_expensive[0].name, "Ragavan");
+        assert_eq!(pricing.most_expensive[0].quantity, 6);
+        assert!(pricing.cards_without_prices.is_empty());
+    }
+
+    #[test]
+    fn test_cheapest_printing() {
+        let prints = vec![
+            priced("Lightning Bolt", "lea", Some("400.00"), None),
+            priced("Lightning Bolt", "2xm", Some("1.20"), None),
+            priced("Lightning Bolt", "sld", None, None),
+        ];
+        let (card, price) = cheapest_printing(&prints, Currency::Usd).unwrap();
+        assert_eq!(card.set, "2xm");
+        assert_eq!(price, 1.2);
+        assert!(cheapest_printing(&prints, Currency::Tix).is_none());
+    }
+
+    #[test]
+    fn test_budget_prices_skip_unpriced_cards() {
+        assert_eq!(budget_prices(Some(5.0), Some(1.0)), Some((5.0, 1.0)));
+        assert_eq!(budget_prices(Some(5.0), None), Some((5.0, 5.0)));
+        // A printing the search ranks cheapest never raises the budget total
+        assert_eq!(budget_prices(Some(1.0), Some(2.0)), Some((1.0, 1.0)));
+        assert_eq!(budget_prices(None, Some(1.0)), None);
+    }
+}
@@ -206,6 +206,23 @@ impl RankedDecksClient {
         Ok(ParsedDecksResponse { url, decks })
     }
 
+    /// Fetch and parse the decks of a listed article, caching the parsed decks per article
+    pub async fn fetch_article_decks_cached(
+        &self,
+        article: &ContentfulItemWithId,
+    ) -> Result<Vec<ParsedDeck>> {
+        let cache_key = format!("ranked_article_decks_{}", article.id);
+        if let Some(decks) = self.cache.get(&cache_key).await? {
+            return Ok(decks);
+        }
+
+        let url = self.construct_article_link(&article.item.fields);
+        let decks = self.parse_decks_from_url(&url).await?;
+        self.cache.insert(&cache_key, decks.clone()).await?;
+
+        Ok(decks)
+    }
+
     /// Resolve an identifier to a URL (handles both URLs and cached IDs)
     async fn resolve_url(&self, identifier: &str) -> Result<String> {
         if identifier.starts_with("http://") || identifier.starts_with("https://") {
@@ -0,0 +1,436 @@
+use crate::decks::DeckList;
+use crate::scryfall::Card;
+use color_eyre::{eyre::eyre, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// What a card does in a deck
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    Removal,
+    Counterspell,
+    Draw,
+    Ramp,
+    Tutor,
+    Wipe,
+    Threat,
+    Land,
+}
+
+impl Role {
+    pub const ALL: [Role; 8] = [
+        Role::Removal,
+        Role::Counterspell,
+        Role::Draw,
+        Role::Ramp,
+        Role::Tutor,
+        Role::Wipe,
+        Role::Threat,
+        Role::Land,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Role::Removal => "removal",
+            Role::Counterspell => "counterspell",
+            Role::Draw => "draw",
+            Role::Ramp => "ramp",
+            Role::Tutor => "tutor",
+            Role::Wipe => "wipe",
+            Role::Threat => "threat",
+            Role::Land => "land",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Role::Removal => "Removal",
+            Role::Counterspell => "Counterspell",
+            Role::Draw => "Card Draw",
+            Role::Ramp => "Ramp",
+            Role::Tutor => "Tutor",
+            Role::Wipe => "Board Wipe",
+            Role::Threat => "Threat",
+            Role::Land => "Land",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for Role {
+    type Err = color_eyre::eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let key = s.trim().to_lowercase();
+        Role::ALL
+            .into_iter()
+            .find(|role| role.key() == key)
+            .ok_or_else(|| {
+                eyre!(
+                    "Unknown role '{}'. Use one of: {}",
+                    s,
+                    Role::ALL.map(|role| role.key()).join(", ")
+                )
+            })
+    }
+}
+
+/// Roles set by hand for cards the oracle text rules get wrong
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RoleOverrides {
+    /// Card name to the card's roles, replacing the detected ones
+    #[serde(default)]
+    pub cards: BTreeMap<String, Vec<Role>>,
+}
+
+impl RoleOverrides {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| eyre!("Failed to read '{}': {}", path.display(), e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| eyre!("Invalid role tags in '{}': {}", path.display(), e))
+    }
+
+    /// Load overrides from a file, or none if the file doesn't exist
+    pub fn load_or_default(path: &Path) -> Result<Self> {
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<&Vec<Role>> {
+        self.cards
+            .iter()
+            .find(|(card, _)| card.eq_ignore_ascii_case(name))
+            .map(|(_, roles)| roles)
+    }
+}
+
+/// Default location of the role tag file, `MTG_ROLES_FILE` overrides it
+pub fn default_roles_path() -> PathBuf {
+    if let Ok(path) = std::env::var("MTG_ROLES_FILE") {
+        if !path.trim().is_empty() {
+            return PathBuf::from(path);
+        }
+    }
+
+    dirs::config_dir()
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("mtg")
+        .join("roles.json")
+}
+
+struct RolePatterns {
+    wipe: Regex,
+    removal: Regex,
+    counterspell: Regex,
+    draw: Regex,
+    land_search: Regex,
+    tutor: Regex,
+    ramp: Regex,
+}
+
+fn patterns() -> &'static RolePatterns {
+    static PATTERNS: OnceLock<RolePatterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        let regex = |pattern: &str| Regex::new(pattern).expect("role pattern should compile");
+        RolePatterns {
+            wipe: regex(
+                r"(destroy|exile) all (other )?(creatures|nonland permanents|permanents|artifacts|enchantments|planeswalkers)|all (other )?creatures get -|deals? \d+ damage to each (other )?creature|return all (other )?(creatures|nonland permanents) to",
+            ),
+            removal: regex(
+                r"(destroy|exile) (up to (one|two|three) )?(other )?target|damage to (any target|target (creature|planeswalker|battle|permanent))|target creature (an opponent controls )?gets -\d+/-\d+|fights? (target|another target|up to one target)|(target|each) (player|opponent) sacrifices|return target (creature|nonland permanent|permanent)[^.]* to (its|their) owner's hand",
+            ),
+            counterspell: regex(r"counter (target|up to one target|all)"),
+            draw: regex(
+                r"draws? (a|an additional|two|three|four|five|seven|x|that many) cards?|draw cards equal",
+            ),
+            land_search: regex(
+                r"search your library for (up to (one|two|three) )?(a |an |two |three )?(basic |snow )?(land|plains|island|swamp|mountain|forest)",
+            ),
+            tutor: regex(r"search your library for (a|an|up to one|two|three) "),
+            ramp: regex(
+                r"\badd \{|add (one|two|three) mana|mana of any (one )?color|play an additional land|put (it|them|a land card|that card) onto the battlefield",
+            ),
+        }
+    })
+}
+
+/// Keywords that make a two-power creature hard to block
+const EVASION: &[&str] = &[
+    "Flying",
+    "Trample",
+    "Menace",
+    "Shadow",
+    "Fear",
+    "Intimidate",
+    "Skulk",
+    "Horsemanship",
+];
+
+/// Detect a card's roles from its type line, oracle text and keywords
+pub fn detect_roles(card: &Card) -> Vec<Role> {
+    let mut roles = Vec::new();
+    let type_line = card.type_line.split("//").next().unwrap_or_default();
+
+    if type_line.contains("Land") {
+        roles.push(Role::Land);
+    }
+
+    let text = card
+        .oracle_text
+        .as_deref()
+        .unwrap_or_default()
+        .to_lowercase();
+    let patterns = patterns();
+
+    if patterns.wipe.is_match(&text) {
+        roles.push(Role::Wipe);
+    }
+    if patterns.removal.is_match(&text) {
+        roles.push(Role::Removal);
+    }
+    if patterns.counterspell.is_match(&text) {
+        roles.push(Role::Counterspell);
+    }
+    if patterns.draw.is_match(&text) {
+        roles.push(Role::Draw);
+    }
+
+    let land_search = patterns.land_search.is_match(&text);
+    if !roles.contains(&Role::Land) && (land_search || patterns.ramp.is_match(&text)) {
+        roles.push(Role::Ramp);
+    }
+    if !land_search && patterns.tutor.is_match(&text) {
+        roles.push(Role::Tutor);
+    }
+
+    if is_threat(card, type_line) {
+        roles.push(Role::Threat);
+    }
+
+    roles.sort();
+    roles
+}
+
+fn is_threat(card: &Card, type_line: &str) -> bool {
+    if type_line.contains("Planeswalker") {
+        return true;
+    }
+    if !type_line.contains("Creature") && !type_line.contains("Vehicle") {
+        return false;
+    }
+
+    let power: u32 = card
+        .power
+        .as_deref()
+        .and_then(|power| power.parse().ok())
+        .unwrap_or(0);
+    let evasive = card
+        .keywords
+        .as_ref()
+        .is_some_and(|keywords| keywords.iter().any(|k| EVASION.contains(&k.as_str())));
+
+    power >= 3 || (power >= 2 && evasive)
+}
+
+/// Roles of a card, taking overrides into account
+pub fn card_roles(card: &Card, overrides: &RoleOverrides) -> Vec<Role> {
+    match overrides.get(&card.name) {
+        Some(roles) => roles.clone(),
+        None => detect_roles(card),
+    }
+}
+
+/// The main deck cards filling one role
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleCount {
+    pub role: Role,
+    /// Copies in the main deck
+    pub count: u32,
+    pub cards: Vec<String>,
+    /// Copies per mana value
+    pub curve: BTreeMap<u32, u32>,
+}
+
+/// Functional breakdown of a main deck
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RoleBreakdown {
+    /// Roles with at least one card, in role order
+    pub roles: Vec<RoleCount>,
+    /// Roles of each card, by card name
+    pub cards: BTreeMap<String, Vec<Role>>,
+}
+
+impl RoleBreakdown {
+    pub fn count(&self, role: Role) -> u32 {
+        self.roles
+            .iter()
+            .find(|count| count.role == role)
+            .map_or(0, |count| count.count)
+    }
+}
+
+/// Tag the main deck cards with roles and count them per role.
+///
+/// Cards without details are skipped.
+pub fn analyze_roles(deck_list: &DeckList, overrides: &RoleOverrides) -> RoleBreakdown {
+    let mut counts: HashMap<Role, RoleCount> = HashMap::new();
+    let mut cards = BTreeMap::new();
+
+    for card in &deck_list.main_deck {
+        let Some(details) = &card.card_details else {
+            continue;
+        };
+
+        let roles = card_roles(details, overrides);
+        for role in &roles {
+            let count = counts.entry(*role).or_insert_with(|| RoleCount {
+                role: *role,
+                count: 0,
+                cards: Vec::new(),
+                curve: BTreeMap::new(),
+            });
+            count.count += card.quantity;
+            if !count.cards.contains(&card.name) {
+                count.cards.push(card.name.clone());
+            }
+            *count.curve.entry(details.cmc as u32).or_default() += card.quantity;
+        }
+        cards.insert(card.name.clone(), roles);
+    }
+
+    let mut roles: Vec<RoleCount> = counts.into_values().collect();
+    roles.sort_by_key(|count| count.role);
+
+    RoleBreakdown { roles, cards }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decks::test_utils::{card, deck_card, land};
+
+    fn with_text(mut card: Card, text: &str) -> Card {
+        card.oracle_text = Some(text.to_string());
+        card
+    }
+
+    fn creature(name: &str, power: &str, keywords: &[&str]) -> Card {
+        let mut card = card(name, Some("{1}{G}"), 2.0, "Creature — Beast");
+        card.power = Some(power.to_string());
+        card.keywords = Some(keywords.iter().map(|k| k.to_string()).collect());
+        card
+    }
+
+    #[test]
+    fn test_detect_roles() {
+        let spell = |name: &str, text: &str| {
+            detect_roles(&with_text(card(name, Some("{R}"), 1.0, "Instant"), text))
+        };
+
+        assert_eq!(
+            spell(
+                "Lightning Bolt",
+                "Lightning Bolt deals 3 damage to any target."
+            ),
+            vec![Role::Removal]
+        );
+        assert_eq!(
+            spell("Counterspell", "Counter target spell."),
+            vec![Role::Counterspell]
+        );
+        assert_eq!(
+            spell(
+                "Wrath of God",
+                "Destroy all creatures. They can't be regenerated."
+            ),
+            vec![Role::Wipe]
+        );
+        assert_eq!(spell("Opt", "Scry 1.\nDraw a card."), vec![Role::Draw]);
+        assert_eq!(
+            spell(
+                "Rampant Growth",
+                "Search your library for a basic land card, put that card onto the battlefield tapped, then shuffle."
+            ),
+            vec![Role::Ramp]
+        );
+        assert_eq!(
+            spell(
+                "Demonic Tutor",
+                "Search your library for a card, put that card into your hand, then shuffle."
+            ),
+            vec![Role::Tutor]
+        );
+        assert_eq!(
+            detect_roles(&with_text(
+                card("Llanowar Elves", Some("{G}"), 1.0, "Creature — Elf Druid"),
+                "{T}: Add {G}."
+            )),
+            vec![Role::Ramp]
+        );
+
+        assert_eq!(detect_roles(&land("Forest", &["G"])), vec![Role::Land]);
+        assert_eq!(
+            detect_roles(&creature("Grizzly Bears", "2", &[])),
+            Vec::<Role>::new()
+        );
+        assert_eq!(
+            detect_roles(&creature("Serra Angel", "4", &["Flying", "Vigilance"])),
+            vec![Role::Threat]
+        );
+        assert_eq!(
+            detect_roles(&creature("Wind Drake", "2", &["Flying"])),
+            vec![Role::Threat]
+        );
+    }
+
+    #[test]
+    fn test_analyze_roles_with_overrides() {
+        let bolt = with_text(
+            card("Lightning Bolt", Some("{R}"), 1.0, "Instant"),
+            "Lightning Bolt deals 3 damage to any target.",
+        );
+        let deck = DeckList {
+            main_deck: vec![
+                deck_card(4, bolt),
+                deck_card(2, creature("Grizzly Bears", "2", &[])),
+                deck_card(20, land("Mountain", &["R"])),
+            ],
+            sideboard: vec![],
+        };
+
+        let breakdown = analyze_roles(&deck, &RoleOverrides::default());
+        assert_eq!(breakdown.count(Role::Removal), 4);
+        assert_eq!(breakdown.count(Role::Land), 20);
+        assert_eq!(breakdown.count(Role::Threat), 0);
+        assert_eq!(breakdown.roles[0].curve[&1], 4);
+
+        let overrides: RoleOverrides =
+            serde_json::from_str(r#"{"cards": {"grizzly bears": ["threat", "draw"]}}"#).unwrap();
+        let breakdown = analyze_roles(&deck, &overrides);
+        assert_eq!(breakdown.count(Role::Threat), 2);
+        assert_eq!(breakdown.count(Role::Draw), 2);
+        assert_eq!(
+            breakdown.cards["Grizzly Bears"],
+            vec![Role::Threat, Role::Draw]
+        );
+
+        assert_eq!("wipe".parse::<Role>().unwrap(), Role::Wipe);
+        assert!("burn".parse::<Role>().is_err());
+    }
+}
@@ -0,0 +1,451 @@
+use crate::decks::mana::basic_land_color;
+use crate::decks::{DeckCard, DeckList};
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Copies of a card moved in or out when sideboarding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanCard {
+    pub quantity: u32,
+    pub name: String,
+}
+
+/// Cards to bring in and take out against one opposing archetype
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SideboardPlan {
+    pub archetype: String,
+    #[serde(default, rename = "in")]
+    pub cards_in: Vec<PlanCard>,
+    #[serde(default, rename = "out")]
+    pub cards_out: Vec<PlanCard>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+impl SideboardPlan {
+    pub fn count_in(&self) -> u32 {
+        self.cards_in.iter().map(|card| card.quantity).sum()
+    }
+
+    pub fn count_out(&self) -> u32 {
+        self.cards_out.iter().map(|card| card.quantity).sum()
+    }
+}
+
+/// Problems that make a sideboard plan unusable with a deck
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PlanIssue {
+    /// The number of cards in and out differ
+    Unbalanced { cards_in: u32, cards_out: u32 },
+    /// A card brought in is not in the sideboard, or not enough copies of it
+    NotInSideboard {
+        name: String,
+        wanted: u32,
+        available: u32,
+    },
+    /// A card taken out is not in the main deck, or not enough copies of it
+    NotInMainDeck {
+        name: String,
+        wanted: u32,
+        available: u32,
+    },
+    /// The post-board main deck is smaller than the minimum deck size
+    DeckTooSmall { size: u32, minimum: u32 },
+    /// The post-board main deck has more than four copies of a card
+    TooManyCopies { name: String, copies: u32 },
+}
+
+impl fmt::Display for PlanIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanIssue::Unbalanced {
+                cards_in,
+                cards_out,
+            } => write!(f, "{cards_in} cards in but {cards_out} cards out"),
+            PlanIssue::NotInSideboard {
+                name,
+                wanted,
+                available,
+            } => write!(
+                f,
+                "brings in {wanted} {name} but the sideboard has {available}"
+            ),
+            PlanIssue::NotInMainDeck {
+                name,
+                wanted,
+                available,
+            } => write!(
+                f,
+                "takes out {wanted} {name} but the main deck has {available}"
+            ),
+            PlanIssue::DeckTooSmall { size, minimum } => {
+                write!(f, "main deck has {size} cards, fewer than {minimum}")
+            }
+            PlanIssue::TooManyCopies { name, copies } => {
+                write!(f, "main deck has {copies} copies of {name}")
+            }
+        }
+    }
+}
+
+/// A plan checked against a deck together with the post-board deck list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanValidation {
+    pub plan: SideboardPlan,
+    pub issues: Vec<PlanIssue>,
+    /// The deck after sideboarding, with missing cards skipped
+    pub deck_list: DeckList,
+}
+
+impl PlanValidation {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Parse sideboard plans from text.
+///
+/// Each plan starts with a `vs <archetype>` line followed by `+N Card` lines
+/// for cards to bring in and `-N Card` lines for cards to take out. Any other
+/// line is kept as a note for the current plan and `#` starts a comment.
+///
+/// ```text
+/// vs Mono Red
+/// +2 Aether Gust
+/// -2 Thought Scour
+/// Stay on the draw.
+/// ```
+pub fn parse_sideboard_plans(content: &str) -> Result<Vec<SideboardPlan>> {
+    let mut plans: Vec<SideboardPlan> = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(archetype) = strip_prefix_ignore_case(line, "vs ") {
+            let archetype = archetype.trim().trim_end_matches(':').trim();
+            if archetype.is_empty() {
+                return Err(eyre!("Line {}: missing archetype after 'vs'", index + 1));
+            }
+            plans.push(SideboardPlan {
+                archetype: archetype.to_string(),
+                ..Default::default()
+            });
+            continue;
+        }
+
+        let plan = plans.last_mut().ok_or_else(|| {
+            eyre!(
+                "Line {}: expected a 'vs <archetype>' line before '{}'",
+                index + 1,
+                line
+            )
+        })?;
+
+        let sign = line.chars().next();
+        if matches!(sign, Some('+') | Some('-')) {
+            let card = parse_plan_card(&line[1..])
+                .ok_or_else(|| eyre!("Line {}: expected '+N Card' or '-N Card'", index + 1))?;
+            if sign == Some('+') {
+                plan.cards_in.push(card);
+            } else {
+                plan.cards_out.push(card);
+            }
+        } else {
+            plan.notes = Some(match plan.notes.take() {
+                Some(notes) => format!("{notes}\n{line}"),
+                None => line.to_string(),
+            });
+        }
+    }
+
+    Ok(plans)
+}
+
+/// Format sideboard plans in the text format read by [`parse_sideboard_plans`]
+pub fn format_sideboard_plans(plans: &[SideboardPlan]) -> String {
+    let mut output = String::new();
+    for (index, plan) in plans.iter().enumerate() {
+        if index > 0 {
+            output.push('\n');
+        }
+        output.push_str(&format!("vs {}\n", plan.archetype));
+        for card in &plan.cards_in {
+            output.push_str(&format!("+{} {}\n", card.quantity, card.name));
+        }
+        for card in &plan.cards_out {
+            output.push_str(&format!("-{} {}\n", card.quantity, card.name));
+        }
+        if let Some(notes) = &plan.notes {
+            for line in notes.lines() {
+                output.push_str(line);
+                output.push('\n');
+            }
+        }
+    }
+    output
+}
+
+fn strip_prefix_ignore_case<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+    line.get(..prefix.len())
+        .filter(|start| start.eq_ignore_ascii_case(prefix))
+        .map(|_| &line[prefix.len()..])
+}
+
+fn parse_plan_card(text: &str) -> Option<PlanCard> {
+    let text = text.trim();
+    let (quantity, name) = match text.split_once(char::is_whitespace) {
+        Some((quantity, name)) => (quantity.trim_end_matches('x').parse().ok()?, name.trim()),
+        None => return None,
+    };
+    if quantity == 0 || name.is_empty() {
+        return None;
+    }
+    Some(PlanCard {
+        quantity,
+        name: name.to_string(),
+    })
+}
+
+/// Check a plan against a deck and build the post-board deck list.
+///
+/// The minimum deck size is 60, or 40 for decks that start below 60 cards.
+pub fn validate_plan(deck_list: &DeckList, plan: &SideboardPlan) -> PlanValidation {
+    let mut issues = Vec::new();
+
+    let (cards_in, cards_out) = (plan.count_in(), plan.count_out());
+    if cards_in != cards_out {
+        issues.push(PlanIssue::Unbalanced {
+            cards_in,
+            cards_out,
+        });
+    }
+
+    let mut main = counts(&deck_list.main_deck);
+    let mut sideboard = counts(&deck_list.sideboard);
+    // Cards boarded out are not in the sideboard to begin with, so ins are checked before
+    // any outs are moved over
+    let original_sideboard = sideboard.clone();
+
+    for card in &plan.cards_out {
+        let available = main.get(&card.name.to_lowercase()).map_or(0, |c| c.1);
+        if available < card.quantity {
+            issues.push(PlanIssue::NotInMainDeck {
+                name: card.name.clone(),
+                wanted: card.quantity,
+                available,
+            });
+        }
+        move_copies(&mut main, &mut sideboard, &card.name, card.quantity);
+    }
+
+    for card in &plan.cards_in {
+        let available = original_sideboard
+            .get(&card.name.to_lowercase())
+            .map_or(0, |c| c.1);
+        if available < card.quantity {
+            issues.push(PlanIssue::NotInSideboard {
+                name: card.name.clone(),
+                wanted: card.quantity,
+                available,
+            });
+        }
+        move_copies(&mut sideboard, &mut main, &card.name, card.quantity);
+    }
+
+    let original_size: u32 = deck_list.main_deck.iter().map(|c| c.quantity).sum();
+    let minimum = if original_size >= 60 { 60 } else { 40 };
+    let size: u32 = main.values().map(|(_, quantity)| quantity).sum();
+    if size < minimum {
+        issues.push(PlanIssue::DeckTooSmall { size, minimum });
+    }
+
+    for (name, copies) in main.values() {
+        if *copies > 4 && basic_land_color(name).is_none() && name != "Wastes" {
+            issues.push(PlanIssue::TooManyCopies {
+                name: name.clone(),
+                copies: *copies,
+            });
+        }
+    }
+
+    PlanValidation {
+        plan: plan.clone(),
+        issues,
+        deck_list: DeckList {
+            main_deck: to_cards(main, &deck_list.main_deck, &deck_list.sideboard),
+            sideboard: to_cards(sideboard, &deck_list.sideboard, &deck_list.main_deck),
+        },
+    }
+}
+
+/// Card counts keyed by lowercase name, keeping the first spelling seen
+fn counts(cards: &[DeckCard]) -> BTreeMap<String, (String, u32)> {
+    let mut counts: BTreeMap<String, (String, u32)> = BTreeMap::new();
+    for card in cards {
+        counts
+            .entry(card.name.to_lowercase())
+            .or_insert_with(|| (card.name.clone(), 0))
+            .1 += card.quantity;
+    }
+    counts
+}
+
+/// Move up to `quantity` copies of a card between sections
+fn move_copies(
+    from: &mut BTreeMap<String, (String, u32)>,
+    to: &mut BTreeMap<String, (String, u32)>,
+    name: &str,
+    quantity: u32,
+) {
+    let key = name.to_lowercase();
+    let Some((spelling, available)) = from.get_mut(&key) else {
+        return;
+    };
+    let moved = quantity.min(*available);
+    *available -= moved;
+    let spelling = spelling.clone();
+    if *available == 0 {
+        from.remove(&key);
+    }
+    if moved > 0 {
+        to.entry(key).or_insert_with(|| (spelling, 0)).1 += moved;
+    }
+}
+
+/// Turn counts back into deck cards, in the original deck order where possible
+fn to_cards(
+    counts: BTreeMap<String, (String, u32)>,
+    order: &[DeckCard],
+    other: &[DeckCard],
+) -> Vec<DeckCard> {
+    let mut remaining = counts;
+    let mut cards = Vec::new();
+
+    for card in order.iter().chain(other) {
+        if let Some((_, quantity)) = remaining.remove(&card.name.to_lowercase()) {
+            cards.push(DeckCard {
+                quantity,
+                ..card.clone()
+            });
+        }
+    }
+
+    cards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(quantity: u32, name: &str) -> DeckCard {
+        DeckCard {
+            quantity,
+            name: name.to_string(),
+            set_code: None,
+            collector_number: None,
+            card_details: None,
+        }
+    }
+
+    fn deck() -> DeckList {
+        DeckList {
+            main_deck: vec![
+                entry(4, "Lightning Bolt"),
+                entry(4, "Opt"),
+                entry(32, "Island"),
+                entry(20, "Mountain"),
+            ],
+            sideboard: vec![entry(3, "Negate"), entry(2, "Abrade")],
+        }
+    }
+
+    #[test]
+    fn test_parse_sideboard_plans() {
+        let plans = parse_sideboard_plans(
+            "# Izzet guide\nvs Mono Red:\n+2 Abrade\n-2 Opt\nStay on the draw.\n\nVS Control\n+3 Negate\n-3 Lightning Bolt\n",
+        )
+        .unwrap();
+
+        assert_eq!(plans.len(), 2);
+        assert_eq!(plans[0].archetype, "Mono Red");
+        assert_eq!(plans[0].cards_in[0].name, "Abrade");
+        assert_eq!(plans[0].cards_out[0].quantity, 2);
+        assert_eq!(plans[0].notes.as_deref(), Some("Stay on the draw."));
+        assert_eq!(plans[1].count_in(), 3);
+
+        assert_eq!(
+            parse_sideboard_plans(&format_sideboard_plans(&plans)).unwrap(),
+            plans
+        );
+        assert!(parse_sideboard_plans("+2 Abrade").is_err());
+    }
+
+    #[test]
+    fn test_validate_plan() {
+        let plans = parse_sideboard_plans("vs Mono Red\n+2 abrade\n-2 Opt\n").unwrap();
+        let validation = validate_plan(&deck(), &plans[0]);
+
+        assert!(validation.is_valid(), "{:?}", validation.issues);
+        let main = &validation.deck_list.main_deck;
+        assert_eq!(main.iter().map(|c| c.quantity).sum::<u32>(), 60);
+        assert!(main.iter().any(|c| c.name == "Opt" && c.quantity == 2));
+        assert!(main.iter().any(|c| c.name == "Abrade" && c.quantity == 2));
+        assert!(!validation
+            .deck_list
+            .sideboard
+            .iter()
+            .any(|c| c.name == "Abrade"));
+    }
+
+    #[test]
+    fn test_validate_plan_issues() {
+        let plans = parse_sideboard_plans(
+            "vs Control\n+3 Negate\n+1 Duress\n-4 Lightning Bolt\n-1 Shock\n",
+        )
+        .unwrap();
+        let issues = validate_plan(&deck(), &plans[0]).issues;
+
+        assert!(issues.contains(&PlanIssue::NotInSideboard {
+            name: "Duress".to_string(),
+            wanted: 1,
+            available: 0,
+        }));
+        assert!(issues.contains(&PlanIssue::NotInMainDeck {
+            name: "Shock".to_string(),
+            wanted: 1,
+            available: 0,
+        }));
+        assert!(issues.contains(&PlanIssue::DeckTooSmall {
+            size: 59,
+            minimum: 60,
+        }));
+
+        // A card boarded out is not in the sideboard to board back in
+        let plans = parse_sideboard_plans("vs Burn\n+2 Opt\n-2 Opt\n").unwrap();
+        assert_eq!(
+            validate_plan(&deck(), &plans[0]).issues,
+            vec![PlanIssue::NotInSideboard {
+                name: "Opt".to_string(),
+                wanted: 2,
+                available: 0,
+            }]
+        );
+
+        let plans = parse_sideboard_plans("vs Aggro\n+1 Lightning Bolt\n-1 Opt\n").unwrap();
+        let mut deck = deck();
+        deck.sideboard.push(entry(1, "Lightning Bolt"));
+        assert_eq!(
+            validate_plan(&deck, &plans[0]).issues,
+            vec![PlanIssue::TooManyCopies {
+                name: "Lightning Bolt".to_string(),
+                copies: 5,
+            }]
+        );
+    }
+}
@@ -0,0 +1,466 @@
+use crate::decks::mana::{
+    basic_land_color, can_pay, is_land, parse_mana_cost, produced_colors, ManaCost,
+};
+use crate::decks::DeckList;
+use color_eyre::{eyre::eyre, Result};
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const OPENING_HAND_SIZE: u32 = 7;
+
+/// Heuristic used to decide whether an opening hand is kept
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeepHeuristic {
+    /// Minimum number of lands in a keepable hand
+    pub min_lands: u32,
+    /// Maximum number of lands in a keepable hand
+    pub max_lands: u32,
+    /// Hands of this size or smaller are always kept
+    pub min_hand_size: u32,
+}
+
+impl Default for KeepHeuristic {
+    fn default() -> Self {
+        Self {
+            min_lands: 2,
+            max_lands: 5,
+            min_hand_size: 5,
+        }
+    }
+}
+
+/// Configuration for a goldfish simulation run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationConfig {
+    pub iterations: u32,
+    pub seed: u64,
+    pub on_the_play: bool,
+    pub turns: u32,
+    pub keep: KeepHeuristic,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 10_000,
+            seed: 0,
+            on_the_play: true,
+            turns: 6,
+            keep: KeepHeuristic::default(),
+        }
+    }
+}
+
+/// Rate of hitting the land drop on a given turn
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnRate {
+    pub turn: u32,
+    pub rate: f64,
+}
+
+/// How often a card can be cast on curve
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardCurveRate {
+    pub name: String,
+    pub mana_cost: String,
+    pub mana_value: u32,
+    pub copies: u32,
+    /// Lands in play on turn `mana_value` can pay the card's cost
+    pub castable_rate: f64,
+    /// The card is also in hand on that turn
+    pub on_curve_rate: f64,
+}
+
+/// Aggregated results of a goldfish simulation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationReport {
+    pub iterations: u32,
+    pub seed: u64,
+    pub on_the_play: bool,
+    pub deck_size: u32,
+    pub land_count: u32,
+    pub average_opening_lands: f64,
+    /// Fraction of opening seven-card hands with each number of lands
+    pub opening_hand_lands: BTreeMap<u32, f64>,
+    /// Chance to keep a hand of each size, given that size was reached
+    pub keep_rates: BTreeMap<u32, f64>,
+    /// Fraction of games that started with each final hand size
+    pub final_hand_sizes: BTreeMap<u32, f64>,
+    pub average_mulligans: f64,
+    pub land_drops: Vec<TurnRate>,
+    pub castable_on_curve: Vec<CardCurveRate>,
+    /// Cards that could not be simulated because they have no card details
+    pub cards_without_details: Vec<String>,
+}
+
+/// A unique card prepared for simulation
+#[derive(Debug, Clone)]
+struct SimCard {
+    name: String,
+    mana_cost: String,
+    land_colors: Option<Vec<String>>,
+    cost: Option<ManaCost>,
+    mana_value: u32,
+    copies: u32,
+}
+
+/// Run a seeded goldfish and mulligan simulation over a deck's main deck
+pub fn simulate_deck(deck_list: &DeckList, config: &SimulationConfig) -> Result<SimulationReport> {
+    if config.iterations == 0 {
+        return Err(eyre!("Number of iterations must be greater than zero"));
+    }
+
+    let (cards, cards_without_details) = prepare_cards(deck_list);
+    let library: Vec<usize> = cards
+        .iter()
+        .enumerate()
+        .flat_map(|(index, card)| std::iter::repeat_n(index, card.copies as usize))
+        .collect();
+
+    if library.len() < OPENING_HAND_SIZE as usize {
+        return Err(eyre!(
+            "Deck needs at least {} main deck cards to simulate, found {}",
+            OPENING_HAND_SIZE,
+            library.len()
+        ));
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(config.seed);
+    let mut opening_lands: BTreeMap<u32, u32> = BTreeMap::new();
+    let mut reached: BTreeMap<u32, u32> = BTreeMap::new();
+    let mut kept: BTreeMap<u32, u32> = BTreeMap::new();
+    let mut total_mulligans = 0u32;
+    let mut land_drop_hits = vec![0u32; config.turns as usize];
+    let mut castable_hits = vec![0u32; cards.len()];
+    let mut on_curve_hits = vec![0u32; cards.len()];
+
+    for _ in 0..config.iterations {
+        let mut deck = library.clone();
+        let mut mulligans = 0;
+
+        let mut hand = loop {
+            deck.shuffle(&mut rng);
+            let hand_size = OPENING_HAND_SIZE.saturating_sub(mulligans);
+            *reached.entry(hand_size).or_insert(0) += 1;
+
+            let seven: Vec<usize> = deck[..OPENING_HAND_SIZE as usize].to_vec();
+            if mulligans == 0 {
+                let lands = seven.iter().filter(|&&i| cards[i].is_land()).count() as u32;
+                *opening_lands.entry(lands).or_insert(0) += 1;
+            }
+
+            let hand = bottom_cards(seven, mulligans, &cards, &config.keep);
+            let lands = hand.iter().filter(|&&i| cards[i].is_land()).count() as u32;
+
+            let keepable = lands >= config.keep.min_lands && lands <= config.keep.max_lands;
+            if keepable || hand_size <= config.keep.min_hand_size || hand_size <= 1 {
+                *kept.entry(hand_size).or_insert(0) += 1;
+                break hand;
+            }
+
+            mulligans += 1;
+        };
+        total_mulligans += mulligans;
+
+        let mut next_draw = OPENING_HAND_SIZE as usize;
+        let mut battlefield: Vec<Vec<String>> = Vec::new();
+
+        for turn in 1..=config.turns {
+            let skips_draw = turn == 1 && config.on_the_play;
+            if !skips_draw && next_draw < deck.len() {
+                hand.push(deck[next_draw]);
+                next_draw += 1;
+            }
+
+            if let Some(position) = choose_land(&hand, &cards, &battlefield) {
+                let land = hand.remove(position);
+                battlefield.push(cards[land].land_colors.clone().unwrap_or_default());
+            }
+
+            if battlefield.len() as u32 >= turn {
+                land_drop_hits[turn as usize - 1] += 1;
+            }
+
+            for (index, card) in cards.iter().enumerate() {
+                if card.mana_value != turn {
+                    continue;
+                }
+                if let Some(cost) = &card.cost {
+                    if can_pay(cost, &battlefield) {
+                        castable_hits[index] += 1;
+                        if hand.contains(&index) {
+                            on_curve_hits[index] += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let iterations = config.iterations as f64;
+    let land_count = cards
+        .iter()
+        .filter(|card| card.is_land())
+        .map(|card| card.copies)
+        .sum();
+
+    let average_opening_lands = opening_lands
+        .iter()
+        .map(|(lands, count)| (*lands * *count) as f64)
+        .sum::<f64>()
+        / iterations;
+
+    let mut castable_on_curve: Vec<CardCurveRate> = cards
+        .iter()
+        .enumerate()
+        .filter(|(_, card)| {
+            card.cost.is_some() && card.mana_value >= 1 && card.mana_value <= config.turns
+        })
+        .map(|(index, card)| CardCurveRate {
+            name: card.name.clone(),
+            mana_cost: card.mana_cost.clone(),
+            mana_value: card.mana_value,
+            copies: card.copies,
+            castable_rate: castable_hits[index] as f64 / iterations,
+            on_curve_rate: on_curve_hits[index] as f64 / iterations,
+        })
+        .collect();
+    castable_on_curve.sort_by(|a, b| a.mana_value.cmp(&b.mana_value).then(a.name.cmp(&b.name)));
+
+    Ok(SimulationReport {
+        iterations: config.iterations,
+        seed: config.seed,
+        on_the_play: config.on_the_play,
+        deck_size: library.len() as u32,
+        land_count,
+        average_opening_lands,
+        opening_hand_lands: opening_lands
+            .into_iter()
+            .map(|(lands, count)| (lands, count as f64 / iterations))
+            .collect(),
+        keep_rates: reached
+            .iter()
+            .map(|(size, count)| {
+                let kept_count = kept.get(size).copied().unwrap_or(0);
+                (*size, kept_count as f64 / *count as f64)
+            })
+            .collect(),
+        final_hand_sizes: kept
+            .into_iter()
+            .map(|(size, count)| (size, count as f64 / iterations))
+            .collect(),
+        average_mulligans: total_mulligans as f64 / iterations,
+        land_drops: land_drop_hits
+            .into_iter()
+            .enumerate()
+            .map(|(turn, hits)| TurnRate {
+                turn: turn as u32 + 1,
+                rate: hits as f64 / iterations,
+            })
+            .collect(),
+        castable_on_curve,
+        cards_without_details,
+    })
+}
+
+impl SimCard {
+    fn is_land(&self) -> bool {
+        self.land_colors.is_some()
+    }
+}
+
+/// Turn the main deck into unique simulation cards
+fn prepare_cards(deck_list: &DeckList) -> (Vec<SimCard>, Vec<String>) {
+    let mut cards: Vec<SimCard> = Vec::new();
+    let mut without_details = Vec::new();
+
+    for deck_card in &deck_list.main_deck {
+        if let Some(existing) = cards.iter_mut().find(|c| c.name == deck_card.name) {
+            existing.copies += deck_card.quantity;
+            continue;
+        }
+
+        let card = match &deck_card.card_details {
+            Some(details) if is_land(details) => SimCard {
+                name: deck_card.name.clone(),
+                mana_cost: String::new(),
+                land_colors: Some(produced_colors(details)),
+                cost: None,
+                mana_value: 0,
+                copies: deck_card.quantity,
+            },
+            Some(details) => {
+                let mana_cost = details.mana_cost.clone().unwrap_or_default();
+                SimCard {
+                    name: deck_card.name.clone(),
+                    cost: Some(parse_mana_cost(&mana_cost)),
+                    mana_cost,
+                    land_colors: None,
+                    mana_value: details.cmc as u32,
+                    copies: deck_card.quantity,
+                }
+            }
+            None => {
+                let land_colors =
+                    basic_land_color(&deck_card.name).map(|color| vec![color.to_string()]);
+                if land_colors.is_none() {
+                    without_details.push(deck_card.name.clone());
+                }
+                SimCard {
+                    name: deck_card.name.clone(),
+                    mana_cost: String::new(),
+                    land_colors,
+                    cost: None,
+                    mana_value: 0,
+                    copies: deck_card.quantity,
+                }
+            }
+        };
+        cards.push(card);
+    }
+
+    (cards, without_details)
+}
+
+/// Put `count` cards from a seven-card hand on the bottom (London mulligan).
+///
+/// Lands are bottomed first when the hand has too many of them, otherwise the
+/// most expensive spells go to the bottom.
+fn bottom_cards(
+    mut hand: Vec<usize>,
+    count: u32,
+    cards: &[SimCard],
+    keep: &KeepHeuristic,
+) -> Vec<usize> {
+    for _ in 0..count.min(hand.len() as u32) {
+        let lands = hand.iter().filter(|&&i| cards[i].is_land()).count() as u32;
+        let spells = hand.len() as u32 - lands;
+
+        let bottom_land = spells == 0 || (lands > keep.max_lands.min(hand.len() as u32 - 1));
+        let position = if bottom_land {
+            hand.iter().position(|&i| cards[i].is_land())
+        } else {
+            hand.iter()
+                .enumerate()
+                .filter(|(_, &i)| !cards[i].is_land())
+                .max_by_key(|(_, &i)| cards[i].mana_value)
+                .map(|(position, _)| position)
+        };
+
+        if let Some(position) = position {
+            hand.remove(position);
+        }
+    }
+
+    hand
+}
+
+/// Pick the land to play, preferring lands that add colors not yet on the battlefield
+fn choose_land(hand: &[usize], cards: &[SimCard], battlefield: &[Vec<String>]) -> Option<usize> {
+    hand.iter()
+        .enumerate()
+        .filter_map(|(position, &index)| {
+            cards[index].land_colors.as_ref().map(|colors| {
+                let new_colors = colors
+                    .iter()
+                    .filter(|color| !battlefield.iter().any(|land| land.contains(color)))
+                    .count();
+                (position, (new_colors, colors.len()))
+            })
+        })
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+        .map(|(position, _)| position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decks::test_utils::{deck_card, land, spell};
+
+    fn mono_red_deck() -> DeckList {
+        DeckList {
+            main_deck: vec![
+                deck_card(24, land("Mountain", &["R"])),
+                deck_card(20, spell("Lightning Bolt", "{R}", 1.0)),
+                deck_card(16, spell("Lava Coil", "{1}{R}", 2.0)),
+            ],
+            sideboard: vec![],
+        }
+    }
+
+    #[test]
+    fn test_simulation_is_deterministic() {
+        let config = SimulationConfig {
+            iterations: 500,
+            seed: 42,
+            ..Default::default()
+        };
+        let deck = mono_red_deck();
+
+        let first = simulate_deck(&deck, &config).unwrap();
+        let second = simulate_deck(&deck, &config).unwrap();
+
+        assert_eq!(first.average_mulligans, second.average_mulligans);
+        assert_eq!(first.opening_hand_lands, second.opening_hand_lands);
+        assert_eq!(first.land_drops[2].rate, second.land_drops[2].rate);
+    }
+
+    #[test]
+    fn test_simulation_report_shape() {
+        let config = SimulationConfig {
+            iterations: 1000,
+            seed: 7,
+            turns: 4,
+            ..Default::default()
+        };
+        let report = simulate_deck(&mono_red_deck(), &config).unwrap();
+
+        assert_eq!(report.deck_size, 60);
+        assert_eq!(report.land_count, 24);
+        assert_eq!(report.land_drops.len(), 4);
+        assert!(report.land_drops[0].rate > report.land_drops[3].rate);
+        assert!(report.average_opening_lands > 2.0 && report.average_opening_lands < 4.0);
+
+        let total: f64 = report.final_hand_sizes.values().sum();
+        assert!((total - 1.0).abs() < 1e-9);
+
+        // Mono red with only Mountains can always cast Bolt once a land is down
+        let bolt = report
+            .castable_on_curve
+            .iter()
+            .find(|card| card.name == "Lightning Bolt")
+            .unwrap();
+        assert_eq!(bolt.castable_rate, report.land_drops[0].rate);
+        assert!(bolt.on_curve_rate <= bolt.castable_rate);
+    }
+
+    #[test]
+    fn test_off_color_spell_is_never_castable() {
+        let deck = DeckList {
+            main_deck: vec![
+                deck_card(30, land("Mountain", &["R"])),
+                deck_card(30, spell("Opt", "{U}", 1.0)),
+            ],
+            sideboard: vec![],
+        };
+        let config = SimulationConfig {
+            iterations: 200,
+            ..Default::default()
+        };
+        let report = simulate_deck(&deck, &config).unwrap();
+
+        assert_eq!(report.castable_on_curve[0].castable_rate, 0.0);
+    }
+
+    #[test]
+    fn test_small_deck_is_rejected() {
+        let deck = DeckList {
+            main_deck: vec![deck_card(5, land("Mountain", &["R"]))],
+            sideboard: vec![],
+        };
+        assert!(simulate_deck(&deck, &SimulationConfig::default()).is_err());
+    }
+}
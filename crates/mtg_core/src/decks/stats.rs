@@ -1,12 +1,16 @@
 use crate::cache::{CacheStore, DiskCacheBuilder};
+use crate::decks::mana_base::{analyze_mana_base, ManaBaseReport};
+use crate::decks::pricing::{calculate_deck_pricing, DeckPricing};
+use crate::decks::roles::{analyze_roles, RoleBreakdown, RoleOverrides};
 use crate::decks::{DeckCard, DeckList, ParsedDeck};
 use crate::scryfall::{Card, ScryfallClient};
 use color_eyre::{eyre::eyre, Result};
 use futures::future::join_all;
+use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 
 /// Statistics calculated from a deck list
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DeckStats {
     pub total_cards: u32,
     pub main_deck_cards: u32,
@@ -18,6 +22,10 @@ pub struct DeckStats {
     pub type_distribution: HashMap<String, u32>,
     pub rarity_distribution: HashMap<String, u32>,
     pub format_legality: HashMap<String, bool>,
+    pub mana_base: ManaBaseReport,
+    pub pricing: DeckPricing,
+    /// Main deck roles without user overrides, see [`analyze_roles`]
+    pub roles: RoleBreakdown,
 }
 
 /// Calculate comprehensive statistics from a deck list
@@ -202,6 +210,9 @@ pub fn calculate_deck_stats(deck_list: &DeckList) -> Result<DeckStats> {
         type_distribution,
         rarity_distribution,
         format_legality: final_format_legality,
+        mana_base: analyze_mana_base(deck_list),
+        pricing: calculate_deck_pricing(deck_list),
+        roles: analyze_roles(deck_list, &RoleOverrides::default()),
     })
 }
 
@@ -221,126 +232,6 @@ fn extract_primary_type(type_line: &str) -> String {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::decks::DeckCard;
-    use crate::scryfall::types::Card;
-
-    fn create_test_card(name: &str, cmc: f64, type_line: &str, rarity: &str) -> DeckCard {
-        use serde_json::json;
-
-        let legalities = json!({
-            "standard": "legal",
-            "modern": "legal"
-        });
-
-        DeckCard {
-            quantity: 1,
-            name: name.to_string(),
-            set_code: None,
-            collector_number: None,
-            card_details: Some(Card {
-                object: "card".to_string(),
-                id: "test".to_string(),
-                oracle_id: None,
-                multiverse_ids: None,
-                mtgo_id: None,
-                arena_id: None,
-                tcgplayer_id: None,
-                cardmarket_id: None,
-                name: name.to_string(),
-                lang: "en".to_string(),
-                released_at: "2021-01-01".to_string(),
-                uri: "".to_string(),
-                scryfall_uri: "".to_string(),
-                layout: "normal".to_string(),
-                highres_image: false,
-                image_status: "".to_string(),
-                image_uris: None,
-                mana_cost: None,
-                cmc,
-                type_line: type_line.to_string(),
-                oracle_text: None,
-                power: None,
-                toughness: None,
-                loyalty: None,
-                colors: None,
-                color_identity: vec!["R".to_string()],
-                keywords: None,
-                legalities,
-                games: vec![],
-                reserved: false,
-                foil: false,
-                nonfoil: true,
-                finishes: vec![],
-                oversized: false,
-                promo: false,
-                reprint: false,
-                variation: false,
-                set_id: "".to_string(),
-                set: "".to_string(),
-                set_name: "".to_string(),
-                set_type: "".to_string(),
-                set_uri: "".to_string(),
-                set_search_uri: "".to_string(),
-                scryfall_set_uri: "".to_string(),
-                rulings_uri: "".to_string(),
-                prints_search_uri: "".to_string(),
-                collector_number: "1".to_string(),
-                digital: false,
-                rarity: rarity.to_string(),
-                flavor_text: None,
-                card_back_id: None,
-                artist: None,
-                artist_ids: None,
-                illustration_id: None,
-                border_color: "black".to_string(),
-                frame: "2015".to_string(),
-                security_stamp: None,
-                full_art: false,
-                textless: false,
-                booster: true,
-                story_spotlight: false,
-                edhrec_rank: None,
-                penny_rank: None,
-                prices: None,
-                related_uris: None,
-                purchase_uris: None,
-            }),
-        }
-    }
-
-    #[test]
-    fn test_calculate_basic_stats() {
-        let deck_list = DeckList {
-            main_deck: vec![
-                create_test_card("Lightning Bolt", 1.0, "Instant", "common"),
-                create_test_card("Grizzly Bears", 2.0, "Creature — Bear", "common"),
-            ],
-            sideboard: vec![create_test_card("Negate", 2.0, "Instant", "common")],
-        };
-
-        let stats = calculate_deck_stats(&deck_list).unwrap();
-        assert_eq!(stats.total_cards, 3);
-        assert_eq!(stats.main_deck_cards, 2);
-        assert_eq!(stats.sideboard_cards, 1);
-        assert_eq!(stats.unique_cards, 3);
-        assert_eq!(stats.average_mana_value, 1.5); // (1 + 2) / 2
-    }
-
-    #[test]
-    fn test_extract_primary_type() {
-        assert_eq!(extract_primary_type("Creature — Human Wizard"), "Creature");
-        assert_eq!(extract_primary_type("Instant"), "Instant");
-        assert_eq!(extract_primary_type("Artifact - Equipment"), "Artifact");
-        assert_eq!(
-            extract_primary_type("Legendary Creature — Dragon"),
-            "Legendary Creature"
-        );
-    }
-}
-
 /// Check if input is a deck ID (16 hex characters)
 pub fn is_deck_id(input: &str) -> bool {
     let trimmed = input.trim();
@@ -684,3 +575,124 @@ pub fn convert_parsed_deck_to_deck_list(parsed_deck: &ParsedDeck) -> DeckList {
         sideboard: parsed_deck.sideboard.clone(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decks::DeckCard;
+    use crate::scryfall::types::Card;
+
+    fn create_test_card(name: &str, cmc: f64, type_line: &str, rarity: &str) -> DeckCard {
+        use serde_json::json;
+
+        let legalities = json!({
+            "standard": "legal",
+            "modern": "legal"
+        });
+
+        DeckCard {
+            quantity: 1,
+            name: name.to_string(),
+            set_code: None,
+            collector_number: None,
+            card_details: Some(Card {
+                object: "card".to_string(),
+                id: "test".to_string(),
+                oracle_id: None,
+                multiverse_ids: None,
+                mtgo_id: None,
+                arena_id: None,
+                tcgplayer_id: None,
+                cardmarket_id: None,
+                name: name.to_string(),
+                lang: "en".to_string(),
+                released_at: "2021-01-01".to_string(),
+                uri: "".to_string(),
+                scryfall_uri: "".to_string(),
+                layout: "normal".to_string(),
+                highres_image: false,
+                image_status: "".to_string(),
+                image_uris: None,
+                mana_cost: None,
+                cmc,
+                type_line: type_line.to_string(),
+                oracle_text: None,
+                power: None,
+                toughness: None,
+                loyalty: None,
+                colors: None,
+                color_identity: vec!["R".to_string()],
+                keywords: None,
+                produced_mana: None,
+                legalities,
+                games: vec![],
+                reserved: false,
+                foil: false,
+                nonfoil: true,
+                finishes: vec![],
+                oversized: false,
+                promo: false,
+                reprint: false,
+                variation: false,
+                set_id: "".to_string(),
+                set: "".to_string(),
+                set_name: "".to_string(),
+                set_type: "".to_string(),
+                set_uri: "".to_string(),
+                set_search_uri: "".to_string(),
+                scryfall_set_uri: "".to_string(),
+                rulings_uri: "".to_string(),
+                prints_search_uri: "".to_string(),
+                collector_number: "1".to_string(),
+                digital: false,
+                rarity: rarity.to_string(),
+                flavor_text: None,
+                card_back_id: None,
+                artist: None,
+                artist_ids: None,
+                illustration_id: None,
+                border_color: "black".to_string(),
+                frame: "2015".to_string(),
+                security_stamp: None,
+                full_art: false,
+                textless: false,
+                booster: true,
+                story_spotlight: false,
+                edhrec_rank: None,
+                penny_rank: None,
+                prices: None,
+                related_uris: None,
+                purchase_uris: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_calculate_basic_stats() {
+        let deck_list = DeckList {
+            main_deck: vec![
+                create_test_card("Lightning Bolt", 1.0, "Instant", "common"),
+                create_test_card("Grizzly Bears", 2.0, "Creature — Bear", "common"),
+            ],
+            sideboard: vec![create_test_card("Negate", 2.0, "Instant", "common")],
+        };
+
+        let stats = calculate_deck_stats(&deck_list).unwrap();
+        assert_eq!(stats.total_cards, 3);
+        assert_eq!(stats.main_deck_cards, 2);
+        assert_eq!(stats.sideboard_cards, 1);
+        assert_eq!(stats.unique_cards, 3);
+        assert_eq!(stats.average_mana_value, 1.5); // (1 + 2) / 2
+    }
+
+    #[test]
+    fn test_extract_primary_type() {
+        assert_eq!(extract_primary_type("Creature — Human Wizard"), "Creature");
+        assert_eq!(extract_primary_type("Instant"), "Instant");
+        assert_eq!(extract_primary_type("Artifact - Equipment"), "Artifact");
+        assert_eq!(
+            extract_primary_type("Legendary Creature — Dragon"),
+            "Legendary Creature"
+        );
+    }
+}
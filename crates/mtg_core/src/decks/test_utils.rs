@@ -0,0 +1,82 @@
+//! Card fixtures shared by the deck tests
+
+use crate::decks::DeckCard;
+use crate::scryfall::Card;
+
+/// Build a minimal Scryfall card with the given mana cost and type line
+pub fn card(name: &str, mana_cost: Option<&str>, cmc: f64, type_line: &str) -> Card {
+    let mut card: Card = serde_json::from_str(BASE_CARD).expect("test card should deserialize");
+    card.id = format!("test-{}", name.to_lowercase().replace(' ', "-"));
+    card.name = name.to_string();
+    card.mana_cost = mana_cost.map(String::from);
+    card.cmc = cmc;
+    card.type_line = type_line.to_string();
+    card
+}
+
+const BASE_CARD: &str = r#"{
+    "object": "card",
+    "id": "",
+    "name": "",
+    "lang": "en",
+    "released_at": "2024-01-01",
+    "uri": "",
+    "scryfall_uri": "",
+    "layout": "normal",
+    "highres_image": false,
+    "image_status": "missing",
+    "cmc": 0.0,
+    "type_line": "",
+    "color_identity": [],
+    "legalities": { "standard": "legal" },
+    "games": ["paper", "arena"],
+    "reserved": false,
+    "foil": false,
+    "nonfoil": true,
+    "finishes": ["nonfoil"],
+    "oversized": false,
+    "promo": false,
+    "reprint": false,
+    "variation": false,
+    "set_id": "",
+    "set": "tst",
+    "set_name": "Test Set",
+    "set_type": "expansion",
+    "set_uri": "",
+    "set_search_uri": "",
+    "scryfall_set_uri": "",
+    "rulings_uri": "",
+    "prints_search_uri": "",
+    "collector_number": "1",
+    "digital": false,
+    "rarity": "common",
+    "border_color": "black",
+    "frame": "2015",
+    "full_art": false,
+    "textless": false,
+    "booster": true,
+    "story_spotlight": false
+}"#;
+
+/// A land producing the given colors
+pub fn land(name: &str, colors: &[&str]) -> Card {
+    let mut land = card(name, None, 0.0, "Land");
+    land.produced_mana = Some(colors.iter().map(|c| c.to_string()).collect());
+    land
+}
+
+/// A spell with a mana cost
+pub fn spell(name: &str, mana_cost: &str, cmc: f64) -> Card {
+    card(name, Some(mana_cost), cmc, "Instant")
+}
+
+/// Wrap a card into a deck entry
+pub fn deck_card(quantity: u32, card: Card) -> DeckCard {
+    DeckCard {
+        quantity,
+        name: card.name.clone(),
+        set_code: Some(card.set.clone()),
+        collector_number: Some(card.collector_number.clone()),
+        card_details: Some(card),
+    }
+}
@@ -1,4 +1,4 @@
-use crate::decks::DeckList;
+use crate::decks::{DeckCard, DeckList};
 use crate::scryfall::ScryfallClient;
 use color_eyre::Result;
 use serde::Serialize;
@@ -18,6 +18,37 @@ pub fn generate_short_hash<T: Serialize>(value: &T) -> String {
         .collect::<String>()
 }
 
+/// Format a deck list in the standard `Deck` / `Sideboard` text format
+pub fn format_deck_list(deck_list: &DeckList) -> String {
+    let format_card = |card: &DeckCard| match (&card.set_code, &card.collector_number) {
+        (Some(set), Some(number)) => format!(
+            "{} {} ({}) {}",
+            card.quantity,
+            card.name,
+            set.to_uppercase(),
+            number
+        ),
+        (Some(set), None) => format!("{} {} ({})", card.quantity, card.name, set.to_uppercase()),
+        _ => format!("{} {}", card.quantity, card.name),
+    };
+
+    let mut output = String::from("Deck\n");
+    for card in &deck_list.main_deck {
+        output.push_str(&format_card(card));
+        output.push('\n');
+    }
+
+    if !deck_list.sideboard.is_empty() {
+        output.push_str("\nSideboard\n");
+        for card in &deck_list.sideboard {
+            output.push_str(&format_card(card));
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
 /// Fetch card details for all cards in a deck list using Scryfall API
 pub async fn fetch_card_details(
     mut deck_list: DeckList,
@@ -58,6 +89,14 @@ async fn fetch_single_card(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_format_deck_list_round_trips() {
+        let content =
+            "Deck\n4 Lightning Bolt (2XM) 141\n20 Mountain\n\nSideboard\n2 Abrade (DMU)\n";
+        let deck_list = crate::decks::parse_deck_list(content).unwrap();
+        assert_eq!(format_deck_list(&deck_list), content);
+    }
+
     #[test]
     fn test_generate_short_hash() {
         let test_data = "test string";
@@ -1,8 +1,11 @@
 pub mod cache;
+pub mod collection;
 pub mod companion;
 pub mod decks;
 pub mod gatherer;
+pub mod paths;
 pub mod scryfall;
+pub mod sealed;
 
 // Re-export the ScryfallClient for easy access from the binary
 pub use scryfall::{
@@ -33,11 +36,4 @@ pub use decks::{
 };
 
 // Re-export companion types for easy access
-pub use companion::{
-    event_parser::EventParser as CompanionEventParser,
-    player_parser::PlayerEventParser as CompanionPlayerEventParser,
-    types::{
-        format_mana_cost, to_camel_case, zone_to_string, DraftState, GameAction, MatchState,
-        ParsedEvent, PlayerEvent, PlayerInfo, RawLogEvent,
-    },
-};
+pub use companion::{parse_log as parse_arena_log, ArenaEvent, ArenaParser};
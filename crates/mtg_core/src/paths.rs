@@ -0,0 +1,100 @@
+//! Where the local data of the tool lives, and how it is read and written.
+//!
+//! Everything the tool keeps between runs (collection, match and draft history, deck
+//! library, ...) goes under one `mtg` directory in the user's data directory.
+
+use color_eyre::{eyre::eyre, Result};
+use serde::{de::DeserializeOwned, Serialize};
+use std::path::{Path, PathBuf};
+
+/// The `mtg` directory in the user's local data directory.
+///
+/// Follows `data_local_dir`, then `data_dir`, then a directory in the home folder. When no
+/// home directory is known the current directory is used, never a temporary one, so the
+/// data is not lost on the next reboot.
+pub fn data_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .or_else(dirs::data_dir)
+        .or_else(|| {
+            dirs::home_dir().map(|home| {
+                #[cfg(windows)]
+                return home.join("AppData").join("Local");
+
+                #[cfg(not(windows))]
+                return home.join(".local").join("share");
+            })
+        })
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("mtg")
+}
+
+/// A file in [`data_dir`], or the path in the environment variable `env` when it is set
+pub fn data_file(env: &str, name: &str) -> PathBuf {
+    if let Ok(path) = std::env::var(env) {
+        if !path.trim().is_empty() {
+            return PathBuf::from(path);
+        }
+    }
+
+    data_dir().join(name)
+}
+
+/// Read a JSON file, the default value when the file does not exist yet.
+///
+/// `what` names the file in error messages, e.g. "match history".
+pub fn read_json_or_default<T: DeserializeOwned + Default>(path: &Path, what: &str) -> Result<T> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| eyre!("Failed to read {} '{}': {}", what, path.display(), e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| eyre!("Failed to parse {} '{}': {}", what, path.display(), e))
+}
+
+/// Write a value as JSON, creating the parent directory when needed.
+///
+/// The JSON goes to a temporary file first that then replaces the file, so a failed write
+/// never corrupts what was there before.
+pub fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| eyre!("Failed to create directory '{}': {}", parent.display(), e))?;
+    }
+
+    let temp_path = path.with_extension("json.tmp");
+    std::fs::write(&temp_path, serde_json::to_string_pretty(value)?)
+        .map_err(|e| eyre!("Failed to write '{}': {}", temp_path.display(), e))?;
+    std::fs::rename(&temp_path, path)
+        .map_err(|e| eyre!("Failed to replace '{}': {}", path.display(), e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_json_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("data.json");
+
+        let empty: BTreeMap<String, u32> = read_json_or_default(&path, "test data").unwrap();
+        assert!(empty.is_empty());
+
+        let value = BTreeMap::from([("cards".to_string(), 60)]);
+        write_json_atomic(&path, &value).unwrap();
+        assert!(!path.with_extension("json.tmp").exists());
+        let read: BTreeMap<String, u32> = read_json_or_default(&path, "test data").unwrap();
+        assert_eq!(read, value);
+
+        std::fs::write(&path, "not json").unwrap();
+        let err = read_json_or_default::<BTreeMap<String, u32>>(&path, "test data").unwrap_err();
+        assert!(err.to_string().contains("Failed to parse test data"));
+    }
+}
@@ -31,6 +31,7 @@ pub struct Card {
     pub colors: Option<Vec<String>>,
     pub color_identity: Vec<String>,
     pub keywords: Option<Vec<String>>,
+    pub produced_mana: Option<Vec<String>>,
     pub legalities: Value,
     pub games: Vec<String>,
     pub reserved: bool,
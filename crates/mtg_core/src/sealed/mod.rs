@@ -0,0 +1,437 @@
+use crate::decks::{DeckCard, DeckList};
+use crate::scryfall::{Card, ScryfallClient, SearchParams};
+use color_eyre::{eyre::eyre, Result};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+/// Kind of booster to open
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BoosterKind {
+    /// Play booster: 14 cards with two wildcard slots
+    Play,
+    /// Classic draft booster: 15 cards
+    Draft,
+}
+
+impl BoosterKind {
+    /// Chance that the rare slot is upgraded to a mythic
+    pub fn default_mythic_rate(&self) -> f64 {
+        match self {
+            BoosterKind::Play => 1.0 / 7.0,
+            BoosterKind::Draft => 1.0 / 8.0,
+        }
+    }
+}
+
+impl fmt::Display for BoosterKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoosterKind::Play => write!(f, "play"),
+            BoosterKind::Draft => write!(f, "draft"),
+        }
+    }
+}
+
+impl FromStr for BoosterKind {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "play" => Ok(BoosterKind::Play),
+            "draft" => Ok(BoosterKind::Draft),
+            _ => Err(eyre!("Unknown booster kind '{}' (use play or draft)", s)),
+        }
+    }
+}
+
+/// The cards of a set that can be opened in boosters, split by slot
+#[derive(Debug, Clone, Default)]
+pub struct BoosterSheets {
+    pub set: String,
+    pub commons: Vec<Card>,
+    pub uncommons: Vec<Card>,
+    pub rares: Vec<Card>,
+    pub mythics: Vec<Card>,
+    pub basics: Vec<Card>,
+}
+
+impl BoosterSheets {
+    /// Build the sheets from a set's cards.
+    ///
+    /// Only cards Scryfall flags as found in boosters are used, one printing
+    /// per name. Basic lands fill the land slot whether they are flagged or not.
+    pub fn from_cards(set: &str, cards: Vec<Card>) -> Result<Self> {
+        let mut sheets = BoosterSheets {
+            set: set.to_lowercase(),
+            ..Default::default()
+        };
+        let mut seen = HashSet::new();
+
+        for card in cards {
+            let basic = card.type_line.contains("Basic") && card.type_line.contains("Land");
+            if !basic && !card.booster {
+                continue;
+            }
+            if !seen.insert(card.name.clone()) {
+                continue;
+            }
+            if basic {
+                sheets.basics.push(card);
+                continue;
+            }
+            match card.rarity.as_str() {
+                "common" => sheets.commons.push(card),
+                "uncommon" => sheets.uncommons.push(card),
+                "rare" => sheets.rares.push(card),
+                "mythic" => sheets.mythics.push(card),
+                _ => {}
+            }
+        }
+
+        if sheets.commons.is_empty()
+            || sheets.uncommons.is_empty()
+            || (sheets.rares.is_empty() && sheets.mythics.is_empty())
+        {
+            return Err(eyre!(
+                "Set '{}' has no booster commons, uncommons and rares to open",
+                set
+            ));
+        }
+
+        Ok(sheets)
+    }
+}
+
+/// A card opened in a booster
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoosterCard {
+    pub name: String,
+    pub set_code: String,
+    pub collector_number: String,
+    pub rarity: String,
+}
+
+impl From<&Card> for BoosterCard {
+    fn from(card: &Card) -> Self {
+        Self {
+            name: card.name.clone(),
+            set_code: card.set.clone(),
+            collector_number: card.collector_number.clone(),
+            rarity: card.rarity.clone(),
+        }
+    }
+}
+
+/// One opened booster
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Booster {
+    pub kind: BoosterKind,
+    pub cards: Vec<BoosterCard>,
+}
+
+/// Settings for opening boosters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SealedConfig {
+    pub kind: BoosterKind,
+    pub packs: u32,
+    /// Seed for the random number generator, a random seed is used when unset
+    pub seed: Option<u64>,
+    /// Chance of a mythic in the rare slot, the booster kind's rate when unset
+    pub mythic_rate: Option<f64>,
+}
+
+impl Default for SealedConfig {
+    fn default() -> Self {
+        Self {
+            kind: BoosterKind::Play,
+            packs: 6,
+            seed: None,
+            mythic_rate: None,
+        }
+    }
+}
+
+/// The boosters opened for a sealed or draft pool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SealedPool {
+    pub set: String,
+    pub kind: BoosterKind,
+    /// Seed that regenerates this pool
+    pub seed: u64,
+    pub packs: Vec<Booster>,
+}
+
+impl SealedPool {
+    pub fn cards(&self) -> impl Iterator<Item = &BoosterCard> {
+        self.packs.iter().flat_map(|pack| pack.cards.iter())
+    }
+
+    pub fn total_cards(&self) -> usize {
+        self.packs.iter().map(|pack| pack.cards.len()).sum()
+    }
+
+    /// Cards opened per rarity
+    pub fn by_rarity(&self) -> BTreeMap<String, u32> {
+        let mut counts = BTreeMap::new();
+        for card in self.cards() {
+            *counts.entry(card.rarity.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// The pool as a deck list with every card in the main deck, sorted by name
+    pub fn to_deck_list(&self) -> DeckList {
+        let mut cards: BTreeMap<&str, DeckCard> = BTreeMap::new();
+        for card in self.cards() {
+            cards
+                .entry(&card.name)
+                .and_modify(|entry| entry.quantity += 1)
+                .or_insert_with(|| DeckCard {
+                    quantity: 1,
+                    name: card.name.clone(),
+                    set_code: Some(card.set_code.clone()),
+                    collector_number: Some(card.collector_number.clone()),
+                    card_details: None,
+                });
+        }
+
+        DeckList {
+            main_deck: cards.into_values().collect(),
+            sideboard: Vec::new(),
+        }
+    }
+}
+
+/// Opens boosters from a set's sheets
+pub struct BoosterGenerator<'a> {
+    sheets: &'a BoosterSheets,
+    kind: BoosterKind,
+    mythic_rate: f64,
+    rng: ChaCha8Rng,
+}
+
+impl<'a> BoosterGenerator<'a> {
+    pub fn new(sheets: &'a BoosterSheets, kind: BoosterKind, seed: u64) -> Self {
+        Self {
+            sheets,
+            kind,
+            mythic_rate: kind.default_mythic_rate(),
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn mythic_rate(mut self, rate: f64) -> Self {
+        self.mythic_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Open one booster
+    pub fn booster(&mut self) -> Booster {
+        let mut cards = Vec::new();
+
+        match self.kind {
+            BoosterKind::Draft => {
+                self.pick(&self.sheets.commons, 10, &mut cards);
+                self.pick(&self.sheets.uncommons, 3, &mut cards);
+                self.pick_rare(&mut cards);
+                self.pick_land(&mut cards);
+            }
+            BoosterKind::Play => {
+                self.pick(&self.sheets.commons, 7, &mut cards);
+                self.pick(&self.sheets.uncommons, 3, &mut cards);
+                self.pick_rare(&mut cards);
+                self.pick_wildcard(&mut cards);
+                self.pick_wildcard(&mut cards);
+                self.pick_land(&mut cards);
+            }
+        }
+
+        Booster {
+            kind: self.kind,
+            cards,
+        }
+    }
+
+    /// Pick distinct cards from a sheet, repeating only when the sheet is too small
+    fn pick(&mut self, sheet: &[Card], count: usize, cards: &mut Vec<BoosterCard>) {
+        let mut remaining = count;
+        while remaining > 0 && !sheet.is_empty() {
+            let take = remaining.min(sheet.len());
+            cards.extend(
+                sheet
+                    .choose_multiple(&mut self.rng, take)
+                    .map(BoosterCard::from),
+            );
+            remaining -= take;
+        }
+    }
+
+    fn pick_rare(&mut self, cards: &mut Vec<BoosterCard>) {
+        let mythic = !self.sheets.mythics.is_empty()
+            && (self.sheets.rares.is_empty() || self.rng.gen_bool(self.mythic_rate));
+        let sheet = if mythic {
+            &self.sheets.mythics
+        } else {
+            &self.sheets.rares
+        };
+        self.pick(sheet, 1, cards);
+    }
+
+    /// A slot of any rarity, weighted towards commons
+    fn pick_wildcard(&mut self, cards: &mut Vec<BoosterCard>) {
+        let roll: f64 = self.rng.gen();
+        if roll < 1.0 / 6.0 {
+            self.pick_rare(cards);
+        } else if roll < 0.5 {
+            self.pick(&self.sheets.uncommons, 1, cards);
+        } else {
+            self.pick(&self.sheets.commons, 1, cards);
+        }
+    }
+
+    /// A basic land, or a common for sets without basics
+    fn pick_land(&mut self, cards: &mut Vec<BoosterCard>) {
+        if self.sheets.basics.is_empty() {
+            self.pick(&self.sheets.commons, 1, cards);
+        } else {
+            self.pick(&self.sheets.basics, 1, cards);
+        }
+    }
+}
+
+/// Open the boosters of a sealed or draft pool
+pub fn generate_pool(sheets: &BoosterSheets, config: &SealedConfig) -> SealedPool {
+    let seed = config.seed.unwrap_or_else(rand::random);
+    let mut generator = BoosterGenerator::new(sheets, config.kind, seed);
+    if let Some(rate) = config.mythic_rate {
+        generator = generator.mythic_rate(rate);
+    }
+
+    SealedPool {
+        set: sheets.set.clone(),
+        kind: config.kind,
+        seed,
+        packs: (0..config.packs).map(|_| generator.booster()).collect(),
+    }
+}
+
+/// Fetch every printing in a set from Scryfall, following all result pages
+pub async fn fetch_set_cards(client: &ScryfallClient, set: &str) -> Result<Vec<Card>> {
+    let mut cards = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let response = client
+            .search_cards(SearchParams {
+                q: format!("e:{}", set.to_lowercase()),
+                unique: Some("prints".to_string()),
+                order: Some("set".to_string()),
+                page: Some(page),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| eyre!("Failed to fetch the cards of set '{}': {}", set, e))?;
+
+        cards.extend(response.data);
+        if !response.has_more {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(cards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decks::test_utils::card;
+
+    fn set_cards() -> Vec<Card> {
+        let mut cards = Vec::new();
+        for (rarity, count) in [("common", 20), ("uncommon", 10), ("rare", 5), ("mythic", 2)] {
+            for index in 0..count {
+                let mut card = card(&format!("{rarity} {index}"), Some("{1}"), 1.0, "Creature");
+                card.rarity = rarity.to_string();
+                cards.push(card);
+            }
+        }
+        let mut promo = card("Box Topper", Some("{1}"), 1.0, "Artifact");
+        promo.rarity = "mythic".to_string();
+        promo.booster = false;
+        cards.push(promo);
+        let mut forest = card("Forest", None, 0.0, "Basic Land — Forest");
+        forest.booster = false;
+        cards.push(forest);
+        cards
+    }
+
+    #[test]
+    fn test_booster_slots() {
+        let sheets = BoosterSheets::from_cards("TST", set_cards()).unwrap();
+        assert_eq!(sheets.mythics.len(), 2);
+        assert_eq!(sheets.basics.len(), 1);
+
+        let mut generator = BoosterGenerator::new(&sheets, BoosterKind::Draft, 7);
+        let booster = generator.booster();
+        assert_eq!(booster.cards.len(), 15);
+        let commons = booster.cards[..10]
+            .iter()
+            .filter(|card| card.rarity == "common")
+            .map(|card| &card.name)
+            .collect::<HashSet<_>>();
+        assert_eq!(commons.len(), 10);
+        assert_eq!(booster.cards.last().unwrap().name, "Forest");
+        assert!(booster.cards.iter().all(|card| card.name != "Box Topper"));
+
+        let mut generator = BoosterGenerator::new(&sheets, BoosterKind::Play, 7).mythic_rate(1.0);
+        let booster = generator.booster();
+        assert_eq!(booster.cards.len(), 14);
+        assert_eq!(booster.cards[10].rarity, "mythic");
+    }
+
+    #[test]
+    fn test_non_booster_printing_first() {
+        let mut cards = set_cards();
+        let mut promo = card("common 0", Some("{1}"), 1.0, "Creature");
+        promo.rarity = "rare".to_string();
+        promo.booster = false;
+        cards.insert(0, promo);
+
+        let sheets = BoosterSheets::from_cards("tst", cards).unwrap();
+        assert_eq!(sheets.commons.len(), 20);
+        assert!(sheets.commons.iter().any(|card| card.name == "common 0"));
+        assert!(sheets.rares.iter().all(|card| card.name != "common 0"));
+    }
+
+    #[test]
+    fn test_seeded_pool() {
+        let sheets = BoosterSheets::from_cards("tst", set_cards()).unwrap();
+        let config = SealedConfig {
+            seed: Some(42),
+            ..Default::default()
+        };
+
+        let pool = generate_pool(&sheets, &config);
+        let again = generate_pool(&sheets, &config);
+        assert_eq!(pool.seed, 42);
+        assert_eq!(pool.total_cards(), 84);
+        assert_eq!(
+            pool.cards().collect::<Vec<_>>(),
+            again.cards().collect::<Vec<_>>()
+        );
+
+        let deck = pool.to_deck_list();
+        assert_eq!(
+            deck.main_deck.iter().map(|card| card.quantity).sum::<u32>(),
+            84
+        );
+        assert!(BoosterSheets::from_cards("empty", Vec::new()).is_err());
+    }
+}